@@ -0,0 +1,97 @@
+import {
+    init,
+    ColliderDesc,
+    EventQueue,
+    ImpulseJoint,
+    JointData,
+    RigidBody,
+    RigidBodyDesc,
+    Vector3,
+    World,
+} from "../pkg3d";
+
+describe("3d/BreakableJoints", () => {
+    let world: World;
+    let eventQueue: EventQueue;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+        eventQueue = new EventQueue(true);
+    });
+
+    afterEach(() => {
+        eventQueue.free();
+        world.free();
+    });
+
+    function createPendulum(x: number): [RigidBody, RigidBody, ImpulseJoint] {
+        const anchor = world.createRigidBody(
+            RigidBodyDesc.fixed().setTranslation(x, 5.0, 0.0),
+        );
+        const bob = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(x, 4.0, 0.0),
+        );
+        world.createCollider(ColliderDesc.ball(0.5), bob);
+        const joint = world.createImpulseJoint(
+            JointData.spherical(
+                new Vector3(0.0, -1.0, 0.0),
+                new Vector3(0.0, 0.0, 0.0),
+            ),
+            anchor,
+            bob,
+            true,
+        );
+        return [anchor, bob, joint];
+    }
+
+    test("thresholds", () => {
+        const [, , joint] = createPendulum(0.0);
+        expect(joint.breakForce()).toBe(Infinity);
+        expect(joint.breakTorque()).toBe(Infinity);
+
+        joint.setBreakThresholds(10.0, Infinity);
+        expect(joint.breakForce()).toBeCloseTo(10.0);
+        expect(joint.breakTorque()).toBe(Infinity);
+    });
+
+    test("joint broken events", () => {
+        // The weight of the bobs is about 5N.
+        const [anchor, bob, weakJoint] = createPendulum(0.0);
+        const [, , strongJoint] = createPendulum(5.0);
+        weakJoint.setBreakThresholds(1.0, Infinity);
+        strongJoint.setBreakThresholds(1000.0, Infinity);
+        const weakHandle = weakJoint.handle;
+
+        let events = [];
+
+        for (let i = 0; i < 10; ++i) {
+            world.step(eventQueue);
+            eventQueue.drainJointBrokenEvents((event) => {
+                events.push({
+                    joint: event.joint(),
+                    body1: event.body1(),
+                    body2: event.body2(),
+                    force: event.force(),
+                });
+            });
+        }
+
+        expect(events).toHaveLength(1);
+        expect(events[0].joint).toBe(weakHandle);
+        expect(events[0].body1).toBe(anchor.handle);
+        expect(events[0].body2).toBe(bob.handle);
+        expect(events[0].force).toBeGreaterThan(1.0);
+
+        // The broken joint is removed, the other one still holds its bob.
+        expect(world.impulseJoints.contains(weakHandle)).toBe(false);
+        expect(world.impulseJoints.contains(strongJoint.handle)).toBe(true);
+        expect(world.impulseJoints.len()).toBe(1);
+        expect(bob.translation().y).toBeLessThan(4.0);
+    });
+});
@@ -0,0 +1,86 @@
+import {
+    init,
+    ColliderDesc,
+    RigidBody,
+    RigidBodyDesc,
+    Vector3,
+    World,
+} from "../pkg3d";
+
+function createScene(): [World, RigidBody[]] {
+    const world = new World(new Vector3(0, -9.81, 0));
+    const bodies = [];
+    world.createCollider(ColliderDesc.cuboid(10.0, 0.1, 10.0));
+
+    // Two stacks far enough from each other to belong to distinct islands.
+    for (const x of [-5.0, 5.0]) {
+        for (let i = 0; i < 2; ++i) {
+            const body = world.createRigidBody(
+                RigidBodyDesc.dynamic().setTranslation(x, 0.6 + i, 0.0),
+            );
+            world.createCollider(ColliderDesc.cuboid(0.5, 0.5, 0.5), body);
+            bodies.push(body);
+        }
+    }
+
+    return [world, bodies];
+}
+
+describe("3d/Checksum", () => {
+    let world1: World;
+    let world2: World;
+    let bodies2: RigidBody[];
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        [world1] = createScene();
+        [world2, bodies2] = createScene();
+    });
+
+    afterEach(() => {
+        world1.free();
+        world2.free();
+    });
+
+    test("identical simulations", () => {
+        for (let i = 0; i < 20; ++i) {
+            world1.step();
+            world2.step();
+            expect(world1.checksum().hash).toBe(world2.checksum().hash);
+        }
+
+        expect(world1.checksum().hash).toMatch(/^[0-9a-f]{16}$/);
+    });
+
+    test("detects desynchronizations", () => {
+        world1.step();
+        world2.step();
+        bodies2[1].setLinvel(new Vector3(0.0, 1.0, 0.0), true);
+
+        expect(world1.checksum().hash).not.toBe(world2.checksum().hash);
+    });
+
+    test("islands", () => {
+        world1.step();
+        world2.step();
+        bodies2[3].setLinvel(new Vector3(0.0, 1.0, 0.0), true);
+
+        const checksum1 = world1.checksum(true);
+        const checksum2 = world2.checksum(true);
+        expect(world1.checksum().islands).toHaveLength(0);
+        expect(checksum1.islands).toHaveLength(2);
+        expect(checksum2.islands).toHaveLength(2);
+
+        // Only the island containing the modified rigid-body differs.
+        const diverged = checksum1.islands.filter(
+            (island, i) => island.hash != checksum2.islands[i].hash,
+        );
+        expect(diverged).toHaveLength(1);
+        expect(diverged[0].bodies).toContain(bodies2[3].handle);
+    });
+});
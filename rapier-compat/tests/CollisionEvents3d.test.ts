@@ -0,0 +1,107 @@
+import {
+    init,
+    ActiveEvents,
+    Collider,
+    ColliderDesc,
+    EventQueue,
+    RigidBodyDesc,
+    Vector3,
+    World,
+} from "../pkg3d";
+
+describe("3d/CollisionEvents", () => {
+    let world: World;
+    let eventQueue: EventQueue;
+    let ball: Collider;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+        eventQueue = new EventQueue(true);
+        const body = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(0.0, 1.6, 0.0),
+        );
+        ball = world.createCollider(
+            ColliderDesc.ball(0.5).setActiveEvents(
+                ActiveEvents.COLLISION_EVENTS,
+            ),
+            body,
+        );
+    });
+
+    afterEach(() => {
+        eventQueue.free();
+        world.free();
+    });
+
+    function collectEvents(numSteps: number) {
+        let events = [];
+
+        for (let i = 0; i < numSteps; ++i) {
+            world.step(eventQueue);
+            eventQueue.drainCollisionEvents((handle1, handle2, started, e) => {
+                events.push({
+                    handle1,
+                    handle2,
+                    started,
+                    sensor: e.sensor(),
+                    removed: e.removed(),
+                    contactPoint: e.contactPoint(),
+                    contactNormal: e.contactNormal(),
+                    relativeVelocity: e.relativeVelocity(),
+                });
+            });
+        }
+
+        return events;
+    }
+
+    test("contact data", () => {
+        const ground = world.createCollider(
+            ColliderDesc.cuboid(10.0, 0.1, 10.0),
+        );
+
+        const events = collectEvents(60);
+        expect(events.length).toBeGreaterThan(0);
+
+        const event = events[0];
+        expect(event.started).toBe(true);
+        expect(event.sensor).toBe(false);
+        expect(event.removed).toBe(false);
+        expect([event.handle1, event.handle2]).toContain(ground.handle);
+        expect([event.handle1, event.handle2]).toContain(ball.handle);
+
+        // The normal points outward the first collider, and the second
+        // collider moves toward the first one.
+        const sign = event.handle1 == ground.handle ? 1 : -1;
+        expect(event.contactPoint.y).toBeCloseTo(0.1, 1);
+        expect(event.contactNormal.y).toBeCloseTo(sign);
+        expect(event.relativeVelocity.y * sign).toBeLessThan(-1.0);
+
+        // Removing a collider stops the collision.
+        world.removeCollider(ball, true);
+        const stopEvents = collectEvents(1);
+        expect(stopEvents).toHaveLength(1);
+        expect(stopEvents[0].started).toBe(false);
+        expect(stopEvents[0].removed).toBe(true);
+        expect(stopEvents[0].contactPoint).toBeNull();
+    });
+
+    test("sensors", () => {
+        world.createCollider(
+            ColliderDesc.cuboid(10.0, 0.1, 10.0).setSensor(true),
+        );
+
+        const events = collectEvents(60);
+        expect(events.length).toBeGreaterThan(0);
+        expect(events[0].started).toBe(true);
+        expect(events[0].sensor).toBe(true);
+        expect(events[0].contactPoint).toBeNull();
+        expect(events[0].contactNormal).toBeNull();
+    });
+});
@@ -0,0 +1,101 @@
+import {
+    init,
+    ActiveHooks,
+    Collider,
+    ColliderDesc,
+    PhysicsHooks,
+    RigidBody,
+    RigidBodyDesc,
+    SolverFlags,
+    TempContactModificationContext,
+    Vector3,
+    World,
+} from "../pkg3d";
+
+describe("3d/ContactModification", () => {
+    let world: World;
+    let ground: Collider;
+    let box: RigidBody;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+        ground = world.createCollider(
+            ColliderDesc.cuboid(10.0, 0.1, 10.0).setActiveHooks(
+                ActiveHooks.MODIFY_SOLVER_CONTACTS,
+            ),
+        );
+        box = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(0.0, 0.6, 0.0),
+        );
+        world.createCollider(ColliderDesc.cuboid(0.5, 0.5, 0.5), box);
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    function hooks(
+        modify: (context: TempContactModificationContext) => void,
+    ): PhysicsHooks {
+        return {
+            filterContactPair: () => SolverFlags.COMPUTE_IMPULSE,
+            filterIntersectionPair: () => true,
+            modifySolverContacts: modify,
+        };
+    }
+
+    function simulate(physicsHooks: PhysicsHooks) {
+        for (let i = 0; i < 60; ++i) {
+            world.step(undefined, physicsHooks);
+        }
+    }
+
+    test("context", () => {
+        let numCalls = 0;
+
+        simulate(
+            hooks((context) => {
+                ++numCalls;
+                const colliders = [context.collider1(), context.collider2()];
+                expect(colliders).toContain(ground.handle);
+                expect([context.body1(), context.body2()]).toContain(
+                    box.handle,
+                );
+                expect(context.numSolverContacts()).toBeGreaterThan(0);
+                expect(Math.abs(context.normal().y)).toBeCloseTo(1.0);
+            }),
+        );
+
+        // The box rests on the ground.
+        expect(numCalls).toBeGreaterThan(0);
+        expect(box.translation().y).toBeCloseTo(0.6, 1);
+    });
+
+    test("removed contacts", () => {
+        simulate(hooks((context) => context.clearSolverContacts()));
+
+        // The box falls through the ground.
+        expect(box.translation().y).toBeLessThan(0.0);
+    });
+
+    test("tangent velocity", () => {
+        const vel = new Vector3(2.0, 0.0, 0.0);
+        simulate(
+            hooks((context) => {
+                for (let i = 0; i < context.numSolverContacts(); ++i) {
+                    context.setSolverContactTangentVelocity(i, vel);
+                }
+            }),
+        );
+
+        // The ground behaves like a conveyor belt.
+        expect(Math.abs(box.translation().x)).toBeGreaterThan(0.1);
+        expect(box.translation().y).toBeCloseTo(0.6, 1);
+    });
+});
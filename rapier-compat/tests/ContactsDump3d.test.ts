@@ -0,0 +1,107 @@
+import {
+    init,
+    ColliderDesc,
+    Cuboid,
+    Quaternion,
+    RigidBodyDesc,
+    ShapeType,
+    Vector3,
+    World,
+} from "../pkg3d";
+
+describe("3d/ContactsDump", () => {
+    let world: World;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    test("layout", () => {
+        world.createCollider(ColliderDesc.cuboid(10.0, 0.1, 10.0));
+        for (const x of [-2.0, 2.0]) {
+            const body = world.createRigidBody(
+                RigidBodyDesc.dynamic().setTranslation(x, 0.6, 0.0),
+            );
+            world.createCollider(ColliderDesc.cuboid(0.5, 0.5, 0.5), body);
+        }
+
+        world.step();
+        const dump = world.dumpContacts();
+        const numManifolds = dump.numManifolds();
+        const numContacts = dump.numContacts();
+
+        expect(numManifolds).toBe(2);
+        expect(dump.manifoldColliders()).toHaveLength(2 * numManifolds);
+        expect(dump.manifoldNormals()).toHaveLength(3 * numManifolds);
+        expect(dump.manifoldOffsets()).toHaveLength(numManifolds + 1);
+        expect(dump.manifoldOffsets()[0]).toBe(0);
+        expect(dump.manifoldOffsets()[numManifolds]).toBe(numContacts);
+        expect(dump.points1()).toHaveLength(3 * numContacts);
+        expect(dump.points2()).toHaveLength(3 * numContacts);
+        expect(dump.dists()).toHaveLength(numContacts);
+        expect(dump.impulses()).toHaveLength(numContacts);
+
+        // All the contact points lie on the top face of the ground.
+        const points1 = dump.points1();
+        for (let i = 0; i < numContacts; ++i) {
+            expect(points1[3 * i + 1]).toBeCloseTo(0.1, 1);
+        }
+
+        // The dump can be reused.
+        expect(world.dumpContacts(dump)).toBe(dump);
+        expect(dump.numManifolds()).toBe(numManifolds);
+        dump.free();
+    });
+
+    test("compound colliders", () => {
+        // The second part is flipped upside down, so its local normals are
+        // flipped too.
+        const compound = world.createCollider(
+            ColliderDesc.compound(
+                [new Cuboid(1.0, 0.1, 1.0), new Cuboid(1.0, 0.1, 1.0)],
+                [new Vector3(-5.0, 0.0, 0.0), new Vector3(5.0, 0.0, 0.0)],
+                [
+                    new Quaternion(0.0, 0.0, 0.0, 1.0),
+                    new Quaternion(0.0, 0.0, 1.0, 0.0),
+                ],
+            ),
+        );
+        const body = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(5.0, 0.6, 0.0),
+        );
+        world.createCollider(ColliderDesc.ball(0.5), body);
+        expect(compound.shape.type).toBe(ShapeType.Compound);
+
+        world.step();
+        const dump = world.dumpContacts();
+        expect(dump.numManifolds()).toBe(1);
+        expect(dump.numContacts()).toBeGreaterThan(0);
+
+        // The normal points outward the first collider of the manifold.
+        const sign = dump.manifoldColliders()[0] == compound.handle ? 1 : -1;
+        const normal = dump.manifoldNormals();
+        expect(normal[0]).toBeCloseTo(0.0);
+        expect(normal[1]).toBeCloseTo(sign);
+        expect(normal[2]).toBeCloseTo(0.0);
+
+        // The contact points are located on the second part, not at the
+        // collider origin.
+        for (const points of [dump.points1(), dump.points2()]) {
+            expect(points[0]).toBeCloseTo(5.0, 1);
+            expect(points[1]).toBeCloseTo(0.1, 1);
+            expect(points[2]).toBeCloseTo(0.0, 1);
+        }
+
+        dump.free();
+    });
+});
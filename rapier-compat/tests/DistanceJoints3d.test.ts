@@ -0,0 +1,123 @@
+import {
+    init,
+    ColliderDesc,
+    JointData,
+    JointType,
+    RigidBody,
+    RigidBodyDesc,
+    RopeImpulseJoint,
+    SpringImpulseJoint,
+    Vector3,
+    World,
+} from "../pkg3d";
+
+describe("3d/DistanceJoints", () => {
+    let world: World;
+    let anchor: RigidBody;
+    let bob: RigidBody;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+        anchor = world.createRigidBody(
+            RigidBodyDesc.fixed().setTranslation(0.0, 5.0, 0.0),
+        );
+        bob = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(0.0, 4.5, 0.0),
+        );
+        world.createCollider(ColliderDesc.ball(0.1).setMass(1.0), bob);
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    function distance(): number {
+        const t1 = anchor.translation();
+        const t2 = bob.translation();
+        return Math.hypot(t2.x - t1.x, t2.y - t1.y, t2.z - t1.z);
+    }
+
+    test("rope joints", () => {
+        const origin = new Vector3(0.0, 0.0, 0.0);
+        const joint = world.createImpulseJoint(
+            JointData.rope(2.0, origin, origin),
+            anchor,
+            bob,
+            true,
+        ) as RopeImpulseJoint;
+        expect(joint.type()).toBe(JointType.Rope);
+        expect(joint.length()).toBeCloseTo(2.0);
+
+        // The rope is slack: the bob falls freely.
+        world.step();
+        expect(bob.translation().y).toBeLessThan(4.5);
+
+        for (let i = 0; i < 120; ++i) {
+            world.step();
+        }
+
+        // The bob hangs at the end of the rope, which is only slightly
+        // stretched since it is corrected progressively.
+        expect(distance()).toBeGreaterThan(1.95);
+        expect(distance()).toBeLessThan(2.05);
+
+        joint.setLength(3.0);
+        expect(joint.length()).toBeCloseTo(3.0);
+
+        for (let i = 0; i < 120; ++i) {
+            world.step();
+        }
+
+        expect(distance()).toBeGreaterThan(2.95);
+        expect(distance()).toBeLessThan(3.05);
+    });
+
+    test("spring joints", () => {
+        const origin = new Vector3(0.0, 0.0, 0.0);
+        const joint = world.createImpulseJoint(
+            JointData.spring(1.0, 100.0, 5.0, origin, origin),
+            anchor,
+            bob,
+            true,
+        ) as SpringImpulseJoint;
+        expect(joint.type()).toBe(JointType.Spring);
+        expect(joint.restLength()).toBeCloseTo(1.0);
+        expect(joint.stiffness()).toBeCloseTo(100.0);
+        expect(joint.damping()).toBeCloseTo(5.0);
+
+        for (let i = 0; i < 300; ++i) {
+            world.step();
+        }
+
+        // The bob settles where the spring balances its weight:
+        // 1 + 9.81 / 100 below the anchor.
+        expect(bob.translation().x).toBeCloseTo(0.0);
+        expect(distance()).toBeCloseTo(1.0981, 2);
+    });
+
+    test("multibody rope and spring joints", () => {
+        const origin = new Vector3(0.0, 0.0, 0.0);
+        expect(() =>
+            world.createMultibodyJoint(
+                JointData.rope(2.0, origin, origin),
+                anchor,
+                bob,
+                true,
+            ),
+        ).toThrow(/can only be created as impulse joints/);
+        expect(() =>
+            world.createMultibodyJoint(
+                JointData.spring(1.0, 100.0, 5.0, origin, origin),
+                anchor,
+                bob,
+                true,
+            ),
+        ).toThrow(/can only be created as impulse joints/);
+    });
+});
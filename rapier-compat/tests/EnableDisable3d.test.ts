@@ -0,0 +1,128 @@
+import {
+    init,
+    ColliderDesc,
+    JointData,
+    RigidBodyDesc,
+    RigidBodyType,
+    Vector3,
+    World,
+} from "../pkg3d";
+
+describe("3d/EnableDisable", () => {
+    let world: World;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    test("disabled colliders", () => {
+        const ground = world.createCollider(
+            ColliderDesc.cuboid(10.0, 0.1, 10.0).setCollisionGroups(0x0001ffff),
+        );
+        const ball = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(0.0, 1.0, 0.0),
+        );
+        world.createCollider(ColliderDesc.ball(0.5), ball);
+
+        ground.setEnabled(false);
+        expect(ground.isEnabled()).toBe(false);
+
+        for (let i = 0; i < 60; ++i) {
+            world.step();
+        }
+
+        // The ball fell through the disabled ground.
+        expect(ball.translation().y).toBeLessThan(-1.0);
+
+        ground.setEnabled(true);
+        expect(ground.isEnabled()).toBe(true);
+        expect(ground.collisionGroups()).toBe(0x0001ffff);
+    });
+
+    test("collider user data", () => {
+        const collider = world.createCollider(ColliderDesc.ball(0.5));
+
+        collider.setUserData(42);
+        expect(collider.userData()).toBe(42);
+
+        collider.setUserData128(new Uint32Array([1, 2, 3, 4]));
+        expect(Array.from(collider.userData128())).toEqual([1, 2, 3, 4]);
+        expect(collider.userData()).toBe(1);
+    });
+
+    test("disabled rigid-bodies", () => {
+        const body = world.createRigidBody(
+            RigidBodyDesc.dynamic()
+                .setTranslation(0.0, 5.0, 0.0)
+                .setLinvel(1.0, 0.0, 0.0),
+        );
+        world.createCollider(ColliderDesc.ball(0.5), body);
+
+        body.setEnabled(false);
+        expect(body.isEnabled()).toBe(false);
+        expect(body.bodyType()).toBe(RigidBodyType.Dynamic);
+        expect(body.isDynamic()).toBe(true);
+
+        for (let i = 0; i < 30; ++i) {
+            world.step();
+        }
+
+        // The disabled rigid-body neither fell nor moved.
+        expect(body.translation().x).toBeCloseTo(0.0);
+        expect(body.translation().y).toBeCloseTo(5.0);
+
+        body.setEnabled(true);
+        expect(body.isEnabled()).toBe(true);
+        expect(body.linvel().x).toBeCloseTo(1.0);
+
+        world.step();
+        expect(body.translation().y).toBeLessThan(5.0);
+    });
+
+    test("joints attached to disabled rigid-bodies", () => {
+        const anchor = world.createRigidBody(
+            RigidBodyDesc.fixed().setTranslation(0.0, 5.0, 0.0),
+        );
+        const bob = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(0.0, 4.0, 0.0),
+        );
+        world.createCollider(ColliderDesc.ball(0.1), bob);
+        const joint = world.createImpulseJoint(
+            JointData.spherical(
+                new Vector3(0.0, -1.0, 0.0),
+                new Vector3(0.0, 0.0, 0.0),
+            ),
+            anchor,
+            bob,
+            true,
+        );
+
+        for (let i = 0; i < 60; ++i) {
+            world.step();
+        }
+
+        // The bob hangs from the anchor.
+        expect(bob.translation().y).toBeCloseTo(4.0, 1);
+
+        anchor.setEnabled(false);
+
+        for (let i = 0; i < 60; ++i) {
+            world.step();
+        }
+
+        // The joint is ignored, but still reports its original data.
+        expect(bob.translation().y).toBeLessThan(3.0);
+        expect(world.impulseJoints.contains(joint.handle)).toBe(true);
+        expect(joint.anchor1().y).toBeCloseTo(-1.0);
+    });
+});
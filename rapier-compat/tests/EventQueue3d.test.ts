@@ -0,0 +1,63 @@
+import {
+    init,
+    ActiveEvents,
+    ColliderDesc,
+    EventQueue,
+    RigidBodyDesc,
+    Vector3,
+    World,
+} from "../pkg3d";
+
+describe("3d/EventQueue", () => {
+    let world: World;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+        world.createCollider(ColliderDesc.cuboid(10.0, 0.1, 10.0));
+
+        // Three balls touching the ground at the same time.
+        for (const x of [-2.0, 0.0, 2.0]) {
+            const body = world.createRigidBody(
+                RigidBodyDesc.dynamic().setTranslation(x, 0.55, 0.0),
+            );
+            world.createCollider(
+                ColliderDesc.ball(0.5).setActiveEvents(
+                    ActiveEvents.COLLISION_EVENTS,
+                ),
+                body,
+            );
+        }
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    test("bounded capacity", () => {
+        const eventQueue = EventQueue.withCapacity(false, 1);
+        world.step(eventQueue);
+
+        let numEvents = 0;
+        eventQueue.drainCollisionEvents(() => ++numEvents);
+        expect(numEvents).toBe(1);
+        expect(eventQueue.numDroppedEvents()).toBe(2);
+
+        world.step(eventQueue);
+        eventQueue.clear();
+        eventQueue.drainCollisionEvents(() => ++numEvents);
+        expect(numEvents).toBe(1);
+        eventQueue.free();
+    });
+
+    test("rejects a zero capacity", () => {
+        expect(() => EventQueue.withCapacity(true, 0)).toThrow(
+            /must be greater than 0/,
+        );
+    });
+});
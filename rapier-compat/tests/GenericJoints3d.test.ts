@@ -0,0 +1,127 @@
+import {
+    init,
+    ColliderDesc,
+    GenericImpulseJoint,
+    JointAxesMask,
+    JointAxis,
+    JointData,
+    Quaternion,
+    RigidBody,
+    RigidBodyDesc,
+    Rotation,
+    Vector3,
+    World,
+} from "../pkg3d";
+
+describe("3d/GenericJoints", () => {
+    let world: World;
+    let ground: RigidBody;
+    let body: RigidBody;
+
+    // A cylindrical joint: only the translation along, and the rotation
+    // around, the `X` axis of the local frames are free.
+    const lockedAxes =
+        JointAxesMask.Y |
+        JointAxesMask.Z |
+        JointAxesMask.AngY |
+        JointAxesMask.AngZ;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+        ground = world.createRigidBody(RigidBodyDesc.fixed());
+        body = world.createRigidBody(RigidBodyDesc.dynamic());
+        world.createCollider(ColliderDesc.ball(0.5), body);
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    function genericJoint(frame1: Rotation): JointData {
+        return JointData.generic(
+            new Vector3(0.0, 0.0, 0.0),
+            frame1,
+            new Vector3(0.0, 0.0, 0.0),
+            new Quaternion(0.0, 0.0, 0.0, 1.0),
+            lockedAxes,
+        );
+    }
+
+    function simulate(numSteps: number) {
+        for (let i = 0; i < numSteps; ++i) {
+            world.step();
+        }
+    }
+
+    test("locked axes and limits", () => {
+        const params = genericJoint(new Quaternion(0.0, 0.0, 0.0, 1.0));
+        params.setLimits(JointAxis.X, -1.0, 1.0);
+        const joint = world.createImpulseJoint(params, ground, body, true);
+        expect(joint).toBeInstanceOf(GenericImpulseJoint);
+
+        body.setLinvel(new Vector3(5.0, 0.0, 0.0), true);
+        simulate(60);
+
+        // The body slides along `X` until it reaches the limit, and
+        // gravity is countered by the locked `Y` axis.
+        expect(body.translation().x).toBeGreaterThan(0.9);
+        expect(body.translation().x).toBeLessThan(1.05);
+        expect(body.translation().y).toBeCloseTo(0.0, 2);
+        expect(body.translation().z).toBeCloseTo(0.0, 2);
+    });
+
+    test("local frames", () => {
+        // The free `X` axis of the first frame is aligned with the world
+        // `Y` axis.
+        const frame1 = new Quaternion(0.0, 0.0, Math.SQRT1_2, Math.SQRT1_2);
+        const params = genericJoint(frame1);
+        params.setLimits(JointAxis.X, -1.0, 1.0);
+        world.createImpulseJoint(params, ground, body, true);
+        simulate(120);
+
+        expect(body.translation().x).toBeCloseTo(0.0, 2);
+        expect(body.translation().y).toBeCloseTo(-1.0, 1);
+    });
+
+    test("motors", () => {
+        const params = genericJoint(new Quaternion(0.0, 0.0, 0.0, 1.0));
+        params.setMotor(JointAxis.AngX, {
+            targetPos: 0.0,
+            targetVel: 2.0,
+            stiffness: 0.0,
+            damping: 1000.0,
+        });
+        world.createImpulseJoint(params, ground, body, true);
+        simulate(30);
+
+        expect(body.angvel().x).toBeCloseTo(2.0, 1);
+        expect(body.angvel().y).toBeCloseTo(0.0, 2);
+    });
+
+    test("modifying limits", () => {
+        const params = genericJoint(new Quaternion(0.0, 0.0, 0.0, 1.0));
+        params.setLimits(JointAxis.X, -1.0, 1.0);
+        const joint = world.createImpulseJoint(
+            params,
+            ground,
+            body,
+            true,
+        ) as GenericImpulseJoint;
+
+        expect(joint.limitsEnabled(JointAxis.X)).toBe(true);
+        expect(joint.limitsEnabled(JointAxis.AngX)).toBe(false);
+        expect(joint.limitsMin(JointAxis.X)).toBeCloseTo(-1.0);
+        expect(joint.limitsMax(JointAxis.X)).toBeCloseTo(1.0);
+
+        joint.setLimits(JointAxis.AngX, -0.5, 0.5);
+        expect(joint.limitsEnabled(JointAxis.AngX)).toBe(true);
+        expect(joint.limitsMin(JointAxis.AngX)).toBeCloseTo(-0.5);
+        expect(joint.limitsMax(JointAxis.AngX)).toBeCloseTo(0.5);
+    });
+});
@@ -0,0 +1,106 @@
+import {
+    init,
+    ColliderDesc,
+    JointAxesMask,
+    JointData,
+    MultibodyJoint,
+    Quaternion,
+    RigidBody,
+    RigidBodyDesc,
+    Vector3,
+    World,
+} from "../pkg3d";
+
+describe("3d/InverseKinematics", () => {
+    let world: World;
+    let link2: RigidBody;
+    let joint1: MultibodyJoint;
+    let joint2: MultibodyJoint;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+
+        // A planar arm made of two links of length 1, rotating about the Z
+        // axis.
+        const root = world.createRigidBody(RigidBodyDesc.fixed());
+        const link1 = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(0.5, 0.0, 0.0),
+        );
+        link2 = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(1.5, 0.0, 0.0),
+        );
+        world.createCollider(ColliderDesc.cuboid(0.5, 0.1, 0.1), link1);
+        world.createCollider(ColliderDesc.cuboid(0.5, 0.1, 0.1), link2);
+
+        const axis = new Vector3(0.0, 0.0, 1.0);
+        joint1 = world.createMultibodyJoint(
+            JointData.revolute(
+                new Vector3(0.0, 0.0, 0.0),
+                new Vector3(-0.5, 0.0, 0.0),
+                axis,
+            ),
+            root,
+            link1,
+            true,
+        );
+        joint2 = world.createMultibodyJoint(
+            JointData.revolute(
+                new Vector3(0.5, 0.0, 0.0),
+                new Vector3(-0.5, 0.0, 0.0),
+                axis,
+            ),
+            link1,
+            link2,
+            true,
+        );
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    test("converges to a reachable target", () => {
+        const result = joint2.inverseKinematics(
+            world.bodies,
+            new Vector3(1.0, 1.0, 0.0),
+            new Quaternion(0.0, 0.0, 0.0, 1.0),
+            {
+                constrainedAxes: JointAxesMask.X | JointAxesMask.Y,
+                apply: true,
+            },
+        );
+
+        expect(result.converged).toBe(true);
+        expect(result.positions.size).toBe(2);
+        expect(result.positions.has(joint1.handle)).toBe(true);
+        expect(result.positions.has(joint2.handle)).toBe(true);
+
+        // The computed coordinates were applied to the arm.
+        expect(link2.translation().x).toBeCloseTo(1.0, 2);
+        expect(link2.translation().y).toBeCloseTo(1.0, 2);
+        expect(joint2.positions()[0]).toBeCloseTo(
+            result.positions.get(joint2.handle)[0],
+        );
+    });
+
+    test("reports unreachable targets", () => {
+        const result = joint2.inverseKinematics(
+            world.bodies,
+            new Vector3(3.0, 0.0, 0.0),
+            new Quaternion(0.0, 0.0, 0.0, 1.0),
+            {constrainedAxes: JointAxesMask.X | JointAxesMask.Y},
+        );
+
+        expect(result.converged).toBe(false);
+
+        // The arm isn’t moved unless `apply` is set.
+        expect(link2.translation().x).toBeCloseTo(1.5);
+        expect(link2.translation().y).toBeCloseTo(0.0);
+    });
+});
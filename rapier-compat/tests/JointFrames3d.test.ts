@@ -0,0 +1,108 @@
+import {
+    init,
+    ColliderDesc,
+    JointData,
+    JointType,
+    Quaternion,
+    RigidBody,
+    RigidBodyDesc,
+    Vector3,
+    World,
+} from "../pkg3d";
+
+describe("3d/JointFrames", () => {
+    let world: World;
+    let ground: RigidBody;
+    let body: RigidBody;
+
+    const identity = new Quaternion(0.0, 0.0, 0.0, 1.0);
+    // A rotation of 90 degrees around `Z`.
+    const rotZ = new Quaternion(0.0, 0.0, Math.SQRT1_2, Math.SQRT1_2);
+
+    // The second rigid-body hangs one unit below the first one.
+    const params = JointData.fixed(
+        new Vector3(0.0, 0.0, 0.0),
+        identity,
+        new Vector3(0.0, 1.0, 0.0),
+        identity,
+    );
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+        ground = world.createRigidBody(RigidBodyDesc.fixed());
+        body = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(0.0, -1.0, 0.0),
+        );
+        world.createCollider(ColliderDesc.ball(0.5), body);
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    function simulate() {
+        for (let i = 0; i < 60; ++i) {
+            world.step();
+        }
+    }
+
+    // With the first frame moved to (2, 0, 0) and rotated by `rotZ`, the
+    // second anchor ends up at (3, 0, 0).
+    function expectMovedBody() {
+        expect(body.translation().x).toBeCloseTo(3.0, 1);
+        expect(body.translation().y).toBeCloseTo(0.0, 1);
+        expect(body.rotation().z).toBeCloseTo(rotZ.z, 1);
+        expect(body.rotation().w).toBeCloseTo(rotZ.w, 1);
+    }
+
+    test("impulse joint frames", () => {
+        const joint = world.createImpulseJoint(params, ground, body, true);
+        joint.setFrame1(new Vector3(2.0, 0.0, 0.0), rotZ);
+
+        expect(joint.anchor1()).toEqual({x: 2.0, y: 0.0, z: 0.0});
+        expect(joint.frameX1().z).toBeCloseTo(rotZ.z);
+        expect(joint.frameX1().w).toBeCloseTo(rotZ.w);
+
+        simulate();
+        expectMovedBody();
+
+        joint.setFrame2(new Vector3(0.0, 2.0, 0.0), identity);
+        expect(joint.anchor2()).toEqual({x: 0.0, y: 2.0, z: 0.0});
+        expect(joint.frameX2().w).toBeCloseTo(1.0);
+    });
+
+    test("multibody joint frames", () => {
+        const joint = world.createMultibodyJoint(params, ground, body, true);
+        expect(joint.type()).toBe(JointType.Fixed);
+        expect(joint.anchor2()).toEqual({x: 0.0, y: 1.0, z: 0.0});
+
+        joint.setFrame1(new Vector3(2.0, 0.0, 0.0), rotZ);
+        expect(joint.anchor1()).toEqual({x: 2.0, y: 0.0, z: 0.0});
+        expect(joint.frameX1().z).toBeCloseTo(rotZ.z);
+        expect(joint.frameX1().w).toBeCloseTo(rotZ.w);
+
+        simulate();
+        expectMovedBody();
+    });
+
+    test("multibody joint anchors", () => {
+        const joint = world.createMultibodyJoint(params, ground, body, true);
+        joint.setAnchor1(new Vector3(2.0, 0.0, 0.0));
+        joint.setAnchor2(new Vector3(0.0, 2.0, 0.0));
+
+        expect(joint.anchor1()).toEqual({x: 2.0, y: 0.0, z: 0.0});
+        expect(joint.anchor2()).toEqual({x: 0.0, y: 2.0, z: 0.0});
+
+        // The orientations of the frames are left unchanged.
+        simulate();
+        expect(body.translation().x).toBeCloseTo(2.0, 1);
+        expect(body.translation().y).toBeCloseTo(-2.0, 1);
+        expect(body.rotation().w).toBeCloseTo(1.0, 1);
+    });
+});
@@ -0,0 +1,129 @@
+import {
+    init,
+    ColliderDesc,
+    GenericImpulseJoint,
+    JointAxesMask,
+    JointAxis,
+    JointData,
+    MotorModel,
+    Quaternion,
+    RevoluteImpulseJoint,
+    RigidBody,
+    RigidBodyDesc,
+    Vector3,
+    World,
+} from "../pkg3d";
+
+describe("3d/JointMotors", () => {
+    let world: World;
+    let ground: RigidBody;
+    let body: RigidBody;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+        ground = world.createRigidBody(RigidBodyDesc.fixed());
+        body = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(0.0, -1.0, 0.0),
+        );
+        world.createCollider(ColliderDesc.ball(0.5).setMass(2.0), body);
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    test("unit joint motor state", () => {
+        const params = JointData.revolute(
+            new Vector3(0.0, 0.0, 0.0),
+            new Vector3(0.0, 1.0, 0.0),
+            new Vector3(0.0, 0.0, 1.0),
+        );
+        const joint = world.createImpulseJoint(
+            params,
+            ground,
+            body,
+            true,
+        ) as RevoluteImpulseJoint;
+        expect(joint.motorEnabled()).toBe(false);
+
+        joint.configureMotorVelocity(2.0, 10.0);
+        joint.configureMotorModel(MotorModel.ForceBased);
+        joint.setMotorMaxForce(5.0);
+
+        expect(joint.motorEnabled()).toBe(true);
+        expect(joint.motorModel()).toBe(MotorModel.ForceBased);
+        expect(joint.motorTargetPos()).toBe(0.0);
+        expect(joint.motorTargetVel()).toBeCloseTo(2.0);
+        expect(joint.motorStiffness()).toBe(0.0);
+        expect(joint.motorDamping()).toBeCloseTo(10.0);
+        expect(joint.motorMaxForce()).toBeCloseTo(5.0);
+    });
+
+    test("generic joint motor state", () => {
+        const identity = new Quaternion(0.0, 0.0, 0.0, 1.0);
+        const params = JointData.generic(
+            new Vector3(0.0, 0.0, 0.0),
+            identity,
+            new Vector3(0.0, 1.0, 0.0),
+            identity,
+            JointAxesMask.X | JointAxesMask.Y | JointAxesMask.Z,
+        ).setMotor(JointAxis.AngY, {
+            targetPos: 0.5,
+            targetVel: 0.0,
+            stiffness: 100.0,
+            damping: 10.0,
+            maxForce: 50.0,
+        });
+        const joint = world.createImpulseJoint(
+            params,
+            ground,
+            body,
+            true,
+        ) as GenericImpulseJoint;
+
+        expect(joint.motorEnabled(JointAxis.AngX)).toBe(false);
+        expect(joint.motorEnabled(JointAxis.AngY)).toBe(true);
+        expect(joint.motorModel(JointAxis.AngY)).toBe(
+            MotorModel.AccelerationBased,
+        );
+        expect(joint.motorTargetPos(JointAxis.AngY)).toBeCloseTo(0.5);
+        expect(joint.motorTargetVel(JointAxis.AngY)).toBe(0.0);
+        expect(joint.motorStiffness(JointAxis.AngY)).toBeCloseTo(100.0);
+        expect(joint.motorDamping(JointAxis.AngY)).toBeCloseTo(10.0);
+        expect(joint.motorMaxForce(JointAxis.AngY)).toBeCloseTo(50.0);
+
+        joint.setMotorMaxForce(JointAxis.AngY, 20.0);
+        expect(joint.motorMaxForce(JointAxis.AngY)).toBeCloseTo(20.0);
+    });
+
+    test("applied impulses", () => {
+        const identity = new Quaternion(0.0, 0.0, 0.0, 1.0);
+        const params = JointData.fixed(
+            new Vector3(0.0, 0.0, 0.0),
+            identity,
+            new Vector3(0.0, 1.0, 0.0),
+            identity,
+        );
+        const joint = world.createImpulseJoint(params, ground, body, true);
+
+        for (let i = 0; i < 60; ++i) {
+            world.step();
+        }
+
+        // The joint supports the weight of the body during each timestep.
+        const dt = world.timestep;
+        const weight = 2.0 * 9.81 * dt;
+        expect(Math.abs(joint.appliedImpulse(JointAxis.Y))).toBeCloseTo(
+            weight,
+            1,
+        );
+        expect(joint.appliedImpulse(JointAxis.X)).toBeCloseTo(0.0, 2);
+        expect(joint.appliedImpulse(JointAxis.AngY)).toBeCloseTo(0.0, 2);
+    });
+});
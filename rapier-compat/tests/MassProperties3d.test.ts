@@ -0,0 +1,97 @@
+import {
+    init,
+    ColliderDesc,
+    Quaternion,
+    RigidBodyDesc,
+    Vector3,
+    World,
+} from "../pkg3d";
+
+describe("3d/MassProperties", () => {
+    let world: World;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    test("collider", () => {
+        const collider = world.createCollider(
+            ColliderDesc.cuboid(1.0, 0.5, 0.25)
+                .setDensity(2.0)
+                .setTranslation(3.0, 0.0, 0.0),
+        );
+        const mprops = collider.massProperties();
+
+        // The mass-properties are expressed in the collider’s local-space.
+        expect(mprops.mass).toBeCloseTo(2.0);
+        expect(mprops.localCom).toEqual({x: 0.0, y: 0.0, z: 0.0});
+        expect(mprops.principalInertia.x).toBeCloseTo((2.0 / 12.0) * 1.25);
+        expect(mprops.principalInertia.y).toBeCloseTo((2.0 / 12.0) * 4.25);
+        expect(mprops.principalInertia.z).toBeCloseTo((2.0 / 12.0) * 5.0);
+    });
+
+    test("explicit collider mass-properties", () => {
+        const frame = new Quaternion(0.0, Math.SQRT1_2, 0.0, Math.SQRT1_2);
+        const collider = world.createCollider(
+            ColliderDesc.ball(0.5).setMassProperties(
+                5.0,
+                new Vector3(0.1, 0.2, 0.3),
+                new Vector3(1.0, 2.0, 3.0),
+                frame,
+            ),
+        );
+        const mprops = collider.massProperties();
+
+        expect(mprops.mass).toBeCloseTo(5.0);
+        expect(mprops.localCom.x).toBeCloseTo(0.1);
+        expect(mprops.localCom.y).toBeCloseTo(0.2);
+        expect(mprops.localCom.z).toBeCloseTo(0.3);
+        expect(mprops.principalInertia.x).toBeCloseTo(1.0);
+        expect(mprops.principalInertia.y).toBeCloseTo(2.0);
+        expect(mprops.principalInertia.z).toBeCloseTo(3.0);
+        expect(mprops.principalInertiaFrame.y).toBeCloseTo(frame.y);
+        expect(mprops.principalInertiaFrame.w).toBeCloseTo(frame.w);
+    });
+
+    test("rigid-body", () => {
+        const body = world.createRigidBody(RigidBodyDesc.dynamic());
+        world.createCollider(
+            ColliderDesc.ball(0.5).setMass(3.0).setTranslation(0.0, 1.0, 0.0),
+            body,
+        );
+        world.createCollider(
+            ColliderDesc.ball(0.5).setMass(1.0).setTranslation(0.0, -1.0, 0.0),
+            body,
+        );
+        body.recomputeMassPropertiesFromColliders();
+        const mprops = body.massProperties();
+
+        expect(mprops.mass).toBeCloseTo(4.0);
+        expect(mprops.mass).toBeCloseTo(body.mass());
+        expect(mprops.localCom.x).toBeCloseTo(0.0);
+        expect(mprops.localCom.y).toBeCloseTo(0.5);
+        expect(mprops.localCom.z).toBeCloseTo(0.0);
+
+        // Each ball contributes 2/5 * m * 0.5² around its center, plus
+        // m * d² around the axes orthogonal to `y`. The principal axes may
+        // be listed in any order.
+        const inertia = [
+            mprops.principalInertia.x,
+            mprops.principalInertia.y,
+            mprops.principalInertia.z,
+        ].sort((a, b) => a - b);
+        expect(inertia[0]).toBeCloseTo(0.4);
+        expect(inertia[1]).toBeCloseTo(3.4);
+        expect(inertia[2]).toBeCloseTo(3.4);
+    });
+});
@@ -0,0 +1,181 @@
+import {
+    init,
+    ColliderDesc,
+    JointData,
+    MotorModel,
+    RevoluteMultibodyJoint,
+    RigidBody,
+    RigidBodyDesc,
+    Vector3,
+    World,
+} from "../pkg3d";
+
+describe("3d/MultibodyJoints", () => {
+    let world: World;
+    let root: RigidBody;
+    let links: RigidBody[];
+    let joints: RevoluteMultibodyJoint[];
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+
+        // A horizontal chain of two links of length 1, rotating about the
+        // Z axis. The colliders are shorter than the links so that adjacent
+        // links don’t touch.
+        root = world.createRigidBody(RigidBodyDesc.fixed());
+        links = [];
+        joints = [];
+
+        for (let i = 0; i < 2; ++i) {
+            const link = world.createRigidBody(
+                RigidBodyDesc.dynamic().setTranslation(0.5 + i, 0.0, 0.0),
+            );
+            world.createCollider(ColliderDesc.cuboid(0.4, 0.1, 0.1), link);
+
+            const params = JointData.revolute(
+                new Vector3(i == 0 ? 0.0 : 0.5, 0.0, 0.0),
+                new Vector3(-0.5, 0.0, 0.0),
+                new Vector3(0.0, 0.0, 1.0),
+            );
+            const parent = i == 0 ? root : links[i - 1];
+            joints.push(
+                world.createMultibodyJoint(
+                    params,
+                    parent,
+                    link,
+                    true,
+                ) as RevoluteMultibodyJoint,
+            );
+            links.push(link);
+        }
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    function simulate(numSteps: number) {
+        for (let i = 0; i < numSteps; ++i) {
+            world.step();
+        }
+    }
+
+    test("linked rigid-bodies", () => {
+        expect(joints[0]).toBeInstanceOf(RevoluteMultibodyJoint);
+        expect(joints[0].bodyHandle1()).toBe(root.handle);
+        expect(joints[0].bodyHandle2()).toBe(links[0].handle);
+        expect(joints[1].bodyHandle1()).toBe(links[0].handle);
+        expect(joints[1].bodyHandle2()).toBe(links[1].handle);
+    });
+
+    test("limits", () => {
+        expect(joints[0].limitsEnabled()).toBe(false);
+
+        joints[0].setLimits(-0.5, 0.5);
+        expect(joints[0].limitsEnabled()).toBe(true);
+        expect(joints[0].limitsMin()).toBeCloseTo(-0.5);
+        expect(joints[0].limitsMax()).toBeCloseTo(0.5);
+
+        // The first link stops falling at the limit, with its center 0.5 away
+        // from the pivot.
+        simulate(120);
+        expect(links[0].translation().y).toBeGreaterThan(
+            -0.5 * Math.sin(0.5) - 0.05,
+        );
+    });
+
+    test("motors", () => {
+        world.gravity = new Vector3(0.0, 0.0, 0.0);
+        joints[0].configureMotorVelocity(2.0, 1000.0);
+        joints[0].setMotorMaxForce(500.0);
+
+        expect(joints[0].motorEnabled()).toBe(true);
+        expect(joints[0].motorModel()).toBe(MotorModel.AccelerationBased);
+        expect(joints[0].motorTargetVel()).toBeCloseTo(2.0);
+        expect(joints[0].motorStiffness()).toBe(0.0);
+        expect(joints[0].motorDamping()).toBeCloseTo(1000.0);
+        expect(joints[0].motorMaxForce()).toBeCloseTo(500.0);
+        expect(joints[1].motorEnabled()).toBe(false);
+
+        joints[0].configureMotorModel(MotorModel.ForceBased);
+        expect(joints[0].motorModel()).toBe(MotorModel.ForceBased);
+        joints[0].configureMotorModel(MotorModel.AccelerationBased);
+
+        simulate(30);
+        expect(links[0].angvel().z).toBeCloseTo(2.0, 1);
+    });
+
+    test("links", () => {
+        expect(joints[0].linkId()).toBe(1);
+        expect(joints[1].linkId()).toBe(2);
+
+        // The root of the multibody has a joint too.
+        let handles = [];
+        let ids = [];
+        world.multibodyJoints.forEachMultibodyLink(joints[1].handle, (j) => {
+            handles.push(j.handle);
+            ids.push(j.linkId());
+        });
+        expect(ids).toEqual([0, 1, 2]);
+        expect(handles).toEqual([
+            root.handle,
+            joints[0].handle,
+            joints[1].handle,
+        ]);
+
+        const multibodyJoints = world.multibodyJoints;
+        expect(multibodyJoints.getForRigidBody(root.handle).linkId()).toBe(0);
+        expect(multibodyJoints.getForRigidBody(links[1].handle)).toBe(
+            joints[1],
+        );
+
+        const other = world.createRigidBody(RigidBodyDesc.dynamic());
+        expect(multibodyJoints.getForRigidBody(other.handle)).toBeNull();
+    });
+
+    test("generalized coordinates", () => {
+        expect(joints[0].numDofs()).toBe(1);
+
+        // The link is teleported to match its new coordinates.
+        joints[0].setPositions(world.bodies, [0.3]);
+        joints[0].setVelocities(world.bodies, [1.5]);
+        expect(joints[0].positions()[0]).toBeCloseTo(0.3);
+        expect(joints[0].velocities()[0]).toBeCloseTo(1.5);
+        expect(links[0].translation().x).toBeCloseTo(0.5 * Math.cos(0.3));
+        expect(links[0].translation().y).toBeCloseTo(0.5 * Math.sin(0.3));
+    });
+
+    test("generalized coordinates round-trip", () => {
+        simulate(20);
+        const positions = joints.map((j) => Array.from(j.positions()));
+        const velocities = joints.map((j) => Array.from(j.velocities()));
+        const translation = links[1].translation();
+
+        simulate(20);
+        joints.forEach((j, i) => {
+            j.setPositions(world.bodies, positions[i]);
+            j.setVelocities(world.bodies, velocities[i]);
+        });
+
+        expect(links[1].translation().x).toBeCloseTo(translation.x);
+        expect(links[1].translation().y).toBeCloseTo(translation.y);
+        joints.forEach((j, i) => {
+            expect(j.velocities()[0]).toBeCloseTo(velocities[i][0]);
+        });
+    });
+
+    test("generalized forces", () => {
+        world.gravity = new Vector3(0.0, 0.0, 0.0);
+        joints[0].addGeneralizedForces(world.bodies, [1.0]);
+        simulate(10);
+
+        expect(joints[0].velocities()[0]).toBeGreaterThan(0.0);
+        expect(links[0].translation().y).toBeGreaterThan(0.0);
+    });
+});
@@ -0,0 +1,139 @@
+import {
+    init,
+    ColliderDesc,
+    JointData,
+    Quaternion,
+    RigidBody,
+    RigidBodyDesc,
+    Vector3,
+    World,
+} from "../pkg3d";
+
+describe("3d/Prefab", () => {
+    let world: World;
+    let target: World;
+    let bodies: RigidBody[];
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+        target = new World(new Vector3(0, -9.81, 0));
+        world.createCollider(ColliderDesc.cuboid(10.0, 0.1, 10.0));
+
+        // Two linked rigid-bodies, and a third one linked to the first.
+        bodies = [1.0, 2.0, 3.0].map((x) => {
+            const body = world.createRigidBody(
+                RigidBodyDesc.dynamic().setTranslation(x, 0.0, 0.0),
+            );
+            world.createCollider(ColliderDesc.ball(0.4), body);
+            return body;
+        });
+
+        const params = JointData.spherical(
+            new Vector3(0.5, 0.0, 0.0),
+            new Vector3(-0.5, 0.0, 0.0),
+        );
+        world.createImpulseJoint(params, bodies[0], bodies[1], true);
+        world.createImpulseJoint(params, bodies[2], bodies[0], true);
+    });
+
+    afterEach(() => {
+        target.free();
+        world.free();
+    });
+
+    test("handle remapping", () => {
+        const prefab = world.serializePrefab([
+            bodies[0].handle,
+            bodies[1].handle,
+        ]);
+
+        // Take a few handles in the target world first so that the
+        // inserted elements can’t keep their original handles.
+        for (let i = 0; i < 3; ++i) {
+            target.createCollider(
+                ColliderDesc.ball(0.1),
+                target.createRigidBody(RigidBodyDesc.fixed()),
+            );
+        }
+
+        const remapping = target.insertPrefab(
+            prefab,
+            new Vector3(0.0, 5.0, 0.0),
+            new Quaternion(0.0, 0.0, Math.SQRT1_2, Math.SQRT1_2),
+        );
+
+        // The joint with the third rigid-body isn’t part of the prefab.
+        expect(remapping.bodies.size).toBe(2);
+        expect(remapping.colliders.size).toBe(2);
+        expect(remapping.impulseJoints.size).toBe(1);
+        expect(remapping.multibodyJoints.size).toBe(0);
+        expect(target.bodies.len()).toBe(5);
+        expect(target.colliders.len()).toBe(5);
+
+        const body1 = target.getRigidBody(
+            remapping.bodies.get(bodies[0].handle),
+        );
+        const body2 = target.getRigidBody(
+            remapping.bodies.get(bodies[1].handle),
+        );
+        expect(body1.handle).not.toBe(bodies[0].handle);
+
+        // The positions are pre-multiplied by the rigid-motion.
+        expect(body1.translation().x).toBeCloseTo(0.0);
+        expect(body1.translation().y).toBeCloseTo(6.0);
+        expect(body2.translation().x).toBeCloseTo(0.0);
+        expect(body2.translation().y).toBeCloseTo(7.0);
+
+        // The colliders and the joint are attached to the copies.
+        remapping.colliders.forEach((handle) => {
+            const parent = target.getCollider(handle).parent();
+            expect([body1.handle, body2.handle]).toContain(parent.handle);
+        });
+
+        remapping.impulseJoints.forEach((handle) => {
+            const joint = target.getImpulseJoint(handle);
+            expect(joint.body1().handle).toBe(body1.handle);
+            expect(joint.body2().handle).toBe(body2.handle);
+        });
+    });
+
+    test("multiple insertions", () => {
+        const prefab = world.serializePrefab([
+            bodies[0].handle,
+            bodies[1].handle,
+        ]);
+        const rotation = new Quaternion(0.0, 0.0, 0.0, 1.0);
+        const remapping1 = target.insertPrefab(
+            prefab,
+            new Vector3(0.0, 0.0, 0.0),
+            rotation,
+        );
+        const remapping2 = target.insertPrefab(
+            prefab,
+            new Vector3(0.0, 0.0, 5.0),
+            rotation,
+        );
+
+        expect(target.bodies.len()).toBe(4);
+        expect(target.impulseJoints.len()).toBe(2);
+        expect(remapping2.bodies.get(bodies[0].handle)).not.toBe(
+            remapping1.bodies.get(bodies[0].handle),
+        );
+    });
+
+    test("rejects invalid data", () => {
+        expect(() =>
+            target.insertPrefab(
+                new Uint8Array([1, 2, 3]),
+                new Vector3(0.0, 0.0, 0.0),
+                new Quaternion(0.0, 0.0, 0.0, 1.0),
+            ),
+        ).toThrow();
+    });
+});
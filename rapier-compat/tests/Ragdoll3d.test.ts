@@ -0,0 +1,131 @@
+import {init, RagdollDesc, RigidBodyType, Vector3, World} from "../pkg3d";
+
+describe("3d/Ragdoll", () => {
+    let world: World;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    // A chain of `n` bones of length 1 along the X axis.
+    function chain(n: number): RagdollDesc {
+        const desc = new RagdollDesc();
+        desc.addRootBone(
+            new Vector3(0.0, 0.0, 0.0),
+            new Vector3(1.0, 0.0, 0.0),
+            0.1,
+        );
+        for (let i = 1; i < n; ++i) {
+            desc.addSphericalBone(
+                i - 1,
+                new Vector3(i, 0.0, 0.0),
+                new Vector3(i + 1, 0.0, 0.0),
+                0.1,
+                Math.PI,
+                -Math.PI,
+                Math.PI,
+            );
+        }
+        return desc;
+    }
+
+    test("bones", () => {
+        const desc = chain(20);
+        expect(desc.numBones()).toBe(20);
+        expect(
+            desc.addSphericalBone(
+                20,
+                new Vector3(0.0, 0.0, 0.0),
+                new Vector3(1.0, 0.0, 0.0),
+                0.1,
+                Math.PI,
+                -Math.PI,
+                Math.PI,
+            ),
+        ).toBeUndefined();
+
+        const ragdoll = world.createRagdoll(desc);
+        expect(ragdoll.multibody).toBe(false);
+        expect(ragdoll.bodies).toHaveLength(20);
+        expect(ragdoll.colliders).toHaveLength(20);
+        expect(ragdoll.joints[0]).toBeUndefined();
+        expect(world.impulseJoints.len()).toBe(19);
+
+        // Each rigid-body is centered on its bone.
+        const body = world.getRigidBody(ragdoll.bodies[3]);
+        expect(body.translation().x).toBeCloseTo(3.5);
+        expect(body.translation().y).toBeCloseTo(0.0);
+
+        const joint = world.getImpulseJoint(ragdoll.joints[3]);
+        expect(joint.body1().handle).toBe(ragdoll.bodies[2]);
+        expect(joint.body2().handle).toBe(body.handle);
+    });
+
+    test("collisions between bones", () => {
+        const ragdoll = world.createRagdoll(chain(20));
+        world.step();
+
+        // Adjacent bones overlap at their joint, but don’t collide.
+        let numContacts = 0;
+        world.contactPair(
+            world.getCollider(ragdoll.colliders[0]),
+            world.getCollider(ragdoll.colliders[1]),
+            (manifold) => {
+                numContacts += manifold.numContacts();
+            },
+        );
+        expect(numContacts).toBe(0);
+
+        // The collision groups are left untouched, even for large ragdolls.
+        ragdoll.colliders.forEach((handle) => {
+            expect(world.getCollider(handle).collisionGroups()).toBe(
+                0xffffffff,
+            );
+        });
+    });
+
+    test("multibody joints", () => {
+        const ragdoll = world.createRagdoll(chain(3).setMultibody(true));
+        expect(ragdoll.multibody).toBe(true);
+        expect(world.impulseJoints.len()).toBe(0);
+        expect(world.getMultibodyJoint(ragdoll.joints[2])).not.toBeNull();
+    });
+
+    test("swing limits", () => {
+        const desc = new RagdollDesc();
+        const root = desc.addRootBone(
+            new Vector3(-1.0, 0.0, 0.0),
+            new Vector3(0.0, 0.0, 0.0),
+            0.1,
+        );
+        desc.addSphericalBone(
+            root,
+            new Vector3(0.0, 0.0, 0.0),
+            new Vector3(1.0, 0.0, 0.0),
+            0.1,
+            0.5,
+            -0.1,
+            0.1,
+        );
+        const ragdoll = world.createRagdoll(desc);
+        world.getRigidBody(ragdoll.bodies[0]).setBodyType(RigidBodyType.Fixed);
+
+        for (let i = 0; i < 120; ++i) {
+            world.step();
+        }
+
+        // The bone swings down until it reaches its limit.
+        const t = world.getRigidBody(ragdoll.bodies[1]).translation();
+        expect(Math.atan2(-t.y, t.x)).toBeCloseTo(0.5, 1);
+    });
+});
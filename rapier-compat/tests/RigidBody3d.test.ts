@@ -0,0 +1,55 @@
+import {init, ColliderDesc, RigidBodyDesc, Vector3, World} from "../pkg3d";
+
+describe("3d/RigidBody", () => {
+    let world: World;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    test("kinetic energy", () => {
+        const body = world.createRigidBody(
+            RigidBodyDesc.dynamic()
+                .setGravityScale(0.0)
+                .setLinvel(1.0, 0.0, 0.0)
+                .setAngvel(new Vector3(0.0, 0.0, 2.0)),
+        );
+        world.createCollider(ColliderDesc.ball(0.5).setMass(2.0), body);
+        world.step();
+
+        // The angular inertia of the ball is 2/5 * 2 * 0.5² = 0.2.
+        expect(body.kineticEnergy()).toBeCloseTo(0.5 * 2.0 + 0.5 * 0.2 * 4.0);
+    });
+
+    test("user forces and torques", () => {
+        const body = world.createRigidBody(RigidBodyDesc.dynamic());
+        world.createCollider(ColliderDesc.ball(0.5), body);
+
+        body.addForce(new Vector3(1.0, 2.0, 3.0), true);
+        body.addForceAtPoint(
+            new Vector3(1.0, 0.0, 0.0),
+            new Vector3(0.0, 1.0, 0.0),
+            true,
+        );
+
+        // The force applied at a point also adds a torque around the
+        // center-of-mass.
+        expect(body.userForce()).toEqual({x: 2.0, y: 2.0, z: 3.0});
+        expect(body.userTorque().z).toBeCloseTo(-1.0);
+
+        body.resetForces(true);
+        body.resetTorques(true);
+        expect(body.userForce()).toEqual({x: 0.0, y: 0.0, z: 0.0});
+        expect(body.userTorque()).toEqual({x: 0.0, y: 0.0, z: 0.0});
+    });
+});
@@ -0,0 +1,163 @@
+import {init, ColliderDesc, RigidBodyDesc, Vector3, World} from "../pkg3d";
+
+describe("3d/Snapshot", () => {
+    let world: World;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+        world.createCollider(ColliderDesc.cuboid(10.0, 0.1, 10.0));
+
+        for (let i = 0; i < 3; ++i) {
+            const body = world.createRigidBody(
+                RigidBodyDesc.dynamic().setTranslation(0.0, 1.0 + i, 0.0),
+            );
+            world.createCollider(ColliderDesc.ball(0.4), body);
+        }
+
+        for (let i = 0; i < 10; ++i) {
+            world.step();
+        }
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    test("round-trip", () => {
+        const restored = World.restoreSnapshot(world.takeSnapshot());
+
+        expect(restored.bodies.len()).toBe(world.bodies.len());
+        expect(restored.colliders.len()).toBe(world.colliders.len());
+        expect(restored.checksum().hash).toBe(world.checksum().hash);
+
+        for (let i = 0; i < 10; ++i) {
+            world.step();
+            restored.step();
+        }
+
+        expect(restored.checksum().hash).toBe(world.checksum().hash);
+        restored.free();
+    });
+
+    test("json round-trip", () => {
+        const restored = World.restoreJsonSnapshot(world.takeJsonSnapshot());
+
+        expect(restored.bodies.len()).toBe(world.bodies.len());
+        expect(restored.checksum().hash).toBe(world.checksum().hash);
+
+        // JSON numbers round-trip exactly, so the simulations stay in sync.
+        for (let i = 0; i < 10; ++i) {
+            world.step();
+            restored.step();
+        }
+
+        expect(restored.checksum().hash).toBe(world.checksum().hash);
+        restored.free();
+    });
+
+    test("rejects invalid json snapshots", () => {
+        const json = JSON.parse(world.takeJsonSnapshot());
+        const restore = (modify: (json: any) => void) => {
+            const copy = JSON.parse(JSON.stringify(json));
+            modify(copy);
+            return () => World.restoreJsonSnapshot(JSON.stringify(copy));
+        };
+
+        expect(restore((j) => ++j.format_version)).toThrow(
+            /unsupported snapshot format version/,
+        );
+        expect(restore((j) => (j.header.dim = 2))).toThrow(
+            /cannot load a 2D snapshot/,
+        );
+        expect(restore((j) => (j.header.rapier_version = "0.1"))).toThrow(
+            /incompatible snapshot/,
+        );
+        expect(restore((j) => (j.header.kind = "Prefab"))).toThrow(
+            /expected a snapshot of world/,
+        );
+        expect(restore((j) => delete j.data.bodies)).toThrow(
+            /corrupted snapshot/,
+        );
+        expect(() => World.restoreJsonSnapshot("{")).toThrow(
+            /corrupted snapshot/,
+        );
+    });
+
+    test("body states deltas", () => {
+        const fixed = world.createRigidBody(
+            RigidBodyDesc.fixed().setTranslation(5.0, 0.0, 0.0),
+        );
+        const base = world.takeBodyStatesSnapshot();
+        const decodedBase = world.decodeBodyStatesSnapshot(base);
+
+        // Nothing changed since the base was taken.
+        const emptyDelta = world.takeBodyStatesDelta(decodedBase);
+        expect(emptyDelta.length).toBeLessThan(base.length);
+
+        for (let i = 0; i < 10; ++i) {
+            world.step();
+        }
+
+        // The decoded base can be reused to take several deltas.
+        const delta = world.takeBodyStatesDelta(decodedBase);
+        expect(world.takeBodyStatesDelta(base)).toEqual(delta);
+        expect(delta.length).toBeLessThan(base.length);
+        const translations = [];
+        world.bodies.forEach((body) => translations.push(body.translation()));
+
+        for (let i = 0; i < 10; ++i) {
+            world.step();
+        }
+        expect(world.takeBodyStatesDelta(decodedBase)).not.toEqual(delta);
+        decodedBase.free();
+
+        // Restoring the base, then the delta, restores the states at the time
+        // the delta was taken.
+        world.restoreBodyStates(base);
+        world.restoreBodyStates(delta);
+        let i = 0;
+        world.bodies.forEach((body) => {
+            expect(body.translation()).toEqual(translations[i++]);
+        });
+        expect(fixed.translation().x).toBeCloseTo(5.0);
+    });
+
+    test("header", () => {
+        const snapshot = world.takeSnapshot();
+        const view = new DataView(snapshot.buffer, snapshot.byteOffset);
+        expect(view.getUint32(4, true)).toBe(1);
+
+        // The rapier version is the minor version of the rapier dependency.
+        const json = JSON.parse(world.takeJsonSnapshot());
+        expect(json.format_version).toBe(1);
+        expect(json.header.dim).toBe(3);
+        expect(json.header.rapier_version).toBe("0.16");
+    });
+
+    test("rejects unsupported format versions", () => {
+        const snapshot = world.takeSnapshot();
+        // The format version is stored as a little-endian u32 right after
+        // the 4 magic bytes.
+        const view = new DataView(snapshot.buffer, snapshot.byteOffset);
+        view.setUint32(4, view.getUint32(4, true) + 1, true);
+
+        expect(() => World.restoreSnapshot(snapshot)).toThrow(
+            /unsupported snapshot format version/,
+        );
+    });
+
+    test("rejects data that is not a snapshot", () => {
+        const snapshot = world.takeSnapshot();
+        snapshot[0] = 0;
+
+        expect(() => World.restoreSnapshot(snapshot)).toThrow(
+            /invalid snapshot/,
+        );
+    });
+});
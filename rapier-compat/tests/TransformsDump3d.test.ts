@@ -0,0 +1,86 @@
+import {
+    init,
+    Quaternion,
+    RigidBody,
+    RigidBodyDesc,
+    RigidBodySet,
+    Vector3,
+    World,
+} from "../pkg3d";
+
+describe("3d/TransformsDump", () => {
+    let world: World;
+    let bodies: RigidBody[];
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+        bodies = [
+            world.createRigidBody(
+                RigidBodyDesc.fixed().setTranslation(1.0, 2.0, 3.0),
+            ),
+            world.createRigidBody(
+                RigidBodyDesc.dynamic()
+                    .setTranslation(4.0, 5.0, 6.0)
+                    .setRotation(new Quaternion(0.0, 1.0, 0.0, 0.0)),
+            ),
+            world.createRigidBody(
+                RigidBodyDesc.dynamic().setTranslation(7.0, 8.0, 9.0),
+            ),
+        ];
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    test("layout", () => {
+        const stride = RigidBodySet.transformStride();
+        expect(stride).toBe(7);
+
+        const dump = world.bodies.fillTransforms();
+        expect(dump.numBodies()).toBe(3);
+        expect(dump.handles()).toHaveLength(3);
+        expect(dump.transforms()).toHaveLength(3 * stride);
+
+        const handles = Array.from(dump.handles());
+        const transforms = Array.from(dump.transforms());
+
+        for (const body of bodies) {
+            const i = handles.indexOf(body.handle);
+            expect(i).toBeGreaterThanOrEqual(0);
+
+            const t = body.translation();
+            const r = body.rotation();
+            const expected = [t.x, t.y, t.z, r.x, r.y, r.z, r.w];
+            const actual = transforms.slice(i * stride, (i + 1) * stride);
+            expected.forEach((value, k) => {
+                expect(actual[k]).toBeCloseTo(value);
+            });
+        }
+
+        dump.free();
+    });
+
+    test("active transforms", () => {
+        world.step();
+
+        // Fixed rigid-bodies are skipped.
+        const dump = world.bodies.fillActiveTransforms(world.islands);
+        expect(dump.numBodies()).toBe(2);
+        const handles = Array.from(dump.handles());
+        expect(handles).toContain(bodies[1].handle);
+        expect(handles).toContain(bodies[2].handle);
+        expect(handles).not.toContain(bodies[0].handle);
+
+        // The dump can be reused.
+        expect(world.bodies.fillTransforms(dump)).toBe(dump);
+        expect(dump.numBodies()).toBe(3);
+        dump.free();
+    });
+});
@@ -0,0 +1,160 @@
+import {init, Vector3, World} from "../pkg3d";
+
+const ARM_URDF = `<?xml version="1.0"?>
+<robot name="arm">
+    <link name="base">
+        <collision>
+            <geometry>
+                <box size="1.0 1.0 0.2"/>
+            </geometry>
+        </collision>
+    </link>
+    <link name="upper_arm">
+        <inertial>
+            <origin xyz="0 0 0.5"/>
+            <mass value="2.0"/>
+            <inertia ixx="0.1" ixy="0" ixz="0" iyy="0.1" iyz="0" izz="0.01"/>
+        </inertial>
+        <collision>
+            <origin xyz="0 0 0.5"/>
+            <geometry>
+                <cylinder radius="0.1" length="1.0"/>
+            </geometry>
+        </collision>
+        <collision>
+            <geometry>
+                <mesh filename="package://arm/meshes/upper_arm.stl"/>
+            </geometry>
+        </collision>
+    </link>
+    <link name="hand">
+        <collision>
+            <geometry>
+                <sphere radius="0.1"/>
+            </geometry>
+        </collision>
+    </link>
+    <joint name="shoulder" type="revolute">
+        <parent link="base"/>
+        <child link="upper_arm"/>
+        <origin xyz="0 0 0.1"/>
+        <axis xyz="0 1 0"/>
+        <limit lower="-1.57" upper="1.57" effort="10" velocity="1"/>
+    </joint>
+    <joint name="wrist" type="fixed">
+        <parent link="upper_arm"/>
+        <child link="hand"/>
+        <origin xyz="0 0 1"/>
+    </joint>
+</robot>`;
+
+describe("3d/Urdf", () => {
+    let world: World;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    test("impulse joints", () => {
+        const robot = world.loadUrdf(ARM_URDF, {
+            translation: new Vector3(1.0, 2.0, 3.0),
+            fixedRoot: true,
+        });
+
+        expect(robot.name).toBe("arm");
+        expect(robot.multibody).toBe(false);
+        expect(Array.from(robot.links.keys())).toEqual([
+            "base",
+            "upper_arm",
+            "hand",
+        ]);
+        expect(Array.from(robot.joints.keys())).toEqual(["shoulder", "wrist"]);
+        expect(world.bodies.len()).toBe(3);
+        expect(world.impulseJoints.len()).toBe(2);
+
+        // Mesh geometries are ignored.
+        expect(robot.colliders.get("base")).toHaveLength(1);
+        expect(robot.colliders.get("upper_arm")).toHaveLength(1);
+        expect(world.colliders.len()).toBe(3);
+
+        const base = world.getRigidBody(robot.links.get("base"));
+        const upperArm = world.getRigidBody(robot.links.get("upper_arm"));
+        const hand = world.getRigidBody(robot.links.get("hand"));
+        expect(base.isFixed()).toBe(true);
+        expect(upperArm.isDynamic()).toBe(true);
+
+        // The links are placed by composing the joint origins.
+        expect(hand.translation().x).toBeCloseTo(1.0);
+        expect(hand.translation().y).toBeCloseTo(2.0);
+        expect(hand.translation().z).toBeCloseTo(4.1);
+
+        const shoulder = world.getImpulseJoint(robot.joints.get("shoulder"));
+        expect(shoulder.body1().handle).toBe(base.handle);
+        expect(shoulder.body2().handle).toBe(upperArm.handle);
+
+        // The <inertial> element replaces the mass computed from the colliders.
+        world.step();
+        expect(upperArm.mass()).toBeCloseTo(2.0);
+    });
+
+    test("multibody joints", () => {
+        const robot = world.loadUrdf(ARM_URDF, {multibody: true});
+
+        expect(robot.multibody).toBe(true);
+        expect(world.impulseJoints.len()).toBe(0);
+
+        const shoulder = world.getMultibodyJoint(robot.joints.get("shoulder"));
+        expect(shoulder).not.toBeNull();
+        // The revolute joint has a single degree of freedom.
+        expect(shoulder.positions()).toHaveLength(1);
+    });
+
+    test("floating joints", () => {
+        const robot = world.loadUrdf(
+            ARM_URDF.replace('type="fixed"', 'type="floating"'),
+            {multibody: true},
+        );
+
+        // The hand is a free rigid-body.
+        expect(Array.from(robot.joints.keys())).toEqual(["shoulder"]);
+        expect(world.multibodyJoints.len()).toBe(1);
+        expect(world.getRigidBody(robot.links.get("hand")).isDynamic()).toBe(
+            true,
+        );
+    });
+
+    test("invalid files", () => {
+        expect(() => world.loadUrdf("<robot>")).toThrow(
+            /failed to parse the XML/,
+        );
+        expect(() => world.loadUrdf("<model/>")).toThrow(
+            /the root element must be <robot>/,
+        );
+        expect(() =>
+            world.loadUrdf(
+                ARM_URDF.replace(
+                    '<child link="hand"/>',
+                    '<child link="finger"/>',
+                ),
+            ),
+        ).toThrow(/references the unknown link "finger"/);
+        expect(() =>
+            world.loadUrdf(ARM_URDF.replace('type="fixed"', 'type="gear"')),
+        ).toThrow(/unsupported type "gear"/);
+
+        // Nothing is inserted when the file is invalid.
+        expect(world.bodies.len()).toBe(0);
+        expect(world.colliders.len()).toBe(0);
+        expect(world.impulseJoints.len()).toBe(0);
+    });
+});
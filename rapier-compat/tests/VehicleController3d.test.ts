@@ -0,0 +1,120 @@
+import {
+    init,
+    ColliderDesc,
+    DynamicRayCastVehicleController,
+    RigidBody,
+    RigidBodyDesc,
+    Vector3,
+    World,
+} from "../pkg3d";
+
+describe("3d/VehicleController", () => {
+    let world: World;
+    let chassis: RigidBody;
+    let vehicle: DynamicRayCastVehicleController;
+
+    beforeAll(init);
+
+    afterAll(async () => {
+        await Promise.resolve();
+    });
+
+    beforeEach(() => {
+        world = new World(new Vector3(0, -9.81, 0));
+        world.createCollider(ColliderDesc.cuboid(50.0, 0.1, 50.0));
+
+        // A chassis of mass 1, moving forward along its local X axis.
+        chassis = world.createRigidBody(
+            RigidBodyDesc.dynamic().setTranslation(0.0, 0.8, 0.0),
+        );
+        world.createCollider(ColliderDesc.cuboid(1.0, 0.25, 0.5), chassis);
+
+        vehicle = world.createVehicleController(chassis);
+
+        for (const [x, z] of [
+            [0.8, 0.5],
+            [0.8, -0.5],
+            [-0.8, 0.5],
+            [-0.8, -0.5],
+        ]) {
+            const i = vehicle.addWheel(
+                new Vector3(x, -0.1, z),
+                new Vector3(0.0, -1.0, 0.0),
+                new Vector3(0.0, 0.0, 1.0),
+                0.3,
+                0.3,
+            );
+            vehicle.setWheelSuspensionStiffness(i, 30.0);
+        }
+    });
+
+    afterEach(() => {
+        world.free();
+    });
+
+    function simulate(numSteps: number) {
+        for (let i = 0; i < numSteps; ++i) {
+            vehicle.updateVehicle(world.timestep);
+            world.step();
+        }
+    }
+
+    test("wheels", () => {
+        expect(vehicle.numWheels()).toBe(4);
+        expect(vehicle.chassis().handle).toBe(chassis.handle);
+        expect(vehicle.indexUpAxis()).toBe(1);
+        expect(vehicle.indexForwardAxis()).toBe(0);
+        expect(vehicle.wheelRadius(0)).toBeCloseTo(0.3);
+        expect(vehicle.wheelSuspensionStiffness(0)).toBeCloseTo(30.0);
+        expect(vehicle.wheelRadius(4)).toBeNull();
+    });
+
+    test("suspension", () => {
+        simulate(120);
+
+        // The chassis rests on its wheels, not on the ground.
+        for (let i = 0; i < vehicle.numWheels(); ++i) {
+            expect(vehicle.wheelIsInContact(i)).toBe(true);
+            expect(vehicle.wheelSuspensionLength(i)).toBeLessThan(0.3);
+        }
+        expect(chassis.translation().y).toBeGreaterThan(0.6);
+        expect(Math.abs(vehicle.currentVehicleSpeed())).toBeLessThan(0.1);
+    });
+
+    test("engine", () => {
+        simulate(60);
+
+        for (let i = 0; i < vehicle.numWheels(); ++i) {
+            vehicle.setWheelEngineForce(i, 1.0);
+        }
+
+        simulate(60);
+
+        expect(vehicle.currentVehicleSpeed()).toBeGreaterThan(0.5);
+        expect(chassis.translation().x).toBeGreaterThan(0.1);
+    });
+
+    test("dynamic ground", () => {
+        // A floating platform of mass 20 under the wheels.
+        const platform = world.createRigidBody(
+            RigidBodyDesc.dynamic()
+                .setTranslation(0.0, 0.3, 0.0)
+                .setGravityScale(0.0)
+                .lockRotations(),
+        );
+        world.createCollider(ColliderDesc.cuboid(5.0, 0.1, 5.0), platform);
+
+        simulate(60);
+
+        for (let i = 0; i < vehicle.numWheels(); ++i) {
+            expect(vehicle.wheelGroundObject(i)).not.toBeNull();
+            vehicle.setWheelEngineForce(i, 1.0);
+        }
+
+        simulate(60);
+
+        // The platform is pushed backward by the reaction of the engine force.
+        expect(vehicle.currentVehicleSpeed()).toBeGreaterThan(0.5);
+        expect(platform.linvel().x).toBeLessThan(-0.05);
+    });
+});
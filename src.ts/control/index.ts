@@ -1 +1,4 @@
 export * from "./character_controller";
+// #if DIM3
+export * from "./ray_cast_vehicle_controller";
+// #endif
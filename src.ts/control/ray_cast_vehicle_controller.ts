@@ -0,0 +1,485 @@
+import {RawDynamicRayCastVehicleController} from "../raw";
+import {Vector, VectorOps} from "../math";
+import {Collider, ColliderSet, InteractionGroups} from "../geometry";
+import {QueryFilterFlags, QueryPipeline} from "../pipeline";
+import {RigidBody, RigidBodySet} from "../dynamics";
+
+/**
+ * A raycast-based vehicle controller, simulating the wheels of a dynamic chassis
+ * rigid-body with suspensions, tire friction, engine forces, brakes, and steering.
+ *
+ * The wheels don’t have any rigid-body or collider: the ground below each wheel is detected
+ * by casting a ray along its suspension.
+ */
+export class DynamicRayCastVehicleController {
+    private raw: RawDynamicRayCastVehicleController;
+    private bodies: RigidBodySet;
+    private colliders: ColliderSet;
+    private queries: QueryPipeline;
+
+    constructor(
+        chassis: RigidBody,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        queries: QueryPipeline,
+    ) {
+        this.raw = new RawDynamicRayCastVehicleController(chassis.handle);
+        this.bodies = bodies;
+        this.colliders = colliders;
+        this.queries = queries;
+    }
+
+    /** @internal */
+    public free() {
+        if (!!this.raw) {
+            this.raw.free();
+        }
+
+        this.raw = undefined;
+    }
+
+    /**
+     * Updates the wheels of the vehicle, and applies the suspension, engine, braking, and
+     * friction impulses to the chassis and to the dynamic rigid-bodies the wheels touch.
+     *
+     * This should be called once before each physics step.
+     *
+     * @param dt - The timestep of the next physics step.
+     * @param filterFlags - Flags for excluding whole subsets of colliders from the ground detection.
+     * @param filterGroups - Groups for excluding colliders with incompatible collision groups from the
+     *                       ground detection.
+     * @param filterPredicate - Any collider for which this closure returns `false` will be excluded from the
+     *                          ground detection.
+     */
+    public updateVehicle(
+        dt: number,
+        filterFlags?: QueryFilterFlags,
+        filterGroups?: InteractionGroups,
+        filterPredicate?: (collider: Collider) => boolean,
+    ) {
+        this.raw.updateVehicle(
+            dt,
+            this.bodies.raw,
+            this.colliders.raw,
+            this.queries.raw,
+            filterFlags,
+            filterGroups,
+            this.colliders.castClosure(filterPredicate),
+        );
+    }
+
+    /**
+     * The speed of the chassis along its forward axis, computed by the last call to
+     * `this.updateVehicle`.
+     */
+    public currentVehicleSpeed(): number {
+        return this.raw.currentVehicleSpeed();
+    }
+
+    /**
+     * The rigid-body the wheels of this vehicle are attached to.
+     */
+    public chassis(): RigidBody {
+        return this.bodies.get(this.raw.chassis());
+    }
+
+    /**
+     * The index of the local-space axis of the chassis pointing upward (0 for X, 1 for Y, 2 for Z).
+     */
+    public indexUpAxis(): number {
+        return this.raw.indexUpAxis();
+    }
+
+    /**
+     * Sets the index of the local-space axis of the chassis pointing upward (0 for X, 1 for Y, 2 for Z).
+     */
+    public setIndexUpAxis(axis: number) {
+        this.raw.setIndexUpAxis(axis);
+    }
+
+    /**
+     * The index of the local-space axis of the chassis pointing forward (0 for X, 1 for Y, 2 for Z).
+     */
+    public indexForwardAxis(): number {
+        return this.raw.indexForwardAxis();
+    }
+
+    /**
+     * Sets the index of the local-space axis of the chassis pointing forward (0 for X, 1 for Y, 2 for Z).
+     */
+    public setIndexForwardAxis(axis: number) {
+        this.raw.setIndexForwardAxis(axis);
+    }
+
+    /**
+     * Adds a wheel to this vehicle.
+     *
+     * @param chassisConnectionCs - The point where the suspension is attached to the chassis, in the
+     *                              local-space of the chassis.
+     * @param directionCs - The direction of the suspension, pointing toward the ground, in the
+     *                      local-space of the chassis.
+     * @param axleCs - The rotation axis of the wheel, in the local-space of the chassis.
+     * @param suspensionRestLength - The length of the suspension when it isn’t compressed.
+     * @param radius - The radius of the wheel.
+     * @returns The index of the new wheel.
+     */
+    public addWheel(
+        chassisConnectionCs: Vector,
+        directionCs: Vector,
+        axleCs: Vector,
+        suspensionRestLength: number,
+        radius: number,
+    ): number {
+        let rawConnection = VectorOps.intoRaw(chassisConnectionCs);
+        let rawDirection = VectorOps.intoRaw(directionCs);
+        let rawAxle = VectorOps.intoRaw(axleCs);
+        let result = this.raw.addWheel(
+            rawConnection,
+            rawDirection,
+            rawAxle,
+            suspensionRestLength,
+            radius,
+        );
+        rawConnection.free();
+        rawDirection.free();
+        rawAxle.free();
+        return result;
+    }
+
+    /**
+     * The number of wheels attached to this vehicle.
+     */
+    public numWheels(): number {
+        return this.raw.numWheels();
+    }
+
+    /*
+     * Configuration of the wheels.
+     */
+
+    /**
+     * The point where the suspension of the `i`-th wheel is attached to the chassis, in the
+     * local-space of the chassis.
+     */
+    public wheelChassisConnectionPointCs(i: number): Vector | null {
+        return VectorOps.fromRaw(this.raw.wheelChassisConnectionPointCs(i));
+    }
+
+    /**
+     * Sets the point where the suspension of the `i`-th wheel is attached to the chassis, in the
+     * local-space of the chassis.
+     */
+    public setWheelChassisConnectionPointCs(i: number, value: Vector) {
+        let rawValue = VectorOps.intoRaw(value);
+        this.raw.setWheelChassisConnectionPointCs(i, rawValue);
+        rawValue.free();
+    }
+
+    /**
+     * The direction of the suspension of the `i`-th wheel, in the local-space of the chassis.
+     */
+    public wheelDirectionCs(i: number): Vector | null {
+        return VectorOps.fromRaw(this.raw.wheelDirectionCs(i));
+    }
+
+    /**
+     * Sets the direction of the suspension of the `i`-th wheel, in the local-space of the chassis.
+     */
+    public setWheelDirectionCs(i: number, value: Vector) {
+        let rawValue = VectorOps.intoRaw(value);
+        this.raw.setWheelDirectionCs(i, rawValue);
+        rawValue.free();
+    }
+
+    /**
+     * The rotation axis of the `i`-th wheel, in the local-space of the chassis.
+     */
+    public wheelAxleCs(i: number): Vector | null {
+        return VectorOps.fromRaw(this.raw.wheelAxleCs(i));
+    }
+
+    /**
+     * Sets the rotation axis of the `i`-th wheel, in the local-space of the chassis.
+     */
+    public setWheelAxleCs(i: number, value: Vector) {
+        let rawValue = VectorOps.intoRaw(value);
+        this.raw.setWheelAxleCs(i, rawValue);
+        rawValue.free();
+    }
+
+    /**
+     * The length of the suspension of the `i`-th wheel when it isn’t compressed.
+     */
+    public wheelSuspensionRestLength(i: number): number | null {
+        return this.raw.wheelSuspensionRestLength(i);
+    }
+
+    /**
+     * Sets the length of the suspension of the `i`-th wheel when it isn’t compressed.
+     */
+    public setWheelSuspensionRestLength(i: number, value: number) {
+        this.raw.setWheelSuspensionRestLength(i, value);
+    }
+
+    /**
+     * The maximum distance the suspension of the `i`-th wheel can travel from its rest length.
+     */
+    public wheelMaxSuspensionTravel(i: number): number | null {
+        return this.raw.wheelMaxSuspensionTravel(i);
+    }
+
+    /**
+     * Sets the maximum distance the suspension of the `i`-th wheel can travel from its rest length.
+     */
+    public setWheelMaxSuspensionTravel(i: number, value: number) {
+        this.raw.setWheelMaxSuspensionTravel(i, value);
+    }
+
+    /**
+     * The radius of the `i`-th wheel.
+     */
+    public wheelRadius(i: number): number | null {
+        return this.raw.wheelRadius(i);
+    }
+
+    /**
+     * Sets the radius of the `i`-th wheel.
+     */
+    public setWheelRadius(i: number, value: number) {
+        this.raw.setWheelRadius(i, value);
+    }
+
+    /**
+     * The stiffness of the suspension spring of the `i`-th wheel.
+     */
+    public wheelSuspensionStiffness(i: number): number | null {
+        return this.raw.wheelSuspensionStiffness(i);
+    }
+
+    /**
+     * Sets the stiffness of the suspension spring of the `i`-th wheel.
+     */
+    public setWheelSuspensionStiffness(i: number, value: number) {
+        this.raw.setWheelSuspensionStiffness(i, value);
+    }
+
+    /**
+     * The damping of the suspension of the `i`-th wheel while it is being compressed.
+     */
+    public wheelSuspensionCompression(i: number): number | null {
+        return this.raw.wheelSuspensionCompression(i);
+    }
+
+    /**
+     * Sets the damping of the suspension of the `i`-th wheel while it is being compressed.
+     */
+    public setWheelSuspensionCompression(i: number, value: number) {
+        this.raw.setWheelSuspensionCompression(i, value);
+    }
+
+    /**
+     * The damping of the suspension of the `i`-th wheel while it is being extended.
+     */
+    public wheelSuspensionRelaxation(i: number): number | null {
+        return this.raw.wheelSuspensionRelaxation(i);
+    }
+
+    /**
+     * Sets the damping of the suspension of the `i`-th wheel while it is being extended.
+     */
+    public setWheelSuspensionRelaxation(i: number, value: number) {
+        this.raw.setWheelSuspensionRelaxation(i, value);
+    }
+
+    /**
+     * The maximum force the suspension of the `i`-th wheel can apply to the chassis.
+     */
+    public wheelMaxSuspensionForce(i: number): number | null {
+        return this.raw.wheelMaxSuspensionForce(i);
+    }
+
+    /**
+     * Sets the maximum force the suspension of the `i`-th wheel can apply to the chassis.
+     */
+    public setWheelMaxSuspensionForce(i: number, value: number) {
+        this.raw.setWheelMaxSuspensionForce(i, value);
+    }
+
+    /**
+     * The ratio between the maximum friction impulse of the `i`-th wheel and its suspension force.
+     */
+    public wheelFrictionSlip(i: number): number | null {
+        return this.raw.wheelFrictionSlip(i);
+    }
+
+    /**
+     * Sets the ratio between the maximum friction impulse of the `i`-th wheel and its suspension force.
+     */
+    public setWheelFrictionSlip(i: number, value: number) {
+        this.raw.setWheelFrictionSlip(i, value);
+    }
+
+    /**
+     * The multiplier of the friction impulse of the `i`-th wheel along its axle.
+     */
+    public wheelSideFrictionStiffness(i: number): number | null {
+        return this.raw.wheelSideFrictionStiffness(i);
+    }
+
+    /**
+     * Sets the multiplier of the friction impulse of the `i`-th wheel along its axle.
+     */
+    public setWheelSideFrictionStiffness(i: number, value: number) {
+        this.raw.setWheelSideFrictionStiffness(i, value);
+    }
+
+    /**
+     * How much the side friction impulse of the `i`-th wheel makes the chassis roll, between 0 and 1.
+     */
+    public wheelRollInfluence(i: number): number | null {
+        return this.raw.wheelRollInfluence(i);
+    }
+
+    /**
+     * Sets how much the side friction impulse of the `i`-th wheel makes the chassis roll, between 0 and 1.
+     */
+    public setWheelRollInfluence(i: number, value: number) {
+        this.raw.setWheelRollInfluence(i, value);
+    }
+
+    /*
+     * Controls of the wheels.
+     */
+
+    /**
+     * The steering angle of the `i`-th wheel, around its suspension direction.
+     */
+    public wheelSteering(i: number): number | null {
+        return this.raw.wheelSteering(i);
+    }
+
+    /**
+     * Sets the steering angle of the `i`-th wheel, around its suspension direction.
+     */
+    public setWheelSteering(i: number, value: number) {
+        this.raw.setWheelSteering(i, value);
+    }
+
+    /**
+     * The forward force applied by the engine on the `i`-th wheel.
+     */
+    public wheelEngineForce(i: number): number | null {
+        return this.raw.wheelEngineForce(i);
+    }
+
+    /**
+     * Sets the forward force applied by the engine on the `i`-th wheel.
+     */
+    public setWheelEngineForce(i: number, value: number) {
+        this.raw.setWheelEngineForce(i, value);
+    }
+
+    /**
+     * The maximum braking impulse applied on the `i`-th wheel.
+     */
+    public wheelBrake(i: number): number | null {
+        return this.raw.wheelBrake(i);
+    }
+
+    /**
+     * Sets the maximum braking impulse applied on the `i`-th wheel.
+     */
+    public setWheelBrake(i: number, value: number) {
+        this.raw.setWheelBrake(i, value);
+    }
+
+    /*
+     * State of the wheels computed by the last call to `this.updateVehicle`.
+     */
+
+    /**
+     * The world-space position of the center of the `i`-th wheel.
+     */
+    public wheelCenter(i: number): Vector | null {
+        return VectorOps.fromRaw(this.raw.wheelCenter(i));
+    }
+
+    /**
+     * The rotation angle of the `i`-th wheel around its axle.
+     */
+    public wheelRotation(i: number): number | null {
+        return this.raw.wheelRotation(i);
+    }
+
+    /**
+     * The forward impulse applied by the `i`-th wheel.
+     */
+    public wheelForwardImpulse(i: number): number | null {
+        return this.raw.wheelForwardImpulse(i);
+    }
+
+    /**
+     * The side impulse applied by the `i`-th wheel.
+     */
+    public wheelSideImpulse(i: number): number | null {
+        return this.raw.wheelSideImpulse(i);
+    }
+
+    /**
+     * The force applied by the suspension of the `i`-th wheel.
+     */
+    public wheelSuspensionForce(i: number): number | null {
+        return this.raw.wheelSuspensionForce(i);
+    }
+
+    /**
+     * The length of the suspension of the `i`-th wheel.
+     */
+    public wheelSuspensionLength(i: number): number | null {
+        return this.raw.wheelSuspensionLength(i);
+    }
+
+    /**
+     * The ratio between the friction impulse applied by the `i`-th wheel and the impulse
+     * needed to prevent it from sliding: 1 if the wheel doesn’t skid, smaller than 1 if it does.
+     */
+    public wheelSkidInfo(i: number): number | null {
+        return this.raw.wheelSkidInfo(i);
+    }
+
+    /**
+     * Is the `i`-th wheel touching the ground?
+     */
+    public wheelIsInContact(i: number): boolean {
+        return this.raw.wheelIsInContact(i);
+    }
+
+    /**
+     * The world-space point where the `i`-th wheel touches the ground.
+     */
+    public wheelContactPoint(i: number): Vector | null {
+        return VectorOps.fromRaw(this.raw.wheelContactPoint(i));
+    }
+
+    /**
+     * The world-space normal of the ground touched by the `i`-th wheel.
+     */
+    public wheelContactNormal(i: number): Vector | null {
+        return VectorOps.fromRaw(this.raw.wheelContactNormal(i));
+    }
+
+    /**
+     * The world-space point where the suspension of the `i`-th wheel is attached to the chassis.
+     */
+    public wheelHardPoint(i: number): Vector | null {
+        return VectorOps.fromRaw(this.raw.wheelHardPoint(i));
+    }
+
+    /**
+     * The collider touched by the `i`-th wheel, or `null` if it doesn’t touch anything.
+     */
+    public wheelGroundObject(i: number): Collider | null {
+        let handle = this.raw.wheelGroundObject(i);
+        return handle === undefined ? null : this.colliders.get(handle);
+    }
+}
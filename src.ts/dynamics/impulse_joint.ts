@@ -25,6 +25,10 @@ export type ImpulseJointHandle = number;
  * - `Prismatic`: A prismatic joint that removes all degrees of freedom between the affected
  *                bodies except for the translation along one axis.
  * - `Spherical`: (3D only) A spherical joint that removes all relative linear degrees of freedom between the affected bodies.
+ * - `Generic`: Any other joint.
+ * - `Rope`: A rope joint that limits the maximum distance between the anchors of the affected bodies.
+ * - `Spring`: A spring joint that applies a damped force restoring the distance between the anchors
+ *             of the affected bodies to a rest length.
  */
 export enum JointType {
     Revolute,
@@ -33,6 +37,9 @@ export enum JointType {
     // #if DIM3
     Spherical,
     // #endif
+    Generic,
+    Rope,
+    Spring,
 }
 
 export enum MotorModel {
@@ -40,6 +47,51 @@ export enum MotorModel {
     ForceBased,
 }
 
+/**
+ * The relative degrees of freedom of a joint: `X`, `Y`, (`Z` in 3D) are the translations along
+ * the axes of its local frames, and `AngX`, (`AngY`, `AngZ` in 3D) the rotations around these axes.
+ */
+export enum JointAxis {
+    X,
+    Y,
+    // #if DIM3
+    Z,
+    // #endif
+    AngX,
+    // #if DIM3
+    AngY,
+    AngZ,
+    // #endif
+}
+
+/**
+ * A bit mask identifying multiple joint axes.
+ */
+export enum JointAxesMask {
+    X = 1 << JointAxis.X,
+    Y = 1 << JointAxis.Y,
+    // #if DIM3
+    Z = 1 << JointAxis.Z,
+    // #endif
+    AngX = 1 << JointAxis.AngX,
+    // #if DIM3
+    AngY = 1 << JointAxis.AngY,
+    AngZ = 1 << JointAxis.AngZ,
+    // #endif
+}
+
+/**
+ * The configuration of the motor of a joint along one of its axes.
+ */
+export interface JointMotorConfig {
+    targetPos: number;
+    targetVel: number;
+    stiffness: number;
+    damping: number;
+    model?: MotorModel;
+    maxForce?: number;
+}
+
 export class ImpulseJoint {
     protected rawSet: RawImpulseJointSet; // The ImpulseJoint won't need to free this.
     protected bodySet: RigidBodySet; // The ImpulseJoint won’t need to free this.
@@ -67,10 +119,16 @@ export class ImpulseJoint {
                 return new PrismaticImpulseJoint(rawSet, bodySet, handle);
             case JointType.Fixed:
                 return new FixedImpulseJoint(rawSet, bodySet, handle);
+            case JointType.Rope:
+                return new RopeImpulseJoint(rawSet, bodySet, handle);
+            case JointType.Spring:
+                return new SpringImpulseJoint(rawSet, bodySet, handle);
             // #if DIM3
             case JointType.Spherical:
                 return new SphericalImpulseJoint(rawSet, bodySet, handle);
             // #endif
+            case JointType.Generic:
+                return new GenericImpulseJoint(rawSet, bodySet, handle);
             default:
                 return new ImpulseJoint(rawSet, bodySet, handle);
         }
@@ -174,6 +232,34 @@ export class ImpulseJoint {
         rawPoint.free();
     }
 
+    /**
+     * Sets the local frame of this joint relative to the first rigid-body it is attached to.
+     *
+     * @param anchor - The new position of the first anchor.
+     * @param frame - The new orientation of the joint’s first local frame.
+     */
+    public setFrame1(anchor: Vector, frame: Rotation) {
+        const rawAnchor = VectorOps.intoRaw(anchor);
+        const rawFrame = RotationOps.intoRaw(frame);
+        this.rawSet.jointSetFrame1(this.handle, rawAnchor, rawFrame);
+        rawAnchor.free();
+        rawFrame.free();
+    }
+
+    /**
+     * Sets the local frame of this joint relative to the second rigid-body it is attached to.
+     *
+     * @param anchor - The new position of the second anchor.
+     * @param frame - The new orientation of the joint’s second local frame.
+     */
+    public setFrame2(anchor: Vector, frame: Rotation) {
+        const rawAnchor = VectorOps.intoRaw(anchor);
+        const rawFrame = RotationOps.intoRaw(frame);
+        this.rawSet.jointSetFrame2(this.handle, rawAnchor, rawFrame);
+        rawAnchor.free();
+        rawFrame.free();
+    }
+
     /**
      * Controls whether contacts are computed between colliders attached
      * to the rigid-bodies linked by this joint.
@@ -189,6 +275,47 @@ export class ImpulseJoint {
     public contactsEnabled(): boolean {
         return this.rawSet.jointContactsEnabled(this.handle);
     }
+
+    /**
+     * The impulse applied by the solver along the given axis of this joint during the last
+     * timestep, including the contributions of its limits and motors.
+     *
+     * Linear axes give a linear impulse, and angular axes give an angular impulse, both
+     * expressed in the local frame of this joint.
+     *
+     * @param axis - The joint axis.
+     */
+    public appliedImpulse(axis: JointAxis): number {
+        return this.rawSet.jointImpulse(this.handle, axis as number);
+    }
+
+    /**
+     * Makes this joint breakable.
+     *
+     * A breakable joint is removed automatically at the end of the first timestep during
+     * which it applies a force or a torque greater than the given thresholds. A joint-broken
+     * event is then generated if the world is stepped with an `EventQueue`.
+     *
+     * @param maxForce - The maximum force this joint can apply. Can be `Infinity`.
+     * @param maxTorque - The maximum torque this joint can apply. Can be `Infinity`.
+     */
+    public setBreakThresholds(maxForce: number, maxTorque: number) {
+        this.rawSet.jointSetBreakThresholds(this.handle, maxForce, maxTorque);
+    }
+
+    /**
+     * The maximum force this joint can apply before breaking, or `Infinity` if it isn’t breakable.
+     */
+    public breakForce(): number {
+        return this.rawSet.jointBreakForce(this.handle);
+    }
+
+    /**
+     * The maximum torque this joint can apply before breaking, or `Infinity` if it isn’t breakable.
+     */
+    public breakTorque(): number {
+        return this.rawSet.jointBreakTorque(this.handle);
+    }
 }
 
 export class UnitImpulseJoint extends ImpulseJoint {
@@ -274,10 +401,257 @@ export class UnitImpulseJoint extends ImpulseJoint {
             damping,
         );
     }
+
+    /**
+     * Is the motor of this joint enabled?
+     */
+    public motorEnabled(): boolean {
+        return this.rawSet.jointMotorEnabled(this.handle, this.rawAxis());
+    }
+
+    /**
+     * The model of the motor of this joint.
+     */
+    public motorModel(): MotorModel {
+        return this.rawSet.jointMotorModel(this.handle, this.rawAxis());
+    }
+
+    /**
+     * The target position of the motor of this joint.
+     */
+    public motorTargetPos(): number {
+        return this.rawSet.jointMotorTargetPos(this.handle, this.rawAxis());
+    }
+
+    /**
+     * The target velocity of the motor of this joint.
+     */
+    public motorTargetVel(): number {
+        return this.rawSet.jointMotorTargetVel(this.handle, this.rawAxis());
+    }
+
+    /**
+     * The stiffness of the motor of this joint.
+     */
+    public motorStiffness(): number {
+        return this.rawSet.jointMotorStiffness(this.handle, this.rawAxis());
+    }
+
+    /**
+     * The damping of the motor of this joint.
+     */
+    public motorDamping(): number {
+        return this.rawSet.jointMotorDamping(this.handle, this.rawAxis());
+    }
+
+    /**
+     * The maximum force the motor of this joint can deliver.
+     */
+    public motorMaxForce(): number {
+        return this.rawSet.jointMotorMaxForce(this.handle, this.rawAxis());
+    }
+
+    /**
+     * Sets the maximum force the motor of this joint can deliver.
+     */
+    public setMotorMaxForce(maxForce: number) {
+        this.rawSet.jointSetMotorMaxForce(
+            this.handle,
+            this.rawAxis(),
+            maxForce,
+        );
+    }
 }
 
 export class FixedImpulseJoint extends ImpulseJoint {}
 
+export class GenericImpulseJoint extends ImpulseJoint {
+    /**
+     * Are the limits of this joint along the given axis enabled?
+     */
+    public limitsEnabled(axis: JointAxis): boolean {
+        return this.rawSet.jointLimitsEnabled(this.handle, axis as number);
+    }
+
+    /**
+     * The min limit of this joint along the given axis.
+     */
+    public limitsMin(axis: JointAxis): number {
+        return this.rawSet.jointLimitsMin(this.handle, axis as number);
+    }
+
+    /**
+     * The max limit of this joint along the given axis.
+     */
+    public limitsMax(axis: JointAxis): number {
+        return this.rawSet.jointLimitsMax(this.handle, axis as number);
+    }
+
+    /**
+     * Sets the limits of this joint along the given axis.
+     *
+     * @param axis - The joint axis to limit.
+     * @param min - The minimum bound of the joint’s coordinate along this axis.
+     * @param max - The maximum bound of the joint’s coordinate along this axis.
+     */
+    public setLimits(axis: JointAxis, min: number, max: number) {
+        this.rawSet.jointSetLimits(this.handle, axis as number, min, max);
+    }
+
+    public configureMotorModel(axis: JointAxis, model: MotorModel) {
+        this.rawSet.jointConfigureMotorModel(
+            this.handle,
+            axis as number,
+            model as number,
+        );
+    }
+
+    public configureMotor(
+        axis: JointAxis,
+        targetPos: number,
+        targetVel: number,
+        stiffness: number,
+        damping: number,
+    ) {
+        this.rawSet.jointConfigureMotor(
+            this.handle,
+            axis as number,
+            targetPos,
+            targetVel,
+            stiffness,
+            damping,
+        );
+    }
+
+    /**
+     * Is the motor of this joint along the given axis enabled?
+     */
+    public motorEnabled(axis: JointAxis): boolean {
+        return this.rawSet.jointMotorEnabled(this.handle, axis as number);
+    }
+
+    /**
+     * The model of the motor of this joint along the given axis.
+     */
+    public motorModel(axis: JointAxis): MotorModel {
+        return this.rawSet.jointMotorModel(this.handle, axis as number);
+    }
+
+    /**
+     * The target position of the motor of this joint along the given axis.
+     */
+    public motorTargetPos(axis: JointAxis): number {
+        return this.rawSet.jointMotorTargetPos(this.handle, axis as number);
+    }
+
+    /**
+     * The target velocity of the motor of this joint along the given axis.
+     */
+    public motorTargetVel(axis: JointAxis): number {
+        return this.rawSet.jointMotorTargetVel(this.handle, axis as number);
+    }
+
+    /**
+     * The stiffness of the motor of this joint along the given axis.
+     */
+    public motorStiffness(axis: JointAxis): number {
+        return this.rawSet.jointMotorStiffness(this.handle, axis as number);
+    }
+
+    /**
+     * The damping of the motor of this joint along the given axis.
+     */
+    public motorDamping(axis: JointAxis): number {
+        return this.rawSet.jointMotorDamping(this.handle, axis as number);
+    }
+
+    /**
+     * The maximum force the motor of this joint along the given axis can deliver.
+     */
+    public motorMaxForce(axis: JointAxis): number {
+        return this.rawSet.jointMotorMaxForce(this.handle, axis as number);
+    }
+
+    /**
+     * Sets the maximum force the motor of this joint along the given axis can deliver.
+     */
+    public setMotorMaxForce(axis: JointAxis, maxForce: number) {
+        this.rawSet.jointSetMotorMaxForce(
+            this.handle,
+            axis as number,
+            maxForce,
+        );
+    }
+}
+
+export class RopeImpulseJoint extends ImpulseJoint {
+    /**
+     * The maximum distance allowed between the anchors of this joint.
+     */
+    public length(): number {
+        return this.rawSet.jointRopeLength(this.handle);
+    }
+
+    /**
+     * Sets the maximum distance allowed between the anchors of this joint.
+     *
+     * @param length - The new maximum distance.
+     */
+    public setLength(length: number) {
+        this.rawSet.jointSetRopeLength(this.handle, length);
+    }
+}
+
+export class SpringImpulseJoint extends ImpulseJoint {
+    /**
+     * The distance this spring tends to restore between its anchors.
+     */
+    public restLength(): number {
+        return this.rawSet.jointSpringRestLength(this.handle);
+    }
+
+    /**
+     * Sets the distance this spring tends to restore between its anchors.
+     *
+     * @param restLength - The new rest length.
+     */
+    public setRestLength(restLength: number) {
+        this.rawSet.jointSetSpringRestLength(this.handle, restLength);
+    }
+
+    /**
+     * The stiffness of this spring.
+     */
+    public stiffness(): number {
+        return this.rawSet.jointSpringStiffness(this.handle);
+    }
+
+    /**
+     * Sets the stiffness of this spring.
+     *
+     * @param stiffness - The new stiffness, in force per unit of length.
+     */
+    public setStiffness(stiffness: number) {
+        this.rawSet.jointSetSpringStiffness(this.handle, stiffness);
+    }
+
+    /**
+     * The damping coefficient of this spring.
+     */
+    public damping(): number {
+        return this.rawSet.jointSpringDamping(this.handle);
+    }
+
+    /**
+     * Sets the damping coefficient of this spring.
+     *
+     * @param damping - The new damping coefficient, in force per unit of relative velocity.
+     */
+    public setDamping(damping: number) {
+        this.rawSet.jointSetSpringDamping(this.handle, damping);
+    }
+}
+
 export class PrismaticImpulseJoint extends UnitImpulseJoint {
     public rawAxis(): RawJointAxis {
         return RawJointAxis.X;
@@ -324,9 +698,82 @@ export class JointData {
     jointType: JointType;
     limitsEnabled: boolean;
     limits: Array<number>;
+    length: number;
+    restLength: number;
+    stiffness: number;
+    damping: number;
+    lockedAxes: JointAxesMask;
+    axisLimits: Map<JointAxis, [number, number]>;
+    axisMotors: Map<JointAxis, JointMotorConfig>;
+    contactsEnabled: boolean;
 
     private constructor() {}
 
+    /**
+     * Creates a new joint descriptor that builds a generic joint.
+     *
+     * A generic joint locks the relative degrees of freedom given by `lockedAxes` between its
+     * local frames. Limits and motors can then be added to its free axes with `this.setLimits`
+     * and `this.setMotor`. This can be used to build, e.g., planar or cylindrical joints.
+     *
+     * @param anchor1 - Point where the joint is attached on the first rigid-body affected by this joint. Expressed in the
+     *                  local-space of the rigid-body.
+     * @param frame1 - The reference orientation of the joint wrt. the first rigid-body.
+     * @param anchor2 - Point where the joint is attached on the second rigid-body affected by this joint. Expressed in the
+     *                  local-space of the rigid-body.
+     * @param frame2 - The reference orientation of the joint wrt. the second rigid-body.
+     * @param lockedAxes - The relative degrees of freedom removed by this joint.
+     */
+    public static generic(
+        anchor1: Vector,
+        frame1: Rotation,
+        anchor2: Vector,
+        frame2: Rotation,
+        lockedAxes: JointAxesMask,
+    ): JointData {
+        let res = new JointData();
+        res.anchor1 = anchor1;
+        res.anchor2 = anchor2;
+        res.frame1 = frame1;
+        res.frame2 = frame2;
+        res.lockedAxes = lockedAxes;
+        res.jointType = JointType.Generic;
+        return res;
+    }
+
+    /**
+     * Limits the relative motion of a generic joint along the given axis.
+     *
+     * @param axis - The joint axis to limit.
+     * @param min - The minimum bound of the joint’s coordinate along this axis.
+     * @param max - The maximum bound of the joint’s coordinate along this axis.
+     */
+    public setLimits(axis: JointAxis, min: number, max: number): JointData {
+        this.axisLimits = this.axisLimits || new Map();
+        this.axisLimits.set(axis, [min, max]);
+        return this;
+    }
+
+    /**
+     * Adds a motor to a generic joint along the given axis.
+     *
+     * @param axis - The joint axis driven by the motor.
+     * @param motor - The configuration of the motor.
+     */
+    public setMotor(axis: JointAxis, motor: JointMotorConfig): JointData {
+        this.axisMotors = this.axisMotors || new Map();
+        this.axisMotors.set(axis, motor);
+        return this;
+    }
+
+    /**
+     * Sets whether contacts are enabled between the rigid-bodies attached by the joint.
+     */
+    public setContactsEnabled(enabled: boolean): JointData {
+        this.contactsEnabled = enabled;
+        return this;
+    }
+
     /**
      * Creates a new joint descriptor that builds a Fixed joint.
      *
@@ -355,6 +802,73 @@ export class JointData {
         return res;
     }
 
+    /**
+     * Creates a new joint descriptor that builds a rope joint.
+     *
+     * A rope joint prevents the distance between the anchors of the two attached rigid-bodies
+     * from exceeding `length`, but doesn’t prevent them from getting closer to each other.
+     * It can only be created as an impulse joint.
+     *
+     * Rope joints aren’t handled by the constraints solver: they are enforced by impulses applied
+     * to the attached rigid-bodies before each timestep. A stretched rope is brought back to `length`
+     * progressively, over several timesteps, and isn’t taken into account by continuous collision detection.
+     *
+     * @param length - The maximum distance allowed between the two anchors.
+     * @param anchor1 - Point where the joint is attached on the first rigid-body affected by this joint. Expressed in the
+     *                  local-space of the rigid-body.
+     * @param anchor2 - Point where the joint is attached on the second rigid-body affected by this joint. Expressed in the
+     *                  local-space of the rigid-body.
+     */
+    public static rope(
+        length: number,
+        anchor1: Vector,
+        anchor2: Vector,
+    ): JointData {
+        let res = new JointData();
+        res.anchor1 = anchor1;
+        res.anchor2 = anchor2;
+        res.length = length;
+        res.jointType = JointType.Rope;
+        return res;
+    }
+
+    /**
+     * Creates a new joint descriptor that builds a spring joint.
+     *
+     * A spring joint applies a force proportional to the difference between the distance separating
+     * the anchors of the two attached rigid-bodies and `restLength`, damped by their relative velocity.
+     * It can only be created as an impulse joint.
+     *
+     * Spring joints aren’t handled by the constraints solver: their force is integrated explicitly and
+     * applied as an impulse to the attached rigid-bodies before each timestep. Large stiffnesses or
+     * dampings relative to the masses of the rigid-bodies, or large timesteps, can make the simulation
+     * unstable. Spring joints aren’t taken into account by continuous collision detection.
+     *
+     * @param restLength - The distance the spring tends to restore between the two anchors.
+     * @param stiffness - The spring stiffness, in force per unit of length.
+     * @param damping - The damping coefficient, in force per unit of relative velocity.
+     * @param anchor1 - Point where the joint is attached on the first rigid-body affected by this joint. Expressed in the
+     *                  local-space of the rigid-body.
+     * @param anchor2 - Point where the joint is attached on the second rigid-body affected by this joint. Expressed in the
+     *                  local-space of the rigid-body.
+     */
+    public static spring(
+        restLength: number,
+        stiffness: number,
+        damping: number,
+        anchor1: Vector,
+        anchor2: Vector,
+    ): JointData {
+        let res = new JointData();
+        res.anchor1 = anchor1;
+        res.anchor2 = anchor2;
+        res.restLength = restLength;
+        res.stiffness = stiffness;
+        res.damping = damping;
+        res.jointType = JointType.Spring;
+        return res;
+    }
+
     // #if DIM2
 
     /**
@@ -527,6 +1041,63 @@ export class JointData {
 
                 rawAx.free();
                 break;
+            case JointType.Rope:
+                result = RawGenericJoint.rope(this.length, rawA1, rawA2);
+                break;
+            case JointType.Generic:
+                let rawGenFra1 = RotationOps.intoRaw(this.frame1);
+                let rawGenFra2 = RotationOps.intoRaw(this.frame2);
+                result = RawGenericJoint.generic(
+                    rawA1,
+                    rawGenFra1,
+                    rawA2,
+                    rawGenFra2,
+                    this.lockedAxes,
+                );
+                rawGenFra1.free();
+                rawGenFra2.free();
+
+                if (!!this.axisLimits) {
+                    this.axisLimits.forEach(([min, max], axis) => {
+                        result.setLimits(axis as number, min, max);
+                    });
+                }
+
+                if (!!this.axisMotors) {
+                    this.axisMotors.forEach((motor, axis) => {
+                        result.setMotor(
+                            axis as number,
+                            motor.targetPos,
+                            motor.targetVel,
+                            motor.stiffness,
+                            motor.damping,
+                        );
+
+                        if (motor.model !== undefined) {
+                            result.setMotorModel(
+                                axis as number,
+                                motor.model as number,
+                            );
+                        }
+
+                        if (motor.maxForce !== undefined) {
+                            result.setMotorMaxForce(
+                                axis as number,
+                                motor.maxForce,
+                            );
+                        }
+                    });
+                }
+                break;
+            case JointType.Spring:
+                result = RawGenericJoint.spring(
+                    this.restLength,
+                    this.stiffness,
+                    this.damping,
+                    rawA1,
+                    rawA2,
+                );
+                break;
             // #if DIM2
             case JointType.Revolute:
                 result = RawGenericJoint.revolute(rawA1, rawA2);
@@ -547,6 +1118,10 @@ export class JointData {
         rawA1.free();
         rawA2.free();
 
+        if (result && this.contactsEnabled !== undefined) {
+            result.setContactsEnabled(this.contactsEnabled);
+        }
+
         return result;
     }
 }
@@ -58,6 +58,16 @@ export class ImpulseJointSet {
         this.map.forEach((joint) => joint.finalizeDeserialization(bodies));
     }
 
+    /** @internal */
+    public registerInserted(
+        bodies: RigidBodySet,
+        handle: ImpulseJointHandle,
+    ): ImpulseJoint {
+        const joint = ImpulseJoint.newTyped(this.raw, bodies, handle);
+        this.map.set(handle, joint);
+        return joint;
+    }
+
     /**
      * Creates a new joint and return its integer handle.
      *
@@ -0,0 +1,57 @@
+import {RawMassProperties} from "../raw";
+import {Vector, VectorOps} from "../math";
+// #if DIM3
+import {Rotation, RotationOps} from "../math";
+// #endif
+
+/**
+ * The mass, center-of-mass, and angular inertia of a rigid-body or a collider.
+ */
+export class MassProperties {
+    /**
+     * The mass.
+     */
+    mass: number;
+    /**
+     * The center-of-mass, expressed in the local-space of the rigid-body or collider.
+     */
+    localCom: Vector;
+    // #if DIM2
+    /**
+     * The principal angular inertia.
+     */
+    principalInertia: number;
+    // #endif
+    // #if DIM3
+    /**
+     * The principal angular inertia, i.e., the angular inertia along each axis of
+     * `principalInertiaFrame`.
+     */
+    principalInertia: Vector;
+    /**
+     * The orientation of the principal angular inertia axes, expressed in the local-space
+     * of the rigid-body or collider.
+     */
+    principalInertiaFrame: Rotation;
+    // #endif
+
+    /** @internal */
+    public static fromRaw(raw: RawMassProperties): MassProperties {
+        if (!raw) return null;
+
+        const result = new MassProperties();
+        result.mass = raw.mass();
+        result.localCom = VectorOps.fromRaw(raw.localCom());
+        // #if DIM2
+        result.principalInertia = raw.principalInertia();
+        // #endif
+        // #if DIM3
+        result.principalInertia = VectorOps.fromRaw(raw.principalInertia());
+        result.principalInertiaFrame = RotationOps.fromRaw(
+            raw.principalInertiaFrame(),
+        );
+        // #endif
+        raw.free();
+        return result;
+    }
+}
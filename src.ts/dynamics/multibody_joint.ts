@@ -1,12 +1,16 @@
 import {RawImpulseJointSet, RawJointAxis, RawMultibodyJointSet} from "../raw";
+import {Rotation, RotationOps, Vector, VectorOps} from "../math";
 import {
     FixedImpulseJoint,
     ImpulseJointHandle,
+    JointAxesMask,
     JointType,
     MotorModel,
     PrismaticImpulseJoint,
     RevoluteImpulseJoint,
 } from "./impulse_joint";
+import {RigidBodyHandle} from "./rigid_body";
+import {RigidBodySet} from "./rigid_body_set";
 
 // #if DIM3
 import {Quaternion} from "../math";
@@ -18,6 +22,64 @@ import {SphericalImpulseJoint} from "./impulse_joint";
  */
 export type MultibodyJointHandle = number;
 
+/**
+ * The options of the inverse-kinematics solver of multibodies.
+ */
+export interface InverseKinematicsOptions {
+    /**
+     * The world-space axes of the target pose that have to be reached. Use the linear axes
+     * only to ignore the target rotation. Defaults to all the axes.
+     */
+    constrainedAxes?: JointAxesMask;
+    /**
+     * The maximum number of iterations of the solver. Defaults to 50.
+     */
+    maxIters?: number;
+    /**
+     * The damping factor of the solver. Greater values improve the stability of the resolution
+     * close to singularities, at the cost of a slower convergence. Defaults to 0.1.
+     */
+    damping?: number;
+    /**
+     * The distance to the target position below which the solver stops. Defaults to 1.0e-3.
+     */
+    epsilonLinear?: number;
+    /**
+     * The angle to the target orientation below which the solver stops. Defaults to 1.0e-3.
+     */
+    epsilonAngular?: number;
+    /**
+     * If `true`, the computed coordinates are applied to the joints, and their rigid-bodies
+     * are teleported accordingly. Defaults to `false`.
+     */
+    apply?: boolean;
+}
+
+/**
+ * The result of the inverse-kinematics solver of multibodies.
+ */
+export interface InverseKinematicsResult {
+    /**
+     * Did the solver reach the target pose within the requested tolerances?
+     */
+    converged: boolean;
+    /**
+     * The generalized coordinates computed for each joint with at least one degree of
+     * freedom between the root of the multibody and the target link.
+     */
+    positions: Map<MultibodyJointHandle, Float32Array>;
+}
+
+const ALL_AXES =
+    JointAxesMask.X |
+    JointAxesMask.Y |
+    // #if DIM3
+    JointAxesMask.Z |
+    JointAxesMask.AngY |
+    JointAxesMask.AngZ |
+    // #endif
+    JointAxesMask.AngX;
+
 export class MultibodyJoint {
     protected rawSet: RawMultibodyJointSet; // The MultibodyJoint won't need to free this.
     handle: MultibodyJointHandle;
@@ -55,66 +117,122 @@ export class MultibodyJoint {
         return this.rawSet.contains(this.handle);
     }
 
-    // /**
-    //  * The unique integer identifier of the first rigid-body this joint it attached to.
-    //  */
-    // public bodyHandle1(): RigidBodyHandle {
-    //     return this.rawSet.jointBodyHandle1(this.handle);
-    // }
-    //
-    // /**
-    //  * The unique integer identifier of the second rigid-body this joint is attached to.
-    //  */
-    // public bodyHandle2(): RigidBodyHandle {
-    //     return this.rawSet.jointBodyHandle2(this.handle);
-    // }
-    //
-    // /**
-    //  * The type of this joint given as a string.
-    //  */
-    // public type(): JointType {
-    //     return this.rawSet.jointType(this.handle);
-    // }
-    //
-    // // #if DIM3
-    // /**
-    //  * The rotation quaternion that aligns this joint's first local axis to the `x` axis.
-    //  */
-    // public frameX1(): Rotation {
-    //     return RotationOps.fromRaw(this.rawSet.jointFrameX1(this.handle));
-    // }
-    //
-    // // #endif
-    //
-    // // #if DIM3
-    // /**
-    //  * The rotation matrix that aligns this joint's second local axis to the `x` axis.
-    //  */
-    // public frameX2(): Rotation {
-    //     return RotationOps.fromRaw(this.rawSet.jointFrameX2(this.handle));
-    // }
-    //
-    // // #endif
-    //
-    // /**
-    //  * The position of the first anchor of this joint.
-    //  *
-    //  * The first anchor gives the position of the points application point on the
-    //  * local frame of the first rigid-body it is attached to.
-    //  */
-    // public anchor1(): Vector {
-    //     return VectorOps.fromRaw(this.rawSet.jointAnchor1(this.handle));
-    // }
-    //
-    // /**
-    //  * The position of the second anchor of this joint.
-    //  *
-    //  * The second anchor gives the position of the points application point on the
-    //  * local frame of the second rigid-body it is attached to.
-    //  */
-    // public anchor2(): Vector {
-    //     return VectorOps.fromRaw(this.rawSet.jointAnchor2(this.handle));
-    // }
+    /**
+     * The unique integer identifier of the first rigid-body this joint it attached to,
+     * i.e., the rigid-body of the parent link.
+     *
+     * Returns `undefined` if this is the joint of the root of a multibody.
+     */
+    public bodyHandle1(): RigidBodyHandle | undefined {
+        return this.rawSet.jointBodyHandle1(this.handle);
+    }
+
+    /**
+     * The unique integer identifier of the second rigid-body this joint is attached to,
+     * i.e., the rigid-body of the child link.
+     */
+    public bodyHandle2(): RigidBodyHandle {
+        return this.rawSet.jointBodyHandle2(this.handle);
+    }
+
+    /**
+     * The type of this joint given as a string.
+     */
+    public type(): JointType {
+        return this.rawSet.jointType(this.handle);
+    }
+
+    // #if DIM3
+    /**
+     * The rotation quaternion that aligns this joint's first local axis to the `x` axis.
+     */
+    public frameX1(): Rotation {
+        return RotationOps.fromRaw(this.rawSet.jointFrameX1(this.handle));
+    }
+
+    // #endif
+
+    // #if DIM3
+    /**
+     * The rotation matrix that aligns this joint's second local axis to the `x` axis.
+     */
+    public frameX2(): Rotation {
+        return RotationOps.fromRaw(this.rawSet.jointFrameX2(this.handle));
+    }
+
+    // #endif
+
+    /**
+     * The position of the first anchor of this joint.
+     *
+     * The first anchor gives the position of the points application point on the
+     * local frame of the first rigid-body it is attached to.
+     */
+    public anchor1(): Vector {
+        return VectorOps.fromRaw(this.rawSet.jointAnchor1(this.handle));
+    }
+
+    /**
+     * The position of the second anchor of this joint.
+     *
+     * The second anchor gives the position of the points application point on the
+     * local frame of the second rigid-body it is attached to.
+     */
+    public anchor2(): Vector {
+        return VectorOps.fromRaw(this.rawSet.jointAnchor2(this.handle));
+    }
+
+    /**
+     * Sets the position of the first anchor of this joint.
+     *
+     * The first anchor gives the position of the application point on the
+     * local frame of the first rigid-body it is attached to.
+     */
+    public setAnchor1(newPos: Vector) {
+        const rawPoint = VectorOps.intoRaw(newPos);
+        this.rawSet.jointSetAnchor1(this.handle, rawPoint);
+        rawPoint.free();
+    }
+
+    /**
+     * Sets the position of the second anchor of this joint.
+     *
+     * The second anchor gives the position of the application point on the
+     * local frame of the second rigid-body it is attached to.
+     */
+    public setAnchor2(newPos: Vector) {
+        const rawPoint = VectorOps.intoRaw(newPos);
+        this.rawSet.jointSetAnchor2(this.handle, rawPoint);
+        rawPoint.free();
+    }
+
+    /**
+     * Sets the local frame of this joint relative to the first rigid-body it is attached to.
+     *
+     * @param anchor - The new position of the first anchor.
+     * @param frame - The new orientation of the joint’s first local frame.
+     */
+    public setFrame1(anchor: Vector, frame: Rotation) {
+        const rawAnchor = VectorOps.intoRaw(anchor);
+        const rawFrame = RotationOps.intoRaw(frame);
+        this.rawSet.jointSetFrame1(this.handle, rawAnchor, rawFrame);
+        rawAnchor.free();
+        rawFrame.free();
+    }
+
+    /**
+     * Sets the local frame of this joint relative to the second rigid-body it is attached to.
+     *
+     * @param anchor - The new position of the second anchor.
+     * @param frame - The new orientation of the joint’s second local frame.
+     */
+    public setFrame2(anchor: Vector, frame: Rotation) {
+        const rawAnchor = VectorOps.intoRaw(anchor);
+        const rawFrame = RotationOps.intoRaw(frame);
+        this.rawSet.jointSetFrame2(this.handle, rawAnchor, rawFrame);
+        rawAnchor.free();
+        rawFrame.free();
+    }
 
     /**
      * Controls whether contacts are computed between colliders attached
@@ -131,6 +249,142 @@ export class MultibodyJoint {
     public contactsEnabled(): boolean {
         return this.rawSet.jointContactsEnabled(this.handle);
     }
+
+    /**
+     * The index of the link attached to its parent by this joint, in its multibody.
+     *
+     * The root of a multibody has the index 0, and the parent of a link always has a
+     * smaller index than the link itself.
+     */
+    public linkId(): number {
+        return this.rawSet.jointLinkId(this.handle);
+    }
+
+    /**
+     * The number of degrees of freedom of this joint, i.e., the number of its
+     * generalized coordinates.
+     */
+    public numDofs(): number {
+        return this.rawSet.jointNumDofs(this.handle);
+    }
+
+    /**
+     * The generalized coordinates of this joint.
+     *
+     * There is one coordinate per free axis of the joint: the free linear axes come
+     * first, followed by the free angular axes. If all the angular axes are free, the
+     * angular coordinates form the rotation vector of the joint.
+     */
+    public positions(): Float32Array {
+        return this.rawSet.jointPositions(this.handle);
+    }
+
+    /**
+     * Sets the generalized coordinates of this joint.
+     *
+     * The rigid-bodies of the link attached by this joint and of all its descendants
+     * are teleported accordingly.
+     *
+     * @param bodies - The set of rigid-bodies attached by the joints of this multibody.
+     * @param positions - The new generalized coordinates, in the same order as `this.positions()`.
+     */
+    public setPositions(bodies: RigidBodySet, positions: ArrayLike<number>) {
+        this.rawSet.jointSetPositions(
+            this.handle,
+            bodies.raw,
+            new Float32Array(positions),
+        );
+    }
+
+    /**
+     * The generalized velocities of this joint, in the same order as `this.positions()`.
+     */
+    public velocities(): Float32Array {
+        return this.rawSet.jointVelocities(this.handle);
+    }
+
+    /**
+     * Sets the generalized velocities of this joint.
+     *
+     * @param bodies - The set of rigid-bodies attached by the joints of this multibody.
+     * @param velocities - The new generalized velocities, in the same order as `this.positions()`.
+     */
+    public setVelocities(bodies: RigidBodySet, velocities: ArrayLike<number>) {
+        this.rawSet.jointSetVelocities(
+            this.handle,
+            bodies.raw,
+            new Float32Array(velocities),
+        );
+    }
+
+    /**
+     * Adds generalized forces along the degrees of freedom of this joint.
+     *
+     * Just like the forces added to rigid-bodies, they are applied at each timestep
+     * until the forces of the rigid-bodies attached by this joint are reset.
+     *
+     * @param bodies - The set of rigid-bodies attached by the joints of this multibody.
+     * @param forces - The generalized forces, in the same order as `this.positions()`.
+     */
+    public addGeneralizedForces(
+        bodies: RigidBodySet,
+        forces: ArrayLike<number>,
+    ) {
+        this.rawSet.jointAddGeneralizedForces(
+            this.handle,
+            bodies.raw,
+            new Float32Array(forces),
+        );
+    }
+
+    /**
+     * Computes the generalized coordinates of the joints between the root of this joint's
+     * multibody and the link attached by this joint, so that this link reaches the given
+     * world-space pose.
+     *
+     * The root of the multibody is never moved, and the coordinates are clamped to the
+     * limits of their joints. The jacobian of the kinematic chain is approximated by finite
+     * differences, with a fixed step of `1.0e-3` on each generalized coordinate, which may slow
+     * down the convergence for multibodies much smaller or larger than one unit of length.
+     *
+     * @param bodies - The set of rigid-bodies attached by the joints of this multibody.
+     * @param targetTranslation - The world-space position the link must reach.
+     * @param targetRotation - The world-space orientation the link must reach.
+     * @param options - The options of the solver.
+     */
+    public inverseKinematics(
+        bodies: RigidBodySet,
+        targetTranslation: Vector,
+        targetRotation: Rotation,
+        options?: InverseKinematicsOptions,
+    ): InverseKinematicsResult {
+        const opts = options || {};
+        const rawTranslation = VectorOps.intoRaw(targetTranslation);
+        const rawRotation = RotationOps.intoRaw(targetRotation);
+        const rawResult = this.rawSet.jointInverseKinematics(
+            this.handle,
+            bodies.raw,
+            rawTranslation,
+            rawRotation,
+            opts.constrainedAxes ?? ALL_AXES,
+            opts.maxIters ?? 50,
+            opts.damping ?? 0.1,
+            opts.epsilonLinear ?? 1.0e-3,
+            opts.epsilonAngular ?? 1.0e-3,
+            !!opts.apply,
+        );
+
+        const positions = new Map<MultibodyJointHandle, Float32Array>();
+        for (let i = 0; i < rawResult.numJoints(); ++i) {
+            positions.set(rawResult.joint(i), rawResult.positions(i));
+        }
+        const result = {converged: rawResult.converged(), positions};
+
+        rawTranslation.free();
+        rawRotation.free();
+        rawResult.free();
+        return result;
+    }
 }
 
 export class UnitMultibodyJoint extends MultibodyJoint {
@@ -139,42 +393,143 @@ export class UnitMultibodyJoint extends MultibodyJoint {
      */
     protected rawAxis?(): RawJointAxis;
 
-    // /**
-    //  * Are the limits enabled for this joint?
-    //  */
-    // public limitsEnabled(): boolean {
-    //     return this.rawSet.jointLimitsEnabled(this.handle, this.rawAxis());
-    // }
-    //
-    // /**
-    //  * The min limit of this joint.
-    //  */
-    // public limitsMin(): number {
-    //     return this.rawSet.jointLimitsMin(this.handle, this.rawAxis());
-    // }
-    //
-    // /**
-    //  * The max limit of this joint.
-    //  */
-    // public limitsMax(): number {
-    //     return this.rawSet.jointLimitsMax(this.handle, this.rawAxis());
-    // }
-    //
-    // public configureMotorModel(model: MotorModel) {
-    //     this.rawSet.jointConfigureMotorModel(this.handle, this.rawAxis(), model);
-    // }
-    //
-    // public configureMotorVelocity(targetVel: number, factor: number) {
-    //     this.rawSet.jointConfigureMotorVelocity(this.handle, this.rawAxis(), targetVel, factor);
-    // }
-    //
-    // public configureMotorPosition(targetPos: number, stiffness: number, damping: number) {
-    //     this.rawSet.jointConfigureMotorPosition(this.handle, this.rawAxis(), targetPos, stiffness, damping);
-    // }
-    //
-    // public configureMotor(targetPos: number, targetVel: number, stiffness: number, damping: number) {
-    //     this.rawSet.jointConfigureMotor(this.handle, this.rawAxis(), targetPos, targetVel, stiffness, damping);
-    // }
+    /**
+     * Are the limits enabled for this joint?
+     */
+    public limitsEnabled(): boolean {
+        return this.rawSet.jointLimitsEnabled(this.handle, this.rawAxis());
+    }
+
+    /**
+     * The min limit of this joint.
+     */
+    public limitsMin(): number {
+        return this.rawSet.jointLimitsMin(this.handle, this.rawAxis());
+    }
+
+    /**
+     * The max limit of this joint.
+     */
+    public limitsMax(): number {
+        return this.rawSet.jointLimitsMax(this.handle, this.rawAxis());
+    }
+
+    /**
+     * Sets the limits of this joint.
+     *
+     * @param min - The minimum bound of this joint’s free coordinate.
+     * @param max - The maximum bound of this joint’s free coordinate.
+     */
+    public setLimits(min: number, max: number) {
+        this.rawSet.jointSetLimits(this.handle, this.rawAxis(), min, max);
+    }
+
+    public configureMotorModel(model: MotorModel) {
+        this.rawSet.jointConfigureMotorModel(
+            this.handle,
+            this.rawAxis(),
+            model,
+        );
+    }
+
+    public configureMotorVelocity(targetVel: number, factor: number) {
+        this.rawSet.jointConfigureMotorVelocity(
+            this.handle,
+            this.rawAxis(),
+            targetVel,
+            factor,
+        );
+    }
+
+    public configureMotorPosition(
+        targetPos: number,
+        stiffness: number,
+        damping: number,
+    ) {
+        this.rawSet.jointConfigureMotorPosition(
+            this.handle,
+            this.rawAxis(),
+            targetPos,
+            stiffness,
+            damping,
+        );
+    }
+
+    public configureMotor(
+        targetPos: number,
+        targetVel: number,
+        stiffness: number,
+        damping: number,
+    ) {
+        this.rawSet.jointConfigureMotor(
+            this.handle,
+            this.rawAxis(),
+            targetPos,
+            targetVel,
+            stiffness,
+            damping,
+        );
+    }
+
+    /**
+     * Is the motor of this joint enabled?
+     */
+    public motorEnabled(): boolean {
+        return this.rawSet.jointMotorEnabled(this.handle, this.rawAxis());
+    }
+
+    /**
+     * The model of the motor of this joint.
+     */
+    public motorModel(): MotorModel {
+        return this.rawSet.jointMotorModel(this.handle, this.rawAxis());
+    }
+
+    /**
+     * The target position of the motor of this joint.
+     */
+    public motorTargetPos(): number {
+        return this.rawSet.jointMotorTargetPos(this.handle, this.rawAxis());
+    }
+
+    /**
+     * The target velocity of the motor of this joint.
+     */
+    public motorTargetVel(): number {
+        return this.rawSet.jointMotorTargetVel(this.handle, this.rawAxis());
+    }
+
+    /**
+     * The stiffness of the motor of this joint.
+     */
+    public motorStiffness(): number {
+        return this.rawSet.jointMotorStiffness(this.handle, this.rawAxis());
+    }
+
+    /**
+     * The damping of the motor of this joint.
+     */
+    public motorDamping(): number {
+        return this.rawSet.jointMotorDamping(this.handle, this.rawAxis());
+    }
+
+    /**
+     * The maximum force the motor of this joint can deliver.
+     */
+    public motorMaxForce(): number {
+        return this.rawSet.jointMotorMaxForce(this.handle, this.rawAxis());
+    }
+
+    /**
+     * Sets the maximum force the motor of this joint can deliver.
+     */
+    public setMotorMaxForce(maxForce: number) {
+        this.rawSet.jointSetMotorMaxForce(
+            this.handle,
+            this.rawAxis(),
+            maxForce,
+        );
+    }
 }
 
 export class FixedMultibodyJoint extends MultibodyJoint {}
@@ -52,9 +52,19 @@ export class MultibodyJointSet {
         }
     }
 
+    /** @internal */
+    public registerInserted(handle: MultibodyJointHandle): MultibodyJoint {
+        const joint = MultibodyJoint.newTyped(this.raw, handle);
+        this.map.set(handle, joint);
+        return joint;
+    }
+
     /**
      * Creates a new joint and return its integer handle.
      *
+     * Throws an error if `desc` describes a rope or spring joint: these joints can only be
+     * created as impulse joints.
+     *
      * @param desc - The joint's parameters.
      * @param parent1 - The handle of the first rigid-body this joint is attached to.
      * @param parent2 - The handle of the second rigid-body this joint is attached to.
@@ -67,13 +77,12 @@ export class MultibodyJointSet {
         wakeUp: boolean,
     ): MultibodyJoint {
         const rawParams = desc.intoRaw();
-        const handle = this.raw.createJoint(
-            rawParams,
-            parent1,
-            parent2,
-            wakeUp,
-        );
-        rawParams.free();
+        let handle: MultibodyJointHandle;
+        try {
+            handle = this.raw.createJoint(rawParams, parent1, parent2, wakeUp);
+        } finally {
+            rawParams.free();
+        }
         let joint = MultibodyJoint.newTyped(this.raw, handle);
         this.map.set(handle, joint);
         return joint;
@@ -134,6 +143,50 @@ export class MultibodyJointSet {
         this.map.forEach(f);
     }
 
+    /**
+     * Gets the joint attaching the given rigid-body to its parent link.
+     *
+     * Returns `null` if the rigid-body isn't part of any multibody.
+     *
+     * @param handle - The integer handle of the rigid-body.
+     */
+    public getForRigidBody(handle: RigidBodyHandle): MultibodyJoint | null {
+        const jointHandle = this.raw.jointForRigidBody(handle);
+        return jointHandle === undefined ? null : this.getLink(jointHandle);
+    }
+
+    /**
+     * Calls the given closure with each joint of the multibody containing the given joint.
+     *
+     * Each joint attaches one link of the multibody to its parent. The joints are visited in
+     * increasing link index order, so the joint of a parent link is always visited before the
+     * joints of its children.
+     *
+     * @param handle - The integer handle of a joint of the multibody.
+     * @param f - The closure to apply.
+     */
+    public forEachMultibodyLink(
+        handle: MultibodyJointHandle,
+        f: (joint: MultibodyJoint) => void,
+    ) {
+        this.raw.forEachMultibodyLink(
+            handle,
+            (linkHandle: MultibodyJointHandle) => {
+                f(this.getLink(linkHandle));
+            },
+        );
+    }
+
+    /**
+     * Gets the joint attaching a link of a multibody to its parent.
+     *
+     * The joint of the root of a multibody isn't created by the user, so it isn't part of this
+     * set: a new object is created to represent it.
+     */
+    private getLink(handle: MultibodyJointHandle): MultibodyJoint {
+        return this.get(handle) || MultibodyJoint.newTyped(this.raw, handle);
+    }
+
     /**
      * Calls the given closure with the integer handle of each multibody joint attached to this rigid-body.
      *
@@ -0,0 +1,324 @@
+import {RawRagdoll, RawRagdollBuilder} from "../raw";
+import {Vector, VectorOps} from "../math";
+import {ImpulseJointHandle, ImpulseJointSet} from "./impulse_joint_set";
+import {MultibodyJointHandle, MultibodyJointSet} from "./multibody_joint_set";
+import {RigidBodyHandle} from "./rigid_body";
+import {RigidBodySet} from "./rigid_body_set";
+import {ColliderHandle, ColliderSet} from "../geometry";
+
+interface RagdollBoneDesc {
+    parent?: number;
+    start: Vector;
+    end: Vector;
+    radius: number;
+    // #if DIM3
+    swingLimit?: number;
+    twistLimits?: [number, number];
+    axis?: Vector;
+    // #endif
+    limits?: [number, number];
+}
+
+/**
+ * A description of the skeleton of a ragdoll.
+ *
+ * Bones are given in world-space, at the rest pose of the ragdoll. The joint limits are
+ * expressed relative to this rest pose. Each bone is converted into a rigid-body with a
+ * capsule collider, attached to its parent bone by a joint located at the start of the bone.
+ */
+export class RagdollDesc {
+    private bones: RagdollBoneDesc[] = [];
+    /**
+     * The density of the colliders of the bones.
+     */
+    density = 1.0;
+    /**
+     * If `true`, the bones are attached by multibody joints instead of impulse joints.
+     */
+    multibody = false;
+
+    /**
+     * The number of bones added to this ragdoll.
+     */
+    public numBones(): number {
+        return this.bones.length;
+    }
+
+    /**
+     * Adds a bone that isn’t attached to any parent bone.
+     *
+     * @param start - The world-space position of the start of the bone.
+     * @param end - The world-space position of the end of the bone.
+     * @param radius - The radius of the capsule collider of the bone.
+     * @returns The index of the new bone.
+     */
+    public addRootBone(start: Vector, end: Vector, radius: number): number {
+        this.bones.push({start, end, radius});
+        return this.bones.length - 1;
+    }
+
+    // #if DIM3
+    /**
+     * Adds a bone attached to its parent by a spherical joint located at the start of the bone.
+     *
+     * @param parent - The index of the parent bone.
+     * @param start - The world-space position of the start of the bone.
+     * @param end - The world-space position of the end of the bone.
+     * @param radius - The radius of the capsule collider of the bone.
+     * @param swingLimit - The maximum rotation of the bone, relative to its parent, around each of the two axes
+     *                     orthogonal to its rest direction. These two rotations are limited independently, so
+     *                     a diagonal swing can exceed `swingLimit`. The swing isn’t limited if this is greater
+     *                     than or equal to π.
+     * @param twistMin - The lower limit of the rotation of the bone around its own axis.
+     * @param twistMax - The upper limit of the rotation of the bone around its own axis.
+     * @returns The index of the new bone, or `undefined` if `parent` isn’t a valid bone index.
+     */
+    public addSphericalBone(
+        parent: number,
+        start: Vector,
+        end: Vector,
+        radius: number,
+        swingLimit: number,
+        twistMin: number,
+        twistMax: number,
+    ): number | undefined {
+        if (parent >= this.bones.length) {
+            return undefined;
+        }
+
+        this.bones.push({
+            parent,
+            start,
+            end,
+            radius,
+            swingLimit,
+            twistLimits: [twistMin, twistMax],
+        });
+        return this.bones.length - 1;
+    }
+
+    /**
+     * Adds a bone attached to its parent by a revolute joint located at the start of the bone.
+     *
+     * @param parent - The index of the parent bone.
+     * @param start - The world-space position of the start of the bone.
+     * @param end - The world-space position of the end of the bone.
+     * @param radius - The radius of the capsule collider of the bone.
+     * @param axis - The world-space rotation axis of the joint.
+     * @param min - The lower limit of the angle of the bone relative to its rest pose.
+     * @param max - The upper limit of the angle of the bone relative to its rest pose.
+     * @returns The index of the new bone, or `undefined` if `parent` isn’t a valid bone index.
+     */
+    public addRevoluteBone(
+        parent: number,
+        start: Vector,
+        end: Vector,
+        radius: number,
+        axis: Vector,
+        min: number,
+        max: number,
+    ): number | undefined {
+        if (parent >= this.bones.length) {
+            return undefined;
+        }
+
+        this.bones.push({parent, start, end, radius, axis, limits: [min, max]});
+        return this.bones.length - 1;
+    }
+
+    // #endif
+
+    // #if DIM2
+    /**
+     * Adds a bone attached to its parent by a revolute joint located at the start of the bone.
+     *
+     * @param parent - The index of the parent bone.
+     * @param start - The world-space position of the start of the bone.
+     * @param end - The world-space position of the end of the bone.
+     * @param radius - The radius of the capsule collider of the bone.
+     * @param min - The lower limit of the angle of the bone relative to its rest pose.
+     * @param max - The upper limit of the angle of the bone relative to its rest pose.
+     * @returns The index of the new bone, or `undefined` if `parent` isn’t a valid bone index.
+     */
+    public addRevoluteBone(
+        parent: number,
+        start: Vector,
+        end: Vector,
+        radius: number,
+        min: number,
+        max: number,
+    ): number | undefined {
+        if (parent >= this.bones.length) {
+            return undefined;
+        }
+
+        this.bones.push({parent, start, end, radius, limits: [min, max]});
+        return this.bones.length - 1;
+    }
+
+    // #endif
+
+    /**
+     * Sets the density of the colliders of the bones.
+     */
+    public setDensity(density: number): RagdollDesc {
+        this.density = density;
+        return this;
+    }
+
+    /**
+     * Sets whether the bones are attached by multibody joints instead of impulse joints.
+     */
+    public setMultibody(multibody: boolean): RagdollDesc {
+        this.multibody = multibody;
+        return this;
+    }
+
+    /** @internal */
+    public intoRaw(): RawRagdollBuilder {
+        const raw = new RawRagdollBuilder();
+
+        this.bones.forEach((bone) => {
+            const rawStart = VectorOps.intoRaw(bone.start);
+            const rawEnd = VectorOps.intoRaw(bone.end);
+
+            if (bone.parent === undefined) {
+                raw.addRootBone(rawStart, rawEnd, bone.radius);
+            } else if (!!bone.limits) {
+                // #if DIM2
+                raw.addRevoluteBone(
+                    bone.parent,
+                    rawStart,
+                    rawEnd,
+                    bone.radius,
+                    bone.limits[0],
+                    bone.limits[1],
+                );
+                // #endif
+
+                // #if DIM3
+                const rawAxis = VectorOps.intoRaw(bone.axis);
+                raw.addRevoluteBone(
+                    bone.parent,
+                    rawStart,
+                    rawEnd,
+                    bone.radius,
+                    rawAxis,
+                    bone.limits[0],
+                    bone.limits[1],
+                );
+                rawAxis.free();
+                // #endif
+            }
+            // #if DIM3
+            else {
+                raw.addSphericalBone(
+                    bone.parent,
+                    rawStart,
+                    rawEnd,
+                    bone.radius,
+                    bone.swingLimit,
+                    bone.twistLimits[0],
+                    bone.twistLimits[1],
+                );
+            }
+            // #endif
+
+            rawStart.free();
+            rawEnd.free();
+        });
+
+        return raw;
+    }
+}
+
+/**
+ * The rigid-bodies, colliders, and joints created for each bone of a ragdoll.
+ *
+ * The arrays are indexed by the indices of the bones in their `RagdollDesc`.
+ */
+export class Ragdoll {
+    /**
+     * The handle of the rigid-body of each bone.
+     */
+    bodies: RigidBodyHandle[];
+    /**
+     * The handle of the capsule collider of each bone.
+     */
+    colliders: ColliderHandle[];
+    /**
+     * The handle of the joint attaching each bone to its parent, or `undefined` for the root bones.
+     */
+    joints: (ImpulseJointHandle | MultibodyJointHandle | undefined)[];
+    /**
+     * Are the joints of this ragdoll multibody joints?
+     */
+    multibody: boolean;
+
+    constructor(raw: RawRagdoll) {
+        this.bodies = [];
+        this.colliders = [];
+        this.joints = [];
+        this.multibody = raw.isMultibody();
+
+        for (let i = 0; i < raw.numBones(); ++i) {
+            this.bodies.push(raw.body(i));
+            this.colliders.push(raw.collider(i));
+            this.joints.push(raw.joint(i));
+        }
+    }
+
+    /**
+     * Creates the rigid-bodies, colliders, and joints of a ragdoll.
+     *
+     * The joints between adjacent bones disable the contacts between them, so the collision
+     * groups of the colliders are left to their default value.
+     *
+     * @param desc - The description of the ragdoll.
+     * @param bodies - The set the rigid-bodies are inserted into.
+     * @param colliders - The set the colliders are inserted into.
+     * @param impulseJoints - The set the impulse joints are inserted into.
+     * @param multibodyJoints - The set the multibody joints are inserted into.
+     */
+    public static create(
+        desc: RagdollDesc,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        impulseJoints: ImpulseJointSet,
+        multibodyJoints: MultibodyJointSet,
+    ): Ragdoll {
+        const rawBuilder = desc.intoRaw();
+        const rawRagdoll = rawBuilder.build(
+            bodies.raw,
+            colliders.raw,
+            impulseJoints.raw,
+            multibodyJoints.raw,
+            desc.density,
+            desc.multibody,
+        );
+        rawBuilder.free();
+
+        const ragdoll = new Ragdoll(rawRagdoll);
+        rawRagdoll.free();
+
+        ragdoll.bodies.forEach((handle) =>
+            bodies.registerInserted(colliders, handle),
+        );
+        ragdoll.colliders.forEach((handle) =>
+            colliders.registerInserted(bodies, handle),
+        );
+        ragdoll.joints.forEach((handle) => {
+            if (handle === undefined) {
+                return;
+            }
+
+            if (ragdoll.multibody) {
+                multibodyJoints.registerInserted(handle);
+            } else {
+                impulseJoints.registerInserted(bodies, handle);
+            }
+        });
+
+        return ragdoll;
+    }
+}
@@ -1,6 +1,10 @@
 import {RawRigidBodySet} from "../raw";
 import {Rotation, RotationOps, Vector, VectorOps} from "../math";
 import {Collider, ColliderHandle, ColliderSet} from "../geometry";
+import {MassProperties} from "./mass_properties";
+// #if DIM3
+import {SdpMatrix3, SdpMatrix3Ops} from "../math";
+// #endif
 
 /**
  * The integer identifier of a collider added to a `ColliderSet`.
@@ -485,6 +489,102 @@ export class RigidBody {
         return this.rawSet.rbMass(this.handle);
     }
 
+    /**
+     * The mass-properties of this rigid-body, including the contributions of its colliders,
+     * expressed in its local-space.
+     *
+     * The contributions of the colliders are updated at the next physics step, or manually with
+     * `this.recomputeMassPropertiesFromColliders`.
+     */
+    public massProperties(): MassProperties {
+        return MassProperties.fromRaw(this.rawSet.rbMassProperties(this.handle));
+    }
+
+    /**
+     * The world-space center-of-mass of this rigid-body.
+     */
+    public centerOfMass(): Vector {
+        return VectorOps.fromRaw(this.rawSet.rbCenterOfMass(this.handle));
+    }
+
+    // #if DIM2
+    /**
+     * The angular inertia of this rigid-body.
+     *
+     * This doesn’t take locked rotations into account.
+     */
+    public worldAngularInertia(): number {
+        return this.rawSet.rbWorldAngularInertia(this.handle);
+    }
+
+    // #endif
+
+    // #if DIM3
+    /**
+     * The world-space angular inertia tensor of this rigid-body, relative to its center-of-mass.
+     *
+     * This doesn’t take locked rotations into account.
+     */
+    public worldAngularInertia(): SdpMatrix3 {
+        return SdpMatrix3Ops.fromRaw(
+            this.rawSet.rbWorldAngularInertia(this.handle),
+        );
+    }
+
+    // #endif
+
+    /**
+     * The kinetic energy of this rigid-body.
+     */
+    public kineticEnergy(): number {
+        return this.rawSet.rbKineticEnergy(this.handle);
+    }
+
+    /**
+     * The world-space velocity of the given world-space point, assuming it is attached to
+     * this rigid-body.
+     *
+     * @param point - the world-space point.
+     */
+    public velocityAtPoint(point: Vector): Vector {
+        const rawPoint = VectorOps.intoRaw(point);
+        const result = VectorOps.fromRaw(
+            this.rawSet.rbVelocityAtPoint(this.handle, rawPoint),
+        );
+        rawPoint.free();
+        return result;
+    }
+
+    /**
+     * The sum of the forces added to this rigid-body since the last call to `this.resetForces`,
+     * including the forces added with `this.addForceAtPoint`.
+     */
+    public userForce(): Vector {
+        return VectorOps.fromRaw(this.rawSet.rbUserForce(this.handle));
+    }
+
+    // #if DIM2
+    /**
+     * The sum of the torques added to this rigid-body since the last call to `this.resetTorques`,
+     * including the torques resulting from `this.addForceAtPoint`.
+     */
+    public userTorque(): number {
+        return this.rawSet.rbUserTorque(this.handle);
+    }
+
+    // #endif
+
+    // #if DIM3
+    /**
+     * The sum of the world-space torques added to this rigid-body since the last call to
+     * `this.resetTorques`, including the torques resulting from `this.addForceAtPoint`.
+     */
+    public userTorque(): Vector {
+        return VectorOps.fromRaw(this.rawSet.rbUserTorque(this.handle));
+    }
+
+    // #endif
+
     /**
      * Put this rigid body to sleep.
      *
@@ -547,6 +647,28 @@ export class RigidBody {
         return this.rawSet.rbSetBodyType(this.handle, type);
     }
 
+    /**
+     * Is this rigid-body enabled?
+     */
+    public isEnabled(): boolean {
+        return this.rawSet.rbIsEnabled(this.handle);
+    }
+
+    /**
+     * Enables or disables this rigid-body, without removing it from the physics world.
+     *
+     * A disabled rigid-body and its colliders don’t interact with anything, and are ignored
+     * by scene queries. The impulse joints attached to it are ignored until it is enabled again.
+     * Its handle, colliders, joints, body type, and velocities are preserved and restored when
+     * it is enabled again. Multibody joints attached to a disabled rigid-body are still simulated:
+     * if it is a link of a multibody, it keeps following the motion of this multibody.
+     *
+     * @param enabled - Set to `false` to disable this rigid-body.
+     */
+    public setEnabled(enabled: boolean) {
+        this.rawSet.rbSetEnabled(this.handle, enabled, this.colliderSet.raw);
+    }
+
     /**
      * Is this rigid-body sleeping?
      */
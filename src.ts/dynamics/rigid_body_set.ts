@@ -1,4 +1,4 @@
-import {RawRigidBodySet} from "../raw";
+import {RawRigidBodySet, RawTransformsDump} from "../raw";
 import {Coarena} from "../coarena";
 import {VectorOps, RotationOps} from "../math";
 import {RigidBody, RigidBodyDesc, RigidBodyHandle} from "./rigid_body";
@@ -50,6 +50,18 @@ export class RigidBodySet {
         this.map.forEach((rb) => rb.finalizeDeserialization(colliderSet));
     }
 
+    /**
+     * Internal method, do not call this explicitly.
+     */
+    public registerInserted(
+        colliderSet: ColliderSet,
+        handle: RigidBodyHandle,
+    ): RigidBody {
+        const body = new RigidBody(this.raw, colliderSet, handle);
+        this.map.set(handle, body);
+        return body;
+    }
+
     /**
      * Creates a new rigid-body and return its integer handle.
      *
@@ -219,6 +231,41 @@ export class RigidBodySet {
         });
     }
 
+    /**
+     * Writes the handle and position of every rigid-body of this set into flat typed arrays,
+     * in a single call.
+     *
+     * @param out - (optional) A dump to fill, in order to reuse its memory across timesteps.
+     */
+    public fillTransforms(out?: TransformsDump): TransformsDump {
+        let dump = out || new TransformsDump();
+        this.raw.fillTransforms(dump.raw);
+        return dump;
+    }
+
+    /**
+     * Writes the handle and position of every active (non-sleeping) dynamic or kinematic rigid-body
+     * into flat typed arrays, in a single call.
+     *
+     * @param islands - The island manager tracking the active rigid-bodies.
+     * @param out - (optional) A dump to fill, in order to reuse its memory across timesteps.
+     */
+    public fillActiveTransforms(
+        islands: IslandManager,
+        out?: TransformsDump,
+    ): TransformsDump {
+        let dump = out || new TransformsDump();
+        this.raw.fillActiveTransforms(islands.raw, dump.raw);
+        return dump;
+    }
+
+    /**
+     * The number of floats of `TransformsDump.transforms()` describing each rigid-body.
+     */
+    public static transformStride(): number {
+        return RawRigidBodySet.transformStride();
+    }
+
     /**
      * Gets all rigid-bodies in the list.
      *
@@ -228,3 +275,55 @@ export class RigidBodySet {
         return this.map.getAll();
     }
 }
+
+/**
+ * Flat arrays containing the handle and position of rigid-bodies.
+ *
+ * The `i`-th rigid-body has the handle `handles()[i]`. Its translation and rotation (an angle in 2D,
+ * a quaternion `x, y, z, w` in 3D) are the `i`-th group of `RigidBodySet.transformStride()` floats
+ * of `transforms()`.
+ *
+ * To avoid leaking WASM resources, this MUST be freed manually with `transformsDump.free()`
+ * once you are done using it.
+ */
+export class TransformsDump {
+    raw: RawTransformsDump;
+
+    public free() {
+        if (!!this.raw) {
+            this.raw.free();
+        }
+        this.raw = undefined;
+    }
+
+    constructor(raw?: RawTransformsDump) {
+        this.raw = raw || new RawTransformsDump();
+    }
+
+    /**
+     * The number of rigid-bodies written into this dump.
+     */
+    public numBodies(): number {
+        return this.raw.numBodies();
+    }
+
+    /**
+     * The handle of each rigid-body.
+     *
+     * This is a view into the WASM memory, not a copy: it is invalidated by the next fill of this
+     * dump and by any other call to this library, so it must be read (or copied) right away.
+     */
+    public handles(): Float64Array {
+        return this.raw.handles();
+    }
+
+    /**
+     * The translation and rotation of each rigid-body.
+     *
+     * This is a view into the WASM memory, not a copy: it is invalidated by the next fill of this
+     * dump and by any other call to this library, so it must be read (or copied) right away.
+     */
+    public transforms(): Float32Array {
+        return this.raw.transforms();
+    }
+}
@@ -2,6 +2,7 @@ import {RawColliderSet} from "../raw";
 import {Rotation, RotationOps, Vector, VectorOps} from "../math";
 import {
     CoefficientCombineRule,
+    MassProperties,
     RigidBody,
     RigidBodyHandle,
     RigidBodySet,
@@ -15,6 +16,7 @@ import {
     ShapeType,
     Capsule,
     TriMesh,
+    Compound,
     Polyline,
     Heightfield,
     Segment,
@@ -185,6 +187,63 @@ export class Collider {
         this.colliderSet.raw.coSetSensor(this.handle, isSensor);
     }
 
+    /**
+     * Is this collider enabled?
+     */
+    public isEnabled(): boolean {
+        return this.colliderSet.raw.coIsEnabled(this.handle);
+    }
+
+    /**
+     * Enables or disables this collider, without removing it from the physics world.
+     *
+     * A disabled collider doesn’t generate any contact or intersection, and is ignored by
+     * scene queries. It still contributes to the mass of the rigid-body it is attached to.
+     *
+     * @param enabled - Set to `false` to disable this collider.
+     */
+    public setEnabled(enabled: boolean) {
+        this.colliderSet.raw.coSetEnabled(this.handle, enabled);
+    }
+
+    /**
+     * The user-defined 32-bit integer of this collider.
+     *
+     * These are the 32 least significant bits of its 128-bit user-data.
+     */
+    public userData(): number {
+        return this.colliderSet.raw.coUserData(this.handle);
+    }
+
+    /**
+     * Sets the user-defined 32-bit integer of this collider.
+     *
+     * This sets the 32 least significant bits of its 128-bit user-data, and clears the others.
+     *
+     * @param data - The integer to attach to this collider.
+     */
+    public setUserData(data: number) {
+        this.colliderSet.raw.coSetUserData(this.handle, data);
+    }
+
+    /**
+     * The 128-bit user-data of this collider, as four 32-bit words starting with the least
+     * significant one.
+     */
+    public userData128(): Uint32Array {
+        return this.colliderSet.raw.coUserData128(this.handle);
+    }
+
+    /**
+     * Sets the 128-bit user-data of this collider.
+     *
+     * @param data - Up to four 32-bit words, starting with the least significant one. Missing
+     *               words are set to zero.
+     */
+    public setUserData128(data: Uint32Array) {
+        this.colliderSet.raw.coSetUserData128(this.handle, data);
+    }
+
     /**
      * Sets the new shape of the collider.
      * @param shape - The collider’s new shape.
@@ -670,6 +729,15 @@ export class Collider {
         return this.colliderSet.raw.coMass(this.handle);
     }
 
+    /**
+     * The mass-properties of this collider, expressed in its local-space.
+     */
+    public massProperties(): MassProperties {
+        return MassProperties.fromRaw(
+            this.colliderSet.raw.coMassProperties(this.handle),
+        );
+    }
+
     /**
      * The volume of this collider.
      */
@@ -1150,6 +1218,23 @@ export class ColliderDesc {
         return new ColliderDesc(shape);
     }
 
+    /**
+     * Creates a new collider descriptor with a compound shape.
+     *
+     * @param shapes - The shapes of the parts. They can’t be compound shapes, polylines,
+     *   triangle meshes, or heightfields.
+     * @param translations - The translation of each part, relative to the collider.
+     * @param rotations - The rotation of each part, relative to the collider.
+     */
+    public static compound(
+        shapes: Shape[],
+        translations: Vector[],
+        rotations: Rotation[],
+    ): ColliderDesc {
+        const shape = new Compound(shapes, translations, rotations);
+        return new ColliderDesc(shape);
+    }
+
     // #if DIM2
     /**
      * Creates a new collider descriptor with a rectangular shape.
@@ -61,6 +61,17 @@ export class ColliderSet {
         );
     }
 
+    /** @internal */
+    public registerInserted(
+        bodies: RigidBodySet,
+        handle: ColliderHandle,
+    ): Collider {
+        const parent = bodies.get(this.raw.coParent(handle));
+        const collider = new Collider(this, handle, parent);
+        this.map.set(handle, collider);
+        return collider;
+    }
+
     /**
      * Creates a new collider and return its integer handle.
      *
@@ -1,5 +1,6 @@
-import {RawNarrowPhase, RawContactManifold} from "../raw";
+import {RawNarrowPhase, RawContactManifold, RawContactsDump} from "../raw";
 import {ColliderHandle} from "./collider";
+import {ColliderSet} from "./collider_set";
 import {Vector, VectorOps} from "../math";
 
 /**
@@ -97,6 +98,101 @@ export class NarrowPhase {
     ): boolean {
         return this.raw.intersection_pair(collider1, collider2);
     }
+
+    /**
+     * Writes all the contact manifolds with at least one contact point into flat typed arrays.
+     *
+     * @param colliders - The set of colliders involved in the contacts.
+     * @param out - (optional) A dump to fill, in order to reuse its memory across timesteps.
+     */
+    public dumpContacts(colliders: ColliderSet, out?: ContactsDump): ContactsDump {
+        let dump = out || new ContactsDump();
+        this.raw.dumpContacts(colliders.raw, dump.raw);
+        return dump;
+    }
+}
+
+/**
+ * Flat arrays describing all the contact manifolds of the narrow-phase.
+ *
+ * The `i`-th manifold involves the colliders `manifoldColliders()[2 * i]` and
+ * `manifoldColliders()[2 * i + 1]`. Its contact points are the points with indices
+ * in the range `manifoldOffsets()[i]..manifoldOffsets()[i + 1]`. Vectors (normals and points)
+ * are stored as consecutive components.
+ *
+ * To avoid leaking WASM resources, this MUST be freed manually with `contactsDump.free()`
+ * once you are done using it.
+ */
+export class ContactsDump {
+    raw: RawContactsDump;
+
+    public free() {
+        if (!!this.raw) {
+            this.raw.free();
+        }
+        this.raw = undefined;
+    }
+
+    constructor(raw?: RawContactsDump) {
+        this.raw = raw || new RawContactsDump();
+    }
+
+    public numManifolds(): number {
+        return this.raw.numManifolds();
+    }
+
+    public numContacts(): number {
+        return this.raw.numContacts();
+    }
+
+    /**
+     * The handles of the two colliders involved in each manifold.
+     */
+    public manifoldColliders(): Float64Array {
+        return this.raw.manifoldColliders();
+    }
+
+    /**
+     * The world-space normal of each manifold, pointing outward the first collider.
+     */
+    public manifoldNormals(): Float32Array {
+        return this.raw.manifoldNormals();
+    }
+
+    /**
+     * The range of contact points of each manifold (`numManifolds() + 1` elements).
+     */
+    public manifoldOffsets(): Uint32Array {
+        return this.raw.manifoldOffsets();
+    }
+
+    /**
+     * The world-space position of each contact point on the first collider.
+     */
+    public points1(): Float32Array {
+        return this.raw.points1();
+    }
+
+    /**
+     * The world-space position of each contact point on the second collider.
+     */
+    public points2(): Float32Array {
+        return this.raw.points2();
+    }
+
+    /**
+     * The signed distance between the colliders at each contact point (negative if they penetrate).
+     */
+    public dists(): Float32Array {
+        return this.raw.dists();
+    }
+
+    /**
+     * The normal impulse applied at each contact point during the last timestep.
+     */
+    public impulses(): Float32Array {
+        return this.raw.impulses();
+    }
 }
 
 export class TempContactManifold {
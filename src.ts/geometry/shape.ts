@@ -1,5 +1,5 @@
 import {Vector, VectorOps, Rotation, RotationOps} from "../math";
-import {RawColliderSet, RawShape} from "../raw";
+import {RawColliderSet, RawCompoundParts, RawShape} from "../raw";
 import {ShapeContact} from "./contact";
 import {PointProjection} from "./point";
 import {Ray, RayIntersection} from "./ray";
@@ -150,6 +150,11 @@ export abstract class Shape {
                 return new Heightfield(nrows, ncols, heights, scale);
             // #endif
 
+            case ShapeType.Compound:
+                throw new Error(
+                    "the parts of a compound shape can’t be read back from its collider",
+                );
+
             // #if DIM2
             case ShapeType.ConvexPolygon:
                 vs = rawSet.coVertices(handle);
@@ -504,7 +509,7 @@ export enum ShapeType {
     Triangle = 5,
     TriMesh = 6,
     HeightField = 7,
-    // Compound = 8,
+    Compound = 8,
     ConvexPolygon = 9,
     RoundCuboid = 10,
     RoundTriangle = 11,
@@ -527,7 +532,7 @@ export enum ShapeType {
     Triangle = 5,
     TriMesh = 6,
     HeightField = 7,
-    // Compound = 8,
+    Compound = 8,
     ConvexPolyhedron = 9,
     Cylinder = 10,
     Cone = 11,
@@ -953,6 +958,69 @@ export class TriMesh extends Shape {
     }
 }
 
+/**
+ * A shape made of several non-composite shapes, each with its own position relative to the
+ * compound shape.
+ *
+ * The parts of a compound shape are not stored by the physics world: the shape of a compound
+ * collider can only be read back if the collider was created from a `ColliderDesc`.
+ */
+export class Compound extends Shape {
+    readonly type = ShapeType.Compound;
+
+    /**
+     * The shapes of the parts.
+     */
+    shapes: Shape[];
+
+    /**
+     * The translations of the parts, relative to the compound shape.
+     */
+    translations: Vector[];
+
+    /**
+     * The rotations of the parts, relative to the compound shape.
+     */
+    rotations: Rotation[];
+
+    /**
+     * Creates a new compound shape.
+     *
+     * @param shapes - The shapes of the parts. They can’t be compound shapes, polylines,
+     *   triangle meshes, or heightfields.
+     * @param translations - The translation of each part, relative to the compound shape.
+     * @param rotations - The rotation of each part, relative to the compound shape.
+     */
+    constructor(
+        shapes: Shape[],
+        translations: Vector[],
+        rotations: Rotation[],
+    ) {
+        super();
+        this.shapes = shapes;
+        this.translations = translations;
+        this.rotations = rotations;
+    }
+
+    public intoRaw(): RawShape {
+        let rawParts = new RawCompoundParts();
+
+        this.shapes.forEach((shape, i) => {
+            let rawShape = shape.intoRaw();
+            let rawTra = VectorOps.intoRaw(this.translations[i]);
+            let rawRot = RotationOps.intoRaw(this.rotations[i]);
+            rawParts.push(rawShape, rawTra, rawRot);
+            rawShape.free();
+            rawTra.free();
+            rawRot.free();
+        });
+
+        let result = RawShape.compound(rawParts);
+        rawParts.free();
+        return result;
+    }
+}
+
 // #if DIM2
 /**
  * A shape that is a convex polygon.
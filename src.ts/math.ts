@@ -1,4 +1,7 @@
 import {RawVector, RawRotation} from "./raw";
+// #if DIM3
+import {RawSdpMatrix3} from "./raw";
+// #endif
 
 // #if DIM2
 export interface Vector {
@@ -172,4 +175,65 @@ export class RotationOps {
     }
 }
 
+/**
+ * A symmetric 3x3 matrix, e.g., an angular inertia tensor.
+ */
+export class SdpMatrix3 {
+    /**
+     * The six elements of the upper-triangular part of this matrix, in the order
+     * `m11, m12, m13, m22, m23, m33`.
+     */
+    elements: Float32Array;
+
+    constructor(elements: Float32Array) {
+        this.elements = elements;
+    }
+
+    get m11(): number {
+        return this.elements[0];
+    }
+
+    get m12(): number {
+        return this.elements[1];
+    }
+
+    get m21(): number {
+        return this.m12;
+    }
+
+    get m13(): number {
+        return this.elements[2];
+    }
+
+    get m31(): number {
+        return this.m13;
+    }
+
+    get m22(): number {
+        return this.elements[3];
+    }
+
+    get m23(): number {
+        return this.elements[4];
+    }
+
+    get m32(): number {
+        return this.m23;
+    }
+
+    get m33(): number {
+        return this.elements[5];
+    }
+}
+
+export class SdpMatrix3Ops {
+    public static fromRaw(raw: RawSdpMatrix3): SdpMatrix3 {
+        if (!raw) return null;
+
+        const res = new SdpMatrix3(raw.elements());
+        raw.free();
+        return res;
+    }
+}
+
 // #endif
@@ -0,0 +1,51 @@
+import {RawWorldChecksum} from "../raw";
+import {RigidBodyHandle} from "../dynamics";
+
+/**
+ * The checksum of a set of dynamic rigid-bodies interacting with each other through
+ * active contacts or joints.
+ */
+export interface IslandChecksum {
+    /**
+     * The checksum of the island, as a 16-characters hexadecimal string.
+     */
+    hash: string;
+    /**
+     * The handles of the rigid-bodies of this island, in increasing handle order.
+     */
+    bodies: RigidBodyHandle[];
+}
+
+/**
+ * A checksum of the state of a physics world.
+ *
+ * It only depends on the position, velocities, and sleep state of the rigid-bodies, on the
+ * position of the colliders, on the accumulated impulses of the impulse joints, and on the
+ * joint coordinates and velocities of the multibodies. Two physics
+ * worlds simulated deterministically from the same initial state have the same checksum,
+ * so this can be used to detect desynchronizations in lockstep multiplayer games.
+ */
+export class WorldChecksum {
+    /**
+     * The checksum of the whole physics world, as a 16-characters hexadecimal string.
+     */
+    hash: string;
+    /**
+     * The checksums of every island, if they were requested.
+     *
+     * Comparing them helps localizing the part of the world where two simulations diverged.
+     */
+    islands: IslandChecksum[];
+
+    constructor(raw: RawWorldChecksum) {
+        this.hash = raw.hash();
+        this.islands = [];
+
+        for (let i = 0; i < raw.numIslands(); ++i) {
+            this.islands.push({
+                hash: raw.islandHash(i),
+                bodies: Array.from(raw.islandBodies(i)),
+            });
+        }
+    }
+}
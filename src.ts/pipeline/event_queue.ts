@@ -1,5 +1,10 @@
-import {RawContactForceEvent, RawEventQueue} from "../raw";
-import {RigidBodyHandle} from "../dynamics";
+import {
+    RawCollisionEvent,
+    RawContactForceEvent,
+    RawEventQueue,
+    RawJointBrokenEvent,
+} from "../raw";
+import {ImpulseJointHandle, RigidBodyHandle} from "../dynamics";
 import {Collider, ColliderHandle} from "../geometry";
 import {Vector, VectorOps} from "../math";
 
@@ -17,6 +22,89 @@ export enum ActiveEvents {
     CONTACT_FORCE_EVENTS = 0b0010,
 }
 
+/**
+ * Event occurring when two colliders start or stop being in contact (or intersecting, if
+ * one of them is a sensor).
+ *
+ * This object should **not** be stored anywhere. Its properties can only be
+ * read from within the closure given to `EventHandler.drainCollisionEvents`.
+ */
+export class TempCollisionEvent {
+    raw: RawCollisionEvent;
+
+    public free() {
+        if (!!this.raw) {
+            this.raw.free();
+        }
+        this.raw = undefined;
+    }
+
+    /**
+     * The first collider involved in the collision.
+     */
+    public collider1(): ColliderHandle {
+        return this.raw.collider1();
+    }
+
+    /**
+     * The second collider involved in the collision.
+     */
+    public collider2(): ColliderHandle {
+        return this.raw.collider2();
+    }
+
+    /**
+     * Is this a collision start event (`true`) or stop event (`false`)?
+     */
+    public started(): boolean {
+        return this.raw.started();
+    }
+
+    /**
+     * Is at least one of the colliders involved in this collision a sensor?
+     */
+    public sensor(): boolean {
+        return this.raw.sensor();
+    }
+
+    /**
+     * Was this collision stopped because one of the colliders was removed?
+     */
+    public removed(): boolean {
+        return this.raw.removed();
+    }
+
+    /**
+     * The world-space position, on the first collider, of the deepest contact point when
+     * the collision started.
+     *
+     * Returns `null` for stop events and for collisions involving a sensor.
+     */
+    public contactPoint(): Vector | null {
+        return VectorOps.fromRaw(this.raw.contactPoint());
+    }
+
+    /**
+     * The world-space contact normal, pointing outward the first collider, when the collision
+     * started.
+     *
+     * Returns `null` for stop events and for collisions involving a sensor.
+     */
+    public contactNormal(): Vector | null {
+        return VectorOps.fromRaw(this.raw.contactNormal());
+    }
+
+    /**
+     * The velocity of the second collider relative to the first collider at the contact point
+     * when the collision started.
+     *
+     * Returns `null` for stop events and for collisions involving a sensor.
+     */
+    public relativeVelocity(): Vector | null {
+        return VectorOps.fromRaw(this.raw.relativeVelocity());
+    }
+}
+
 /**
  * Event occurring when the sum of the magnitudes of the
  * contact forces between two colliders exceed a threshold.
@@ -81,6 +169,59 @@ export class TempContactForceEvent {
     }
 }
 
+/**
+ * Event occurring when a breakable impulse joint applies a force or a torque greater than
+ * its thresholds, and is removed.
+ *
+ * This object should **not** be stored anywhere. Its properties can only be
+ * read from within the closure given to `EventHandler.drainJointBrokenEvents`.
+ */
+export class TempJointBrokenEvent {
+    raw: RawJointBrokenEvent;
+
+    public free() {
+        if (!!this.raw) {
+            this.raw.free();
+        }
+        this.raw = undefined;
+    }
+
+    /**
+     * The handle of the impulse joint that broke. It has already been removed from its set.
+     */
+    public joint(): ImpulseJointHandle {
+        return this.raw.joint();
+    }
+
+    /**
+     * The first rigid-body the joint was attached to.
+     */
+    public body1(): RigidBodyHandle {
+        return this.raw.body1();
+    }
+
+    /**
+     * The second rigid-body the joint was attached to.
+     */
+    public body2(): RigidBodyHandle {
+        return this.raw.body2();
+    }
+
+    /**
+     * The magnitude of the force applied by the joint during the timestep it broke.
+     */
+    public force(): number {
+        return this.raw.force();
+    }
+
+    /**
+     * The magnitude of the torque applied by the joint during the timestep it broke.
+     */
+    public torque(): number {
+        return this.raw.torque();
+    }
+}
+
 /**
  * A structure responsible for collecting events generated
  * by the physics engine.
@@ -103,6 +244,24 @@ export class EventQueue {
         this.raw = raw || new RawEventQueue(autoDrain);
     }
 
+    /**
+     * Creates a new event collector that can hold at most `capacity` collision events,
+     * `capacity` contact force events, and `capacity` joint broken events.
+     *
+     * Events generated while the collector is full are dropped. Their number can be
+     * read with `numDroppedEvents`.
+     *
+     * @param autoDrain - if `true`, the collector will be automatically drained before each `world.step(collector)`.
+     * @param capacity - the maximum number of events of each kind kept in memory. Throws an error if
+     *                   it is `0`.
+     */
+    public static withCapacity(autoDrain: boolean, capacity: number): EventQueue {
+        return new EventQueue(
+            autoDrain,
+            RawEventQueue.withCapacity(autoDrain, capacity),
+        );
+    }
+
     /**
      * Release the WASM memory occupied by this event-queue.
      */
@@ -120,16 +279,23 @@ export class EventQueue {
      * @param f - JavaScript closure applied to each collision event. The
      * closure must take three arguments: two integers representing the handles of the colliders
      * involved in the collision, and a boolean indicating if the collision started (true) or stopped
-     * (false).
+     * (false). The optional fourth argument gives access to the event flags and to the contact
+     * point at the time the collision started.
      */
     public drainCollisionEvents(
         f: (
             handle1: ColliderHandle,
             handle2: ColliderHandle,
             started: boolean,
+            event?: TempCollisionEvent,
         ) => void,
     ) {
-        this.raw.drainCollisionEvents(f);
+        let event = new TempCollisionEvent();
+        this.raw.drainCollisionEvents((raw: RawCollisionEvent) => {
+            event.raw = raw;
+            f(event.collider1(), event.collider2(), event.started(), event);
+            event.free();
+        });
     }
 
     /**
@@ -148,6 +314,29 @@ export class EventQueue {
         });
     }
 
+    /**
+     * Applies the given javascript closure on each joint broken event of this collector, then clear
+     * the internal joint broken event buffer.
+     *
+     * @param f - JavaScript closure applied to each joint broken event. The
+     *            closure must take one `TempJointBrokenEvent` argument.
+     */
+    public drainJointBrokenEvents(f: (event: TempJointBrokenEvent) => void) {
+        let event = new TempJointBrokenEvent();
+        this.raw.drainJointBrokenEvents((raw: RawJointBrokenEvent) => {
+            event.raw = raw;
+            f(event);
+            event.free();
+        });
+    }
+
+    /**
+     * The total number of events that were dropped because this collector was full.
+     */
+    public numDroppedEvents(): number {
+        return this.raw.numDroppedEvents();
+    }
+
     /**
      * Removes all events contained by this collector
      */
@@ -1,7 +1,11 @@
 export * from "./world";
+export * from "./checksum";
 export * from "./physics_pipeline";
 export * from "./serialization_pipeline";
 export * from "./event_queue";
 export * from "./physics_hooks";
 export * from "./debug_render_pipeline";
 export * from "./query_pipeline";
+// #if DIM3
+export * from "./urdf_loader";
+// #endif
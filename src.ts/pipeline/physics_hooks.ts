@@ -1,10 +1,12 @@
+import {RawContactModificationContext} from "../raw";
 import {RigidBodyHandle} from "../dynamics";
 import {ColliderHandle} from "../geometry";
+import {Vector, VectorOps} from "../math";
 
 export enum ActiveHooks {
     FILTER_CONTACT_PAIRS = 0b0001,
     FILTER_INTERSECTION_PAIRS = 0b0010,
-    // MODIFY_SOLVER_CONTACTS = 0b0100, /* Not supported yet in JS. */
+    MODIFY_SOLVER_CONTACTS = 0b0100,
 }
 
 export enum SolverFlags {
@@ -50,4 +52,158 @@ export interface PhysicsHooks {
         body1: RigidBodyHandle,
         body2: RigidBodyHandle,
     ): boolean;
+
+    /**
+     * Function that modifies the set of contacts (and their properties) the constraints solver
+     * will take into account for a contact pair.
+     *
+     * This will only be executed and taken into account if at least one of the involved colliders contains the
+     * `ActiveHooks.MODIFY_SOLVER_CONTACTS` flag in its active hooks.
+     *
+     * The given context is only valid during the execution of this function.
+     *
+     * @param context − The contact pair to modify.
+     */
+    modifySolverContacts?(context: TempContactModificationContext): void;
+}
+
+/**
+ * The contact pair given to `PhysicsHooks.modifySolverContacts`.
+ *
+ * Modifications applied to this context are taken into account by the constraints solver
+ * once the hook returns.
+ */
+export class TempContactModificationContext {
+    raw: RawContactModificationContext;
+
+    public free() {
+        if (!!this.raw) {
+            this.raw.free();
+        }
+        this.raw = undefined;
+    }
+
+    constructor(raw: RawContactModificationContext) {
+        this.raw = raw;
+    }
+
+    /**
+     * Handle of the first collider involved in the contact.
+     */
+    public collider1(): ColliderHandle {
+        return this.raw.collider1();
+    }
+
+    /**
+     * Handle of the second collider involved in the contact.
+     */
+    public collider2(): ColliderHandle {
+        return this.raw.collider2();
+    }
+
+    /**
+     * Handle of the rigid-body the first collider is attached to, if any.
+     */
+    public body1(): RigidBodyHandle | null {
+        return this.raw.rigid_body1();
+    }
+
+    /**
+     * Handle of the rigid-body the second collider is attached to, if any.
+     */
+    public body2(): RigidBodyHandle | null {
+        return this.raw.rigid_body2();
+    }
+
+    /**
+     * The world-space contact normal shared by all the solver contacts.
+     */
+    public normal(): Vector {
+        return VectorOps.fromRaw(this.raw.normal);
+    }
+
+    /**
+     * Sets the world-space contact normal shared by all the solver contacts.
+     */
+    public setNormal(normal: Vector) {
+        const rawNormal = VectorOps.intoRaw(normal);
+        this.raw.normal = rawNormal;
+        rawNormal.free();
+    }
+
+    /**
+     * User-defined data attached to this contact pair, persistent across timesteps.
+     */
+    public userData(): number {
+        return this.raw.user_data;
+    }
+
+    /**
+     * Sets the user-defined data attached to this contact pair.
+     */
+    public setUserData(data: number) {
+        this.raw.user_data = data;
+    }
+
+    public numSolverContacts(): number {
+        return this.raw.num_solver_contacts();
+    }
+
+    /**
+     * Removes all the solver contacts, disabling the contact response for this pair.
+     */
+    public clearSolverContacts() {
+        this.raw.clear_solver_contacts();
+    }
+
+    /**
+     * Removes the `i`-th solver contact. The last solver contact takes its place.
+     */
+    public removeSolverContact(i: number) {
+        this.raw.remove_solver_contact(i);
+    }
+
+    public solverContactPoint(i: number): Vector | null {
+        return VectorOps.fromRaw(this.raw.solver_contact_point(i));
+    }
+
+    public setSolverContactPoint(i: number, point: Vector) {
+        const rawPoint = VectorOps.intoRaw(point);
+        this.raw.set_solver_contact_point(i, rawPoint);
+        rawPoint.free();
+    }
+
+    public solverContactDist(i: number): number {
+        return this.raw.solver_contact_dist(i);
+    }
+
+    public setSolverContactDist(i: number, dist: number) {
+        this.raw.set_solver_contact_dist(i, dist);
+    }
+
+    public solverContactFriction(i: number): number {
+        return this.raw.solver_contact_friction(i);
+    }
+
+    public setSolverContactFriction(i: number, friction: number) {
+        this.raw.set_solver_contact_friction(i, friction);
+    }
+
+    public solverContactRestitution(i: number): number {
+        return this.raw.solver_contact_restitution(i);
+    }
+
+    public setSolverContactRestitution(i: number, restitution: number) {
+        this.raw.set_solver_contact_restitution(i, restitution);
+    }
+
+    public solverContactTangentVelocity(i: number): Vector | null {
+        return VectorOps.fromRaw(this.raw.solver_contact_tangent_velocity(i));
+    }
+
+    public setSolverContactTangentVelocity(i: number, vel: Vector) {
+        const rawVel = VectorOps.intoRaw(vel);
+        this.raw.set_solver_contact_tangent_velocity(i, rawVel);
+        rawVel.free();
+    }
 }
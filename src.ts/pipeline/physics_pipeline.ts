@@ -2,6 +2,7 @@ import {RawPhysicsPipeline} from "../raw";
 import {Vector, VectorOps} from "../math";
 import {
     IntegrationParameters,
+    ImpulseJointHandle,
     ImpulseJointSet,
     MultibodyJointSet,
     RigidBodyHandle,
@@ -16,10 +17,12 @@ import {
     NarrowPhase,
 } from "../geometry";
 import {EventQueue} from "./event_queue";
-import {PhysicsHooks} from "./physics_hooks";
+import {PhysicsHooks, TempContactModificationContext} from "./physics_hooks";
+import {RawContactModificationContext} from "../raw";
 
 export class PhysicsPipeline {
     raw: RawPhysicsPipeline;
+    tempContext: TempContactModificationContext;
 
     public free() {
         if (!!this.raw) {
@@ -30,6 +33,7 @@ export class PhysicsPipeline {
 
     constructor(raw?: RawPhysicsPipeline) {
         this.raw = raw || new RawPhysicsPipeline();
+        this.tempContext = new TempContactModificationContext(null);
     }
 
     public step(
@@ -64,6 +68,13 @@ export class PhysicsPipeline {
                 hooks,
                 !!hooks ? hooks.filterContactPair : null,
                 !!hooks ? hooks.filterIntersectionPair : null,
+                !!hooks && !!hooks.modifySolverContacts
+                    ? (raw: RawContactModificationContext) => {
+                          this.tempContext.raw = raw;
+                          hooks.modifySolverContacts(this.tempContext);
+                          this.tempContext.free();
+                      }
+                    : null,
             );
         } else {
             this.raw.step(
@@ -80,6 +91,13 @@ export class PhysicsPipeline {
             );
         }
 
+        // Breakable joints may have been removed during this step.
+        impulseJoints.raw.forEachBrokenJointHandle(
+            (handle: ImpulseJointHandle) => {
+                impulseJoints.unmap(handle);
+            },
+        );
+
         rawG.free();
     }
 }
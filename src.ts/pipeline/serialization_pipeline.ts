@@ -1,15 +1,74 @@
-import {RawSerializationPipeline} from "../raw";
-import {Vector, VectorOps} from "../math";
+import {
+    RawBodyStates,
+    RawPrefabRemapping,
+    RawSerializationPipeline,
+} from "../raw";
+import {Rotation, RotationOps, Vector, VectorOps} from "../math";
 import {
     IntegrationParameters,
     IslandManager,
+    ImpulseJointHandle,
     ImpulseJointSet,
+    MultibodyJointHandle,
     MultibodyJointSet,
+    RigidBodyHandle,
     RigidBodySet,
 } from "../dynamics";
-import {BroadPhase, ColliderSet, NarrowPhase} from "../geometry";
+import {BroadPhase, ColliderHandle, ColliderSet, NarrowPhase} from "../geometry";
 import {World} from "./world";
 
+function pairsToMap(pairs: Float64Array): Map<number, number> {
+    let result = new Map<number, number>();
+    for (let i = 0; i < pairs.length; i += 2) {
+        result.set(pairs[i], pairs[i + 1]);
+    }
+    return result;
+}
+
+/**
+ * Rigid-body states decoded by `SerializationPipeline.decodeBodyStates`.
+ *
+ * They can be used as the base of several calls to `serializeBodyStatesDelta` without
+ * being decoded again each time.
+ *
+ * To avoid leaking WASM resources, this MUST be freed manually with `bodyStates.free()`
+ * once you are done using it.
+ */
+export class BodyStates {
+    raw: RawBodyStates;
+
+    public free() {
+        if (!!this.raw) {
+            this.raw.free();
+        }
+        this.raw = undefined;
+    }
+
+    constructor(raw: RawBodyStates) {
+        this.raw = raw;
+    }
+}
+
+/**
+ * The handles given to the elements of a prefab inserted into a physics world.
+ *
+ * Each map associates the handle an element had in the world the prefab was created from to
+ * the handle of its copy in the world the prefab was inserted into.
+ */
+export class PrefabRemapping {
+    bodies: Map<RigidBodyHandle, RigidBodyHandle>;
+    colliders: Map<ColliderHandle, ColliderHandle>;
+    impulseJoints: Map<ImpulseJointHandle, ImpulseJointHandle>;
+    multibodyJoints: Map<MultibodyJointHandle, MultibodyJointHandle>;
+
+    constructor(raw: RawPrefabRemapping) {
+        this.bodies = pairsToMap(raw.bodies());
+        this.colliders = pairsToMap(raw.colliders());
+        this.impulseJoints = pairsToMap(raw.impulseJoints());
+        this.multibodyJoints = pairsToMap(raw.multibodyJoints());
+    }
+}
+
 /**
  * A pipeline for serializing the physics scene.
  *
@@ -76,9 +135,223 @@ export class SerializationPipeline {
     /**
      * Deserialize the complete physics state from a single byte array.
      *
+     * Throws an error if the snapshot is corrupted, or was generated by an incompatible version
+     * or dimension (2D vs. 3D) of this library.
+     *
      * @param data - The byte array to deserialize.
      */
     public deserializeAll(data: Uint8Array): World {
         return World.fromRaw(this.raw.deserializeAll(data));
     }
+
+    /**
+     * Serialize a complete physics state into a human-readable JSON string.
+     *
+     * The broad-phase and narrow-phase are stored as opaque arrays of bytes because
+     * they contain maps that can’t be represented in JSON.
+     *
+     * @param gravity - The current gravity affecting the simulation.
+     * @param integrationParameters - The integration parameters of the simulation.
+     * @param broadPhase - The broad-phase of the simulation.
+     * @param narrowPhase - The narrow-phase of the simulation.
+     * @param bodies - The rigid-bodies taking part into the simulation.
+     * @param colliders - The colliders taking part into the simulation.
+     * @param impulseJoints - The impulse joints taking part into the simulation.
+     * @param multibodyJoints - The multibody joints taking part into the simulation.
+     */
+    public serializeAllJson(
+        gravity: Vector,
+        integrationParameters: IntegrationParameters,
+        islands: IslandManager,
+        broadPhase: BroadPhase,
+        narrowPhase: NarrowPhase,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        impulseJoints: ImpulseJointSet,
+        multibodyJoints: MultibodyJointSet,
+    ): string {
+        let rawGra = VectorOps.intoRaw(gravity);
+
+        try {
+            return this.raw.serializeAllJson(
+                rawGra,
+                integrationParameters.raw,
+                islands.raw,
+                broadPhase.raw,
+                narrowPhase.raw,
+                bodies.raw,
+                colliders.raw,
+                impulseJoints.raw,
+                multibodyJoints.raw,
+            );
+        } finally {
+            rawGra.free();
+        }
+    }
+
+    /**
+     * Deserialize the complete physics state from a JSON string generated by `serializeAllJson`.
+     *
+     * Throws an error if the snapshot is invalid, or was generated by an incompatible version
+     * or dimension (2D vs. 3D) of this library.
+     *
+     * @param data - The JSON string to deserialize.
+     */
+    public deserializeAllJson(data: string): World {
+        return World.fromRaw(this.raw.deserializeAllJson(data));
+    }
+
+    /**
+     * Serializes the position, velocities, and sleep state of every rigid-body.
+     *
+     * The result can be used as the base of `serializeBodyStatesDelta`.
+     *
+     * @param bodies - The rigid-bodies to serialize.
+     */
+    public serializeBodyStates(bodies: RigidBodySet): Uint8Array {
+        return this.raw.serializeBodyStates(bodies.raw);
+    }
+
+    /**
+     * Decodes rigid-body states generated by `serializeBodyStates`.
+     *
+     * Passing the result to `serializeBodyStatesDelta` avoids decoding the same base each
+     * time a delta is created. Throws an error if `data` is corrupted, or was generated by an
+     * incompatible version of this library.
+     *
+     * @param data - The body states to decode.
+     */
+    public decodeBodyStates(data: Uint8Array): BodyStates {
+        return new BodyStates(this.raw.decodeBodyStates(data));
+    }
+
+    /**
+     * Serializes the position, velocities, and sleep state of every rigid-body which
+     * state differs from its state in `base`.
+     *
+     * Applying the base, then this delta, with `applyBodyStates` restores the rigid-body
+     * states at the time this delta was created (assuming no rigid-body was added or removed
+     * in-between).
+     *
+     * @param base - Body states previously generated by `serializeBodyStates`, either serialized
+     *               or decoded by `decodeBodyStates`.
+     * @param bodies - The rigid-bodies to serialize.
+     */
+    public serializeBodyStatesDelta(
+        base: Uint8Array | BodyStates,
+        bodies: RigidBodySet,
+    ): Uint8Array {
+        if (base instanceof BodyStates) {
+            return this.raw.serializeBodyStatesDelta(base.raw, bodies.raw);
+        }
+
+        const decoded = this.decodeBodyStates(base);
+        try {
+            return this.raw.serializeBodyStatesDelta(decoded.raw, bodies.raw);
+        } finally {
+            decoded.free();
+        }
+    }
+
+    /**
+     * Restores the rigid-body states generated by `serializeBodyStates` or `serializeBodyStatesDelta`.
+     *
+     * Throws an error if `data` is corrupted, or was generated by an incompatible version of this library.
+     *
+     * @param data - The body states to apply.
+     * @param bodies - The rigid-bodies to modify.
+     */
+    public applyBodyStates(data: Uint8Array, bodies: RigidBodySet) {
+        this.raw.applyBodyStates(data, bodies.raw);
+    }
+
+    /**
+     * Serializes a set of rigid-bodies, together with their attached colliders, and the
+     * impulse and multibody joints linking them together.
+     *
+     * Joints attached to a rigid-body that is not part of `handles` are not serialized.
+     * The result can be inserted into any physics world with `insertPrefab`.
+     *
+     * @param handles - The handles of the rigid-bodies to serialize.
+     * @param bodies - The set containing the rigid-bodies to serialize.
+     * @param colliders - The set containing the colliders attached to the rigid-bodies.
+     * @param impulseJoints - The set containing the impulse joints between the rigid-bodies.
+     * @param multibodyJoints - The set containing the multibody joints between the rigid-bodies.
+     */
+    public serializePrefab(
+        handles: RigidBodyHandle[],
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        impulseJoints: ImpulseJointSet,
+        multibodyJoints: MultibodyJointSet,
+    ): Uint8Array {
+        return this.raw.serializePrefab(
+            new Float64Array(handles),
+            bodies.raw,
+            colliders.raw,
+            impulseJoints.raw,
+            multibodyJoints.raw,
+        );
+    }
+
+    /**
+     * Inserts a prefab generated by `serializePrefab` into the given sets.
+     *
+     * Throws an error if `data` is corrupted, or was generated by an incompatible version of this library.
+     *
+     * @param data - The prefab to insert.
+     * @param translation - The translation applied to the prefab’s rigid-bodies.
+     * @param rotation - The rotation applied to the prefab’s rigid-bodies.
+     * @param bodies - The set the prefab’s rigid-bodies are inserted into.
+     * @param colliders - The set the prefab’s colliders are inserted into.
+     * @param impulseJoints - The set the prefab’s impulse joints are inserted into.
+     * @param multibodyJoints - The set the prefab’s multibody joints are inserted into.
+     * @returns The handles of the inserted elements.
+     */
+    public insertPrefab(
+        data: Uint8Array,
+        translation: Vector,
+        rotation: Rotation,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        impulseJoints: ImpulseJointSet,
+        multibodyJoints: MultibodyJointSet,
+    ): PrefabRemapping {
+        let rawTra = VectorOps.intoRaw(translation);
+        let rawRot = RotationOps.intoRaw(rotation);
+
+        let rawRemapping: RawPrefabRemapping;
+        try {
+            rawRemapping = this.raw.insertPrefab(
+                data,
+                rawTra,
+                rawRot,
+                bodies.raw,
+                colliders.raw,
+                impulseJoints.raw,
+                multibodyJoints.raw,
+            );
+        } finally {
+            rawTra.free();
+            rawRot.free();
+        }
+
+        const remapping = new PrefabRemapping(rawRemapping);
+        rawRemapping.free();
+
+        remapping.bodies.forEach((handle) =>
+            bodies.registerInserted(colliders, handle),
+        );
+        remapping.colliders.forEach((handle) =>
+            colliders.registerInserted(bodies, handle),
+        );
+        remapping.impulseJoints.forEach((handle) =>
+            impulseJoints.registerInserted(bodies, handle),
+        );
+        remapping.multibodyJoints.forEach((handle) =>
+            multibodyJoints.registerInserted(handle),
+        );
+
+        return remapping;
+    }
 }
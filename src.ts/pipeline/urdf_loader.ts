@@ -0,0 +1,164 @@
+import {RawUrdfRobot} from "../raw";
+import {Rotation, RotationOps, Vector, VectorOps} from "../math";
+import {
+    ImpulseJointHandle,
+    ImpulseJointSet,
+    MultibodyJointHandle,
+    MultibodyJointSet,
+    RigidBodyHandle,
+    RigidBodySet,
+} from "../dynamics";
+import {ColliderHandle, ColliderSet} from "../geometry";
+
+/**
+ * The options of the URDF loader.
+ */
+export interface UrdfLoaderOptions {
+    /**
+     * The position of the robot’s root links. Defaults to the origin.
+     */
+    translation?: Vector;
+    /**
+     * The orientation of the robot’s root links. Defaults to the identity.
+     */
+    rotation?: Rotation;
+    /**
+     * If `true`, the links without parent joint are converted into fixed rigid-bodies.
+     * Defaults to `false`.
+     */
+    fixedRoot?: boolean;
+    /**
+     * If `true`, the joints are converted into multibody joints instead of impulse joints.
+     * Defaults to `false`.
+     */
+    multibody?: boolean;
+    /**
+     * If `true`, contacts are enabled between the links attached by a joint.
+     * Defaults to `false`.
+     */
+    enableJointCollisions?: boolean;
+}
+
+/**
+ * The rigid-bodies, colliders, and joints created from a URDF file, indexed by the
+ * names of the URDF links and joints.
+ */
+export class UrdfRobot {
+    /**
+     * The name of the robot.
+     */
+    name: string;
+    /**
+     * The handle of the rigid-body created for each link.
+     */
+    links: Map<string, RigidBodyHandle>;
+    /**
+     * The handles of the colliders created for each link.
+     */
+    colliders: Map<string, ColliderHandle[]>;
+    /**
+     * The handle of the joint created for each URDF joint. Floating joints are ignored.
+     */
+    joints: Map<string, ImpulseJointHandle | MultibodyJointHandle>;
+    /**
+     * Are the joints of this robot multibody joints?
+     */
+    multibody: boolean;
+
+    constructor(raw: RawUrdfRobot) {
+        this.name = raw.name();
+        this.multibody = raw.isMultibody();
+        this.links = new Map();
+        this.colliders = new Map();
+        this.joints = new Map();
+
+        for (let i = 0; i < raw.numLinks(); ++i) {
+            const name = raw.linkName(i);
+            this.links.set(name, raw.linkBody(i));
+            this.colliders.set(name, Array.from(raw.linkColliders(i)));
+        }
+
+        for (let i = 0; i < raw.numJoints(); ++i) {
+            this.joints.set(raw.jointName(i), raw.joint(i));
+        }
+    }
+}
+
+/**
+ * A loader converting robots described in the URDF format into rigid-bodies, colliders,
+ * and joints.
+ */
+export class UrdfLoader {
+    /**
+     * Parses a URDF file, and inserts the robot it describes into the given sets.
+     *
+     * Each link is converted into a dynamic rigid-body with one collider per supported
+     * collision geometry (box, sphere, cylinder, and capsule). Mesh geometries are ignored.
+     * If a link has an `<inertial>` element, its mass properties are used instead of the
+     * ones computed from its colliders. Floating joints are ignored, so their child links
+     * are free rigid-bodies.
+     *
+     * Throws an error and inserts nothing if the URDF file is invalid.
+     *
+     * @param urdf - The content of the URDF file.
+     * @param bodies - The set the rigid-bodies are inserted into.
+     * @param colliders - The set the colliders are inserted into.
+     * @param impulseJoints - The set the impulse joints are inserted into.
+     * @param multibodyJoints - The set the multibody joints are inserted into.
+     * @param options - The options of the loader.
+     */
+    public static load(
+        urdf: string,
+        bodies: RigidBodySet,
+        colliders: ColliderSet,
+        impulseJoints: ImpulseJointSet,
+        multibodyJoints: MultibodyJointSet,
+        options?: UrdfLoaderOptions,
+    ): UrdfRobot {
+        const opts = options || {};
+        const rawTra = VectorOps.intoRaw(opts.translation || VectorOps.zeros());
+        const rawRot = RotationOps.intoRaw(
+            opts.rotation || RotationOps.identity(),
+        );
+
+        let rawRobot: RawUrdfRobot;
+        try {
+            rawRobot = RawUrdfRobot.load(
+                urdf,
+                bodies.raw,
+                colliders.raw,
+                impulseJoints.raw,
+                multibodyJoints.raw,
+                rawTra,
+                rawRot,
+                !!opts.fixedRoot,
+                !!opts.multibody,
+                !!opts.enableJointCollisions,
+            );
+        } finally {
+            rawTra.free();
+            rawRot.free();
+        }
+
+        const robot = new UrdfRobot(rawRobot);
+        rawRobot.free();
+
+        robot.links.forEach((handle) =>
+            bodies.registerInserted(colliders, handle),
+        );
+        robot.colliders.forEach((handles) =>
+            handles.forEach((handle) =>
+                colliders.registerInserted(bodies, handle),
+            ),
+        );
+        robot.joints.forEach((handle) => {
+            if (robot.multibody) {
+                multibodyJoints.registerInserted(handle);
+            } else {
+                impulseJoints.registerInserted(bodies, handle);
+            }
+        });
+
+        return robot;
+    }
+}
@@ -13,6 +13,7 @@ import {
     RawRigidBodySet,
     RawSerializationPipeline,
     RawDebugRenderPipeline,
+    RawWorldChecksum,
 } from "../raw";
 
 import {
@@ -30,6 +31,7 @@ import {
     Shape,
     ShapeColliderTOI,
     TempContactManifold,
+    ContactsDump,
 } from "../geometry";
 import {
     CCDSolver,
@@ -42,6 +44,8 @@ import {
     JointData,
     ImpulseJointSet,
     MultibodyJointSet,
+    Ragdoll,
+    RagdollDesc,
     RigidBody,
     RigidBodyDesc,
     RigidBodyHandle,
@@ -50,12 +54,23 @@ import {
 import {Rotation, Vector, VectorOps} from "../math";
 import {PhysicsPipeline} from "./physics_pipeline";
 import {QueryFilterFlags, QueryPipeline} from "./query_pipeline";
-import {SerializationPipeline} from "./serialization_pipeline";
+import {
+    BodyStates,
+    PrefabRemapping,
+    SerializationPipeline,
+} from "./serialization_pipeline";
+import {WorldChecksum} from "./checksum";
 import {EventQueue} from "./event_queue";
 import {PhysicsHooks} from "./physics_hooks";
 import {DebugRenderBuffers, DebugRenderPipeline} from "./debug_render_pipeline";
 import {KinematicCharacterController} from "../control";
+// #if DIM3
+import {DynamicRayCastVehicleController} from "../control";
+// #endif
 import {Coarena} from "../coarena";
+// #if DIM3
+import {UrdfLoader, UrdfLoaderOptions, UrdfRobot} from "./urdf_loader";
+// #endif
 
 /**
  * The physics world.
@@ -79,6 +94,9 @@ export class World {
     serializationPipeline: SerializationPipeline;
     debugRenderPipeline: DebugRenderPipeline;
     characterControllers: Set<KinematicCharacterController>;
+    // #if DIM3
+    vehicleControllers: Set<DynamicRayCastVehicleController>;
+    // #endif
 
     /**
      * Release the WASM memory occupied by this physics world.
@@ -101,6 +119,9 @@ export class World {
         this.serializationPipeline.free();
         this.debugRenderPipeline.free();
         this.characterControllers.forEach((controller) => controller.free());
+        // #if DIM3
+        this.vehicleControllers.forEach((controller) => controller.free());
+        // #endif
 
         this.integrationParameters = undefined;
         this.islands = undefined;
@@ -116,6 +137,9 @@ export class World {
         this.serializationPipeline = undefined;
         this.debugRenderPipeline = undefined;
         this.characterControllers = undefined;
+        // #if DIM3
+        this.vehicleControllers = undefined;
+        // #endif
     }
 
     constructor(
@@ -155,6 +179,9 @@ export class World {
             rawDebugRenderPipeline,
         );
         this.characterControllers = new Set<KinematicCharacterController>();
+        // #if DIM3
+        this.vehicleControllers = new Set<DynamicRayCastVehicleController>();
+        // #endif
 
         this.impulseJoints.finalizeDeserialization(this.bodies);
         this.bodies.finalizeDeserialization(this.colliders);
@@ -197,10 +224,207 @@ export class World {
         );
     }
 
+    /**
+     * Computes a deterministic checksum of the state of this world.
+     *
+     * Two worlds simulated deterministically from the same initial state have the same checksum.
+     * Comparing checksums computed by two peers every few steps can be used to detect desynchronizations.
+     *
+     * @param withIslands - If `true`, a checksum is also computed for every set of dynamic rigid-bodies
+     *                      interacting with each other through active contacts or joints. This helps
+     *                      localizing the rigid-bodies responsible for a desynchronization.
+     */
+    public checksum(withIslands?: boolean): WorldChecksum {
+        const raw = new RawWorldChecksum(
+            this.bodies.raw,
+            this.colliders.raw,
+            this.impulseJoints.raw,
+            this.multibodyJoints.raw,
+            this.narrowPhase.raw,
+            !!withIslands,
+        );
+        const result = new WorldChecksum(raw);
+        raw.free();
+        return result;
+    }
+
+    /**
+     * Takes a snapshot of this world, as a human-readable JSON string.
+     *
+     * This is useful for debugging, or for storing reproducible scenes in tests. Use
+     * `World.restoreJsonSnapshot` to create a new physics world from this snapshot.
+     */
+    public takeJsonSnapshot(): string {
+        return this.serializationPipeline.serializeAllJson(
+            this.gravity,
+            this.integrationParameters,
+            this.islands,
+            this.broadPhase,
+            this.narrowPhase,
+            this.bodies,
+            this.colliders,
+            this.impulseJoints,
+            this.multibodyJoints,
+        );
+    }
+
+    /**
+     * Creates a new physics world from a snapshot generated by `World.takeJsonSnapshot`.
+     *
+     * Throws an error if the snapshot is invalid, or was generated by an incompatible version
+     * or dimension (2D vs. 3D) of this library.
+     */
+    public static restoreJsonSnapshot(data: string): World {
+        let deser = new SerializationPipeline();
+        return deser.deserializeAllJson(data);
+    }
+
+    /**
+     * Takes a snapshot of the position, velocities, and sleep state of all the rigid-bodies of this world.
+     *
+     * This is much smaller than a full snapshot, and can be used as the base of `this.takeBodyStatesDelta`.
+     */
+    public takeBodyStatesSnapshot(): Uint8Array {
+        return this.serializationPipeline.serializeBodyStates(this.bodies);
+    }
+
+    /**
+     * Decodes a snapshot generated by `this.takeBodyStatesSnapshot`.
+     *
+     * Passing the result to `this.takeBodyStatesDelta` avoids decoding the same base snapshot each
+     * time a delta is taken. The result MUST be freed with `.free()` once it is no longer needed.
+     *
+     * @param data - A snapshot generated by `this.takeBodyStatesSnapshot`.
+     */
+    public decodeBodyStatesSnapshot(data: Uint8Array): BodyStates {
+        return this.serializationPipeline.decodeBodyStates(data);
+    }
+
+    /**
+     * Takes a snapshot of the position, velocities, and sleep state of the rigid-bodies which state
+     * changed since the given base snapshot.
+     *
+     * @param base - A snapshot generated by `this.takeBodyStatesSnapshot`, either serialized or decoded
+     *               by `this.decodeBodyStatesSnapshot`.
+     */
+    public takeBodyStatesDelta(base: Uint8Array | BodyStates): Uint8Array {
+        return this.serializationPipeline.serializeBodyStatesDelta(
+            base,
+            this.bodies,
+        );
+    }
+
+    /**
+     * Restores the rigid-body states from a snapshot generated by `this.takeBodyStatesSnapshot` or
+     * `this.takeBodyStatesDelta`.
+     *
+     * To restore the state recorded by a delta, first restore its base, then the delta itself.
+     * Throws an error if `data` is corrupted, or was generated by an incompatible version of this library.
+     *
+     * @param data - The snapshot to restore.
+     */
+    public restoreBodyStates(data: Uint8Array) {
+        this.serializationPipeline.applyBodyStates(data, this.bodies);
+    }
+
+    /**
+     * Serializes a set of rigid-bodies (for example a ragdoll or a vehicle), together with their
+     * attached colliders and the joints linking them together.
+     *
+     * Joints attached to a rigid-body that is not part of `bodies` are not serialized.
+     * The result can be inserted into any physics world with `World.insertPrefab`.
+     *
+     * @param bodies - The handles of the rigid-bodies to serialize.
+     */
+    public serializePrefab(bodies: RigidBodyHandle[]): Uint8Array {
+        return this.serializationPipeline.serializePrefab(
+            bodies,
+            this.bodies,
+            this.colliders,
+            this.impulseJoints,
+            this.multibodyJoints,
+        );
+    }
+
+    /**
+     * Inserts a copy of a prefab generated by `World.serializePrefab` into this world.
+     *
+     * The positions of the prefab’s rigid-bodies are pre-multiplied by the rigid-motion
+     * described by `translation` and `rotation`, and their velocities and forces are rotated accordingly.
+     * Throws an error if `data` is corrupted, or was generated by an incompatible version of this library.
+     *
+     * @param data - The prefab to insert.
+     * @param translation - The translation applied to the prefab.
+     * @param rotation - The rotation applied to the prefab.
+     * @returns The handles given to the inserted rigid-bodies, colliders, and joints.
+     */
+    public insertPrefab(
+        data: Uint8Array,
+        translation: Vector,
+        rotation: Rotation,
+    ): PrefabRemapping {
+        return this.serializationPipeline.insertPrefab(
+            data,
+            translation,
+            rotation,
+            this.bodies,
+            this.colliders,
+            this.impulseJoints,
+            this.multibodyJoints,
+        );
+    }
+
+    // #if DIM3
+    /**
+     * Inserts into this world the robot described by a URDF file.
+     *
+     * Each link is converted into a rigid-body with its colliders, and each joint into an
+     * impulse joint or a multibody joint, depending on `options.multibody`. Floating joints
+     * don’t constrain their links, so they are ignored: the child link of a floating joint is
+     * a free rigid-body, and the joint is missing from the joints of the result.
+     * Throws an error and inserts nothing if the URDF file is invalid.
+     *
+     * @param urdf - The content of the URDF file.
+     * @param options - The options of the loader.
+     * @returns The handles of the created elements, indexed by the names of their URDF links and joints.
+     */
+    public loadUrdf(urdf: string, options?: UrdfLoaderOptions): UrdfRobot {
+        return UrdfLoader.load(
+            urdf,
+            this.bodies,
+            this.colliders,
+            this.impulseJoints,
+            this.multibodyJoints,
+            options,
+        );
+    }
+
+    // #endif
+
+    /**
+     * Creates the rigid-bodies, colliders, and joints of a ragdoll.
+     *
+     * Adjacent bones of the ragdoll don’t collide with each other.
+     *
+     * @param desc - The description of the bones of the ragdoll.
+     * @returns The handles of the created elements, indexed by bone.
+     */
+    public createRagdoll(desc: RagdollDesc): Ragdoll {
+        return Ragdoll.create(
+            desc,
+            this.bodies,
+            this.colliders,
+            this.impulseJoints,
+            this.multibodyJoints,
+        );
+    }
+
     /**
      * Creates a new physics world from a snapshot.
      *
      * This new physics world will be an identical copy of the snapshoted physics world.
+     * Throws an error if the snapshot is corrupted, or was generated by an incompatible version
+     * or dimension (2D vs. 3D) of this library.
      */
     public static restoreSnapshot(data: Uint8Array): World {
         let deser = new SerializationPipeline();
@@ -366,6 +590,39 @@ export class World {
         controller.free();
     }
 
+    // #if DIM3
+    /**
+     * Creates a new raycast vehicle controller.
+     *
+     * @param chassis - The dynamic rigid-body the wheels of the vehicle are attached to.
+     */
+    public createVehicleController(
+        chassis: RigidBody,
+    ): DynamicRayCastVehicleController {
+        let controller = new DynamicRayCastVehicleController(
+            chassis,
+            this.bodies,
+            this.colliders,
+            this.queryPipeline,
+        );
+        this.vehicleControllers.add(controller);
+        return controller;
+    }
+
+    /**
+     * Removes a vehicle controller from this world.
+     *
+     * @param controller - The vehicle controller to remove.
+     */
+    public removeVehicleController(
+        controller: DynamicRayCastVehicleController,
+    ) {
+        this.vehicleControllers.delete(controller);
+        controller.free();
+    }
+
+    // #endif
+
     /**
      * Creates a new collider.
      *
@@ -403,6 +660,9 @@ export class World {
     /**
      * Creates a new multibody joint from the given joint descriptor.
      *
+     * Throws an error if `params` describes a rope or spring joint: these joints can only be
+     * created as impulse joints.
+     *
      * @param params - The description of the joint to create.
      * @param parent1 - The first rigid-body attached to this joint.
      * @param parent2 - The second rigid-body attached to this joint.
@@ -934,6 +1194,16 @@ export class World {
         this.narrowPhase.contactPair(collider1.handle, collider2.handle, f);
     }
 
+    /**
+     * Writes all the contact manifolds of this world (with at least one contact point)
+     * into flat typed arrays.
+     *
+     * @param out - (optional) A dump to fill, in order to reuse its memory across timesteps.
+     */
+    public dumpContacts(out?: ContactsDump): ContactsDump {
+        return this.narrowPhase.dumpContacts(this.colliders, out);
+    }
+
     /**
      * Returns `true` if `collider1` and `collider2` intersect and at least one of them is a sensor.
      * @param collider1 − The first collider involved in the intersection.
@@ -148,7 +148,7 @@ impl RawKinematicCharacterController {
     ) {
         let handle = crate::utils::collider_handle(collider_handle);
         if let Some(collider) = colliders.0.get(handle) {
-            crate::utils::with_filter(filter_predicate, |predicate| {
+            crate::utils::with_filter(colliders, filter_predicate, |predicate| {
                 let query_filter = QueryFilter {
                     flags: QueryFilterFlags::from_bits(filter_flags)
                         .unwrap_or(QueryFilterFlags::empty()),
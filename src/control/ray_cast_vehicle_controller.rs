@@ -0,0 +1,789 @@
+use crate::dynamics::{inv_effective_mass, RawRigidBodySet};
+use crate::geometry::RawColliderSet;
+use crate::math::RawVector;
+use crate::pipeline::RawQueryPipeline;
+use crate::utils::{self, FlatHandle};
+use rapier::dynamics::{RigidBody, RigidBodyHandle, RigidBodySet};
+use rapier::geometry::{ColliderHandle, ColliderSet, Ray};
+use rapier::math::{Point, Real, Rotation, Vector};
+use rapier::pipeline::{QueryFilter, QueryFilterFlags, QueryPipeline};
+use wasm_bindgen::prelude::*;
+
+/// The result of the ray-cast of the suspension of a wheel.
+#[derive(Copy, Clone, Debug)]
+struct WheelContact {
+    /// The world-space point of the wheel touching the ground.
+    contact_point_ws: Point<Real>,
+    /// The world-space normal of the ground at the contact point.
+    contact_normal_ws: Vector<Real>,
+    /// The world-space point where the suspension is attached to the chassis.
+    hard_point_ws: Point<Real>,
+    suspension_length: Real,
+    is_in_contact: bool,
+    ground_object: Option<ColliderHandle>,
+}
+
+/// A wheel attached to the chassis of a vehicle.
+#[derive(Copy, Clone, Debug)]
+struct Wheel {
+    // Configuration, in the local-space of the chassis.
+    chassis_connection_point_cs: Point<Real>,
+    direction_cs: Vector<Real>,
+    axle_cs: Vector<Real>,
+    suspension_rest_length: Real,
+    max_suspension_travel: Real,
+    radius: Real,
+    suspension_stiffness: Real,
+    damping_compression: Real,
+    damping_relaxation: Real,
+    max_suspension_force: Real,
+    friction_slip: Real,
+    side_friction_stiffness: Real,
+    roll_influence: Real,
+
+    // Controls.
+    steering: Real,
+    engine_force: Real,
+    brake: Real,
+
+    // State computed by the last vehicle update.
+    contact: WheelContact,
+    center: Point<Real>,
+    wheel_direction_ws: Vector<Real>,
+    wheel_axle_ws: Vector<Real>,
+    clipped_inv_contact_dot_suspension: Real,
+    suspension_relative_velocity: Real,
+    wheel_suspension_force: Real,
+    forward_impulse: Real,
+    side_impulse: Real,
+    skid_info: Real,
+    rotation: Real,
+    delta_rotation: Real,
+}
+
+impl Wheel {
+    fn new(
+        chassis_connection_point_cs: Point<Real>,
+        direction_cs: Vector<Real>,
+        axle_cs: Vector<Real>,
+        suspension_rest_length: Real,
+        radius: Real,
+    ) -> Self {
+        Self {
+            chassis_connection_point_cs,
+            direction_cs,
+            axle_cs,
+            suspension_rest_length,
+            max_suspension_travel: 5.0,
+            radius,
+            suspension_stiffness: 5.88,
+            damping_compression: 0.83,
+            damping_relaxation: 0.88,
+            max_suspension_force: 6000.0,
+            friction_slip: 10.5,
+            side_friction_stiffness: 1.0,
+            roll_influence: 0.1,
+            steering: 0.0,
+            engine_force: 0.0,
+            brake: 0.0,
+            contact: WheelContact {
+                contact_point_ws: Point::origin(),
+                contact_normal_ws: Vector::zeros(),
+                hard_point_ws: Point::origin(),
+                suspension_length: suspension_rest_length,
+                is_in_contact: false,
+                ground_object: None,
+            },
+            center: Point::origin(),
+            wheel_direction_ws: direction_cs,
+            wheel_axle_ws: axle_cs,
+            clipped_inv_contact_dot_suspension: 1.0,
+            suspension_relative_velocity: 0.0,
+            wheel_suspension_force: 0.0,
+            forward_impulse: 0.0,
+            side_impulse: 0.0,
+            skid_info: 1.0,
+            rotation: 0.0,
+            delta_rotation: 0.0,
+        }
+    }
+
+    /// Updates the world-space frame of the wheel from the pose of the chassis and the steering.
+    fn update_transform(&mut self, chassis: &RigidBody) {
+        let chassis_pose = chassis.position();
+        self.contact.is_in_contact = false;
+        self.contact.hard_point_ws = chassis_pose * self.chassis_connection_point_cs;
+        self.wheel_direction_ws = chassis_pose * self.direction_cs;
+
+        let steering = Rotation::new(-self.wheel_direction_ws * self.steering);
+        self.wheel_axle_ws = steering * (chassis_pose * self.axle_cs);
+    }
+
+    /// Casts the ray of the suspension of this wheel to find the ground below it.
+    fn ray_cast(
+        &mut self,
+        chassis: &RigidBody,
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        queries: &QueryPipeline,
+        filter: QueryFilter,
+    ) {
+        let ray_length = self.suspension_rest_length + self.radius;
+        let ray = Ray::new(
+            self.contact.hard_point_ws,
+            self.wheel_direction_ws * ray_length,
+        );
+        self.contact.ground_object = None;
+
+        let hit = queries.cast_ray_and_get_normal(bodies, colliders, &ray, 1.0, true, filter);
+
+        if let Some((handle, mut hit)) = hit {
+            if hit.toi == 0.0 {
+                // The ray starts inside of the ground: cast it backward to find the
+                // normal of the surface the wheel is sinking into.
+                let back_ray = Ray::new(ray.point_at(1.0), -ray.dir);
+                let back_hit = colliders.get(handle).and_then(|co| {
+                    co.shape()
+                        .cast_ray_and_get_normal(co.position(), &back_ray, 1.0, false)
+                });
+
+                hit.normal = match back_hit {
+                    Some(back_hit) => -back_hit.normal,
+                    None => -self.wheel_direction_ws,
+                };
+            }
+
+            self.contact.contact_normal_ws = hit.normal;
+            self.contact.is_in_contact = true;
+            self.contact.ground_object = Some(handle);
+            self.contact.suspension_length = (hit.toi * ray_length - self.radius)
+                .max(self.suspension_rest_length - self.max_suspension_travel)
+                .min(self.suspension_rest_length + self.max_suspension_travel);
+            self.contact.contact_point_ws = ray.point_at(hit.toi);
+
+            let denominator = hit.normal.dot(&self.wheel_direction_ws);
+            let chassis_velocity = chassis.velocity_at_point(&self.contact.contact_point_ws);
+            let projected_velocity = hit.normal.dot(&chassis_velocity);
+
+            if denominator >= -0.1 {
+                self.suspension_relative_velocity = 0.0;
+                self.clipped_inv_contact_dot_suspension = 1.0 / 0.1;
+            } else {
+                let inv = -1.0 / denominator;
+                self.suspension_relative_velocity = projected_velocity * inv;
+                self.clipped_inv_contact_dot_suspension = inv;
+            }
+        } else {
+            self.contact.suspension_length = self.suspension_rest_length;
+            self.contact.contact_point_ws = ray.point_at(1.0);
+            self.contact.contact_normal_ws = -self.wheel_direction_ws;
+            self.suspension_relative_velocity = 0.0;
+            self.clipped_inv_contact_dot_suspension = 1.0;
+        }
+    }
+
+    /// Computes the force applied by the suspension spring of this wheel to the chassis.
+    fn update_suspension(&mut self, chassis_mass: Real) {
+        if !self.contact.is_in_contact {
+            self.wheel_suspension_force = 0.0;
+            return;
+        }
+
+        let length_diff = self.suspension_rest_length - self.contact.suspension_length;
+        let mut force =
+            self.suspension_stiffness * length_diff * self.clipped_inv_contact_dot_suspension;
+        let damping = if self.suspension_relative_velocity < 0.0 {
+            self.damping_compression
+        } else {
+            self.damping_relaxation
+        };
+        force -= damping * self.suspension_relative_velocity;
+        self.wheel_suspension_force = (force * chassis_mass).max(0.0);
+    }
+}
+
+/// The relative velocity between two rigid-bodies at the given world-space point.
+fn relative_velocity(
+    rb1: &RigidBody,
+    rb2: Option<&RigidBody>,
+    point: &Point<Real>,
+) -> Vector<Real> {
+    let vel1 = rb1.velocity_at_point(point);
+    let vel2 = rb2
+        .map(|rb| rb.velocity_at_point(point))
+        .unwrap_or_else(Vector::zeros);
+    vel1 - vel2
+}
+
+/// The impulse cancelling a fraction of the relative velocity between two rigid-bodies along
+/// `dir`, clamped to `[-max_impulse, max_impulse]`.
+fn velocity_impulse(
+    rb1: &RigidBody,
+    rb2: Option<&RigidBody>,
+    point: &Point<Real>,
+    dir: &Vector<Real>,
+    fraction: Real,
+    max_impulse: Real,
+) -> Real {
+    let denominator = inv_effective_mass(rb1, point, dir)
+        + rb2.map_or(0.0, |rb| inv_effective_mass(rb, point, dir));
+
+    if denominator <= 0.0 {
+        return 0.0;
+    }
+
+    let vrel = dir.dot(&relative_velocity(rb1, rb2, point));
+    (-fraction * vrel / denominator)
+        .max(-max_impulse)
+        .min(max_impulse)
+}
+
+/// A raycast-based vehicle controller, simulating wheels with suspensions and tire friction by
+/// applying impulses to a dynamic chassis rigid-body.
+///
+/// The wheels don’t have any rigid-body or collider: the ground below each wheel is detected by
+/// casting a ray along its suspension.
+#[wasm_bindgen]
+pub struct RawDynamicRayCastVehicleController {
+    chassis: RigidBodyHandle,
+    wheels: Vec<Wheel>,
+    index_up_axis: usize,
+    index_forward_axis: usize,
+    current_vehicle_speed: Real,
+}
+
+#[wasm_bindgen]
+impl RawDynamicRayCastVehicleController {
+    /// Creates a vehicle controller without any wheel.
+    ///
+    /// # Parameters
+    /// - `chassis`: the dynamic rigid-body the wheels are attached to.
+    #[wasm_bindgen(constructor)]
+    pub fn new(chassis: FlatHandle) -> Self {
+        Self {
+            chassis: utils::body_handle(chassis),
+            wheels: vec![],
+            index_up_axis: 1,
+            index_forward_axis: 0,
+            current_vehicle_speed: 0.0,
+        }
+    }
+
+    /// The handle of the chassis rigid-body.
+    pub fn chassis(&self) -> FlatHandle {
+        utils::flat_handle(self.chassis.0)
+    }
+
+    /// The speed of the chassis along its forward axis, computed by the last vehicle update.
+    pub fn currentVehicleSpeed(&self) -> Real {
+        self.current_vehicle_speed
+    }
+
+    /// The index of the local-space axis of the chassis pointing upward.
+    pub fn indexUpAxis(&self) -> usize {
+        self.index_up_axis
+    }
+
+    pub fn setIndexUpAxis(&mut self, axis: usize) {
+        self.index_up_axis = axis.min(2);
+    }
+
+    /// The index of the local-space axis of the chassis pointing forward.
+    pub fn indexForwardAxis(&self) -> usize {
+        self.index_forward_axis
+    }
+
+    pub fn setIndexForwardAxis(&mut self, axis: usize) {
+        self.index_forward_axis = axis.min(2);
+    }
+
+    /// Adds a wheel to this vehicle and returns its index.
+    ///
+    /// # Parameters
+    /// - `chassisConnectionCs`: the point where the suspension is attached to the chassis, in
+    ///   the local-space of the chassis.
+    /// - `directionCs`: the direction of the suspension, pointing toward the ground, in the
+    ///   local-space of the chassis.
+    /// - `axleCs`: the rotation axis of the wheel, in the local-space of the chassis.
+    /// - `suspensionRestLength`: the length of the suspension when it isn’t compressed.
+    /// - `radius`: the radius of the wheel.
+    pub fn addWheel(
+        &mut self,
+        chassisConnectionCs: &RawVector,
+        directionCs: &RawVector,
+        axleCs: &RawVector,
+        suspensionRestLength: Real,
+        radius: Real,
+    ) -> usize {
+        self.wheels.push(Wheel::new(
+            chassisConnectionCs.0.into(),
+            directionCs.0.normalize(),
+            axleCs.0.normalize(),
+            suspensionRestLength,
+            radius,
+        ));
+        self.wheels.len() - 1
+    }
+
+    /// The number of wheels attached to this vehicle.
+    pub fn numWheels(&self) -> usize {
+        self.wheels.len()
+    }
+
+    /// Casts the ray of every wheel, then applies the suspension, engine, braking, and friction
+    /// impulses to the chassis and to the dynamic rigid-bodies the wheels are in contact with.
+    ///
+    /// This should be called once before each physics step.
+    ///
+    /// # Parameters
+    /// - `dt`: the timestep of the next physics step.
+    /// - `filter_flags`, `filter_groups`, `filter_predicate`: the filter of the colliders the
+    ///   wheels can touch. The colliders attached to the chassis are always ignored.
+    pub fn updateVehicle(
+        &mut self,
+        dt: Real,
+        bodies: &mut RawRigidBodySet,
+        colliders: &RawColliderSet,
+        queries: &RawQueryPipeline,
+        filter_flags: u32,
+        filter_groups: Option<u32>,
+        filter_predicate: &js_sys::Function,
+    ) {
+        let chassis = match bodies.0.get_mut(self.chassis) {
+            Some(chassis) => chassis,
+            None => return,
+        };
+
+        if self.wheels.iter().any(|wheel| wheel.engine_force != 0.0) {
+            chassis.wake_up(true);
+        }
+
+        let chassis = &bodies.0[self.chassis];
+        let forward_ws = chassis.position() * Vector::ith(self.index_forward_axis, 1.0);
+        self.current_vehicle_speed = chassis.linvel().norm();
+
+        if forward_ws.dot(chassis.linvel()) < 0.0 {
+            self.current_vehicle_speed = -self.current_vehicle_speed;
+        }
+
+        for wheel in &mut self.wheels {
+            wheel.update_transform(chassis);
+        }
+
+        utils::with_filter(colliders, filter_predicate, |predicate| {
+            let query_filter = QueryFilter {
+                flags: QueryFilterFlags::from_bits(filter_flags)
+                    .unwrap_or(QueryFilterFlags::empty()),
+                groups: filter_groups.map(crate::geometry::unpack_interaction_groups),
+                exclude_collider: None,
+                exclude_rigid_body: Some(self.chassis),
+                predicate,
+            };
+
+            for wheel in &mut self.wheels {
+                wheel.ray_cast(chassis, &bodies.0, &colliders.0, &queries.0, query_filter);
+                wheel.center = wheel.contact.hard_point_ws
+                    + wheel.wheel_direction_ws * wheel.contact.suspension_length;
+            }
+        });
+
+        let chassis_mass = chassis.mass();
+
+        for wheel in &mut self.wheels {
+            wheel.update_suspension(chassis_mass);
+        }
+
+        if let Some(chassis) = bodies.0.get_mut(self.chassis) {
+            for wheel in &self.wheels {
+                let force = wheel.wheel_suspension_force.min(wheel.max_suspension_force);
+                let impulse = wheel.contact.contact_normal_ws * (force * dt);
+                chassis.apply_impulse_at_point(impulse, wheel.contact.contact_point_ws, false);
+            }
+        }
+
+        self.update_friction(&mut bodies.0, &colliders.0, dt);
+
+        let chassis = &bodies.0[self.chassis];
+        let forward_axis = Vector::ith(self.index_forward_axis, 1.0);
+
+        for wheel in &mut self.wheels {
+            if wheel.contact.is_in_contact {
+                let vel = chassis.velocity_at_point(&wheel.contact.hard_point_ws);
+                let normal = wheel.contact.contact_normal_ws;
+                let mut forward = chassis.position() * forward_axis;
+                forward -= normal * forward.dot(&normal);
+                wheel.delta_rotation = forward.dot(&vel) * dt / wheel.radius;
+            }
+
+            wheel.rotation += wheel.delta_rotation;
+            // Slowly stop the rotation of the wheels that don’t touch the ground.
+            wheel.delta_rotation *= 0.99;
+        }
+    }
+
+    /// The world-space position of the center of a wheel, computed by the last vehicle update.
+    pub fn wheelCenter(&self, i: usize) -> Option<RawVector> {
+        self.wheels.get(i).map(|w| w.center.coords.into())
+    }
+
+    /// The point where the suspension of a wheel is attached to the chassis, in the local-space
+    /// of the chassis.
+    pub fn wheelChassisConnectionPointCs(&self, i: usize) -> Option<RawVector> {
+        self.wheels
+            .get(i)
+            .map(|w| w.chassis_connection_point_cs.coords.into())
+    }
+
+    pub fn setWheelChassisConnectionPointCs(&mut self, i: usize, value: &RawVector) {
+        if let Some(wheel) = self.wheels.get_mut(i) {
+            wheel.chassis_connection_point_cs = value.0.into();
+        }
+    }
+
+    /// The direction of the suspension of a wheel, in the local-space of the chassis.
+    pub fn wheelDirectionCs(&self, i: usize) -> Option<RawVector> {
+        self.wheels.get(i).map(|w| w.direction_cs.into())
+    }
+
+    pub fn setWheelDirectionCs(&mut self, i: usize, value: &RawVector) {
+        if let Some(wheel) = self.wheels.get_mut(i) {
+            wheel.direction_cs = value.0.normalize();
+        }
+    }
+
+    /// The rotation axis of a wheel, in the local-space of the chassis.
+    pub fn wheelAxleCs(&self, i: usize) -> Option<RawVector> {
+        self.wheels.get(i).map(|w| w.axle_cs.into())
+    }
+
+    pub fn setWheelAxleCs(&mut self, i: usize, value: &RawVector) {
+        if let Some(wheel) = self.wheels.get_mut(i) {
+            wheel.axle_cs = value.0.normalize();
+        }
+    }
+
+    /// The length of the suspension of a wheel when it isn’t compressed.
+    pub fn wheelSuspensionRestLength(&self, i: usize) -> Option<Real> {
+        self.wheels.get(i).map(|w| w.suspension_rest_length)
+    }
+
+    pub fn setWheelSuspensionRestLength(&mut self, i: usize, value: Real) {
+        if let Some(wheel) = self.wheels.get_mut(i) {
+            wheel.suspension_rest_length = value;
+        }
+    }
+
+    /// The maximum distance the suspension of a wheel can travel from its rest length.
+    pub fn wheelMaxSuspensionTravel(&self, i: usize) -> Option<Real> {
+        self.wheels.get(i).map(|w| w.max_suspension_travel)
+    }
+
+    pub fn setWheelMaxSuspensionTravel(&mut self, i: usize, value: Real) {
+        if let Some(wheel) = self.wheels.get_mut(i) {
+            wheel.max_suspension_travel = value;
+        }
+    }
+
+    /// The radius of a wheel.
+    pub fn wheelRadius(&self, i: usize) -> Option<Real> {
+        self.wheels.get(i).map(|w| w.radius)
+    }
+
+    pub fn setWheelRadius(&mut self, i: usize, value: Real) {
+        if let Some(wheel) = self.wheels.get_mut(i) {
+            wheel.radius = value;
+        }
+    }
+
+    /// The stiffness of the suspension spring of a wheel.
+    pub fn wheelSuspensionStiffness(&self, i: usize) -> Option<Real> {
+        self.wheels.get(i).map(|w| w.suspension_stiffness)
+    }
+
+    pub fn setWheelSuspensionStiffness(&mut self, i: usize, value: Real) {
+        if let Some(wheel) = self.wheels.get_mut(i) {
+            wheel.suspension_stiffness = value;
+        }
+    }
+
+    /// The damping of the suspension of a wheel while it is being compressed.
+    pub fn wheelSuspensionCompression(&self, i: usize) -> Option<Real> {
+        self.wheels.get(i).map(|w| w.damping_compression)
+    }
+
+    pub fn setWheelSuspensionCompression(&mut self, i: usize, value: Real) {
+        if let Some(wheel) = self.wheels.get_mut(i) {
+            wheel.damping_compression = value;
+        }
+    }
+
+    /// The damping of the suspension of a wheel while it is being extended.
+    pub fn wheelSuspensionRelaxation(&self, i: usize) -> Option<Real> {
+        self.wheels.get(i).map(|w| w.damping_relaxation)
+    }
+
+    pub fn setWheelSuspensionRelaxation(&mut self, i: usize, value: Real) {
+        if let Some(wheel) = self.wheels.get_mut(i) {
+            wheel.damping_relaxation = value;
+        }
+    }
+
+    /// The maximum force the suspension of a wheel can apply to the chassis.
+    pub fn wheelMaxSuspensionForce(&self, i: usize) -> Option<Real> {
+        self.wheels.get(i).map(|w| w.max_suspension_force)
+    }
+
+    pub fn setWheelMaxSuspensionForce(&mut self, i: usize, value: Real) {
+        if let Some(wheel) = self.wheels.get_mut(i) {
+            wheel.max_suspension_force = value;
+        }
+    }
+
+    /// The ratio between the maximum friction impulse of a wheel and its suspension force.
+    pub fn wheelFrictionSlip(&self, i: usize) -> Option<Real> {
+        self.wheels.get(i).map(|w| w.friction_slip)
+    }
+
+    pub fn setWheelFrictionSlip(&mut self, i: usize, value: Real) {
+        if let Some(wheel) = self.wheels.get_mut(i) {
+            wheel.friction_slip = value;
+        }
+    }
+
+    /// The multiplier of the friction impulse of a wheel along its axle.
+    pub fn wheelSideFrictionStiffness(&self, i: usize) -> Option<Real> {
+        self.wheels.get(i).map(|w| w.side_friction_stiffness)
+    }
+
+    pub fn setWheelSideFrictionStiffness(&mut self, i: usize, value: Real) {
+        if let Some(wheel) = self.wheels.get_mut(i) {
+            wheel.side_friction_stiffness = value;
+        }
+    }
+
+    /// How much the side friction impulse of a wheel makes the chassis roll, between 0 and 1.
+    pub fn wheelRollInfluence(&self, i: usize) -> Option<Real> {
+        self.wheels.get(i).map(|w| w.roll_influence)
+    }
+
+    pub fn setWheelRollInfluence(&mut self, i: usize, value: Real) {
+        if let Some(wheel) = self.wheels.get_mut(i) {
+            wheel.roll_influence = value;
+        }
+    }
+
+    /// The steering angle of a wheel, around its suspension direction.
+    pub fn wheelSteering(&self, i: usize) -> Option<Real> {
+        self.wheels.get(i).map(|w| w.steering)
+    }
+
+    pub fn setWheelSteering(&mut self, i: usize, value: Real) {
+        if let Some(wheel) = self.wheels.get_mut(i) {
+            wheel.steering = value;
+        }
+    }
+
+    /// The forward force applied by the engine on a wheel.
+    pub fn wheelEngineForce(&self, i: usize) -> Option<Real> {
+        self.wheels.get(i).map(|w| w.engine_force)
+    }
+
+    pub fn setWheelEngineForce(&mut self, i: usize, value: Real) {
+        if let Some(wheel) = self.wheels.get_mut(i) {
+            wheel.engine_force = value;
+        }
+    }
+
+    /// The maximum braking impulse applied on a wheel.
+    pub fn wheelBrake(&self, i: usize) -> Option<Real> {
+        self.wheels.get(i).map(|w| w.brake)
+    }
+
+    pub fn setWheelBrake(&mut self, i: usize, value: Real) {
+        if let Some(wheel) = self.wheels.get_mut(i) {
+            wheel.brake = value;
+        }
+    }
+
+    /// The rotation angle of a wheel around its axle, accumulated by the vehicle updates.
+    pub fn wheelRotation(&self, i: usize) -> Option<Real> {
+        self.wheels.get(i).map(|w| w.rotation)
+    }
+
+    /// The forward impulse applied by a wheel during the last vehicle update.
+    pub fn wheelForwardImpulse(&self, i: usize) -> Option<Real> {
+        self.wheels.get(i).map(|w| w.forward_impulse)
+    }
+
+    /// The side impulse applied by a wheel during the last vehicle update.
+    pub fn wheelSideImpulse(&self, i: usize) -> Option<Real> {
+        self.wheels.get(i).map(|w| w.side_impulse)
+    }
+
+    /// The force applied by the suspension of a wheel during the last vehicle update.
+    pub fn wheelSuspensionForce(&self, i: usize) -> Option<Real> {
+        self.wheels.get(i).map(|w| w.wheel_suspension_force)
+    }
+
+    /// The length of the suspension of a wheel, computed by the last vehicle update.
+    pub fn wheelSuspensionLength(&self, i: usize) -> Option<Real> {
+        self.wheels.get(i).map(|w| w.contact.suspension_length)
+    }
+
+    /// The ratio between the friction impulse applied by a wheel during the last vehicle update
+    /// and the impulse needed to prevent it from sliding.
+    ///
+    /// This is 1 if the wheel doesn’t skid, and smaller than 1 if it does.
+    pub fn wheelSkidInfo(&self, i: usize) -> Option<Real> {
+        self.wheels.get(i).map(|w| w.skid_info)
+    }
+
+    /// Was a wheel touching the ground during the last vehicle update?
+    pub fn wheelIsInContact(&self, i: usize) -> bool {
+        self.wheels
+            .get(i)
+            .map(|w| w.contact.is_in_contact)
+            .unwrap_or(false)
+    }
+
+    /// The world-space point where a wheel touched the ground during the last vehicle update.
+    pub fn wheelContactPoint(&self, i: usize) -> Option<RawVector> {
+        self.wheels
+            .get(i)
+            .map(|w| w.contact.contact_point_ws.coords.into())
+    }
+
+    /// The world-space normal of the ground touched by a wheel during the last vehicle update.
+    pub fn wheelContactNormal(&self, i: usize) -> Option<RawVector> {
+        self.wheels
+            .get(i)
+            .map(|w| w.contact.contact_normal_ws.into())
+    }
+
+    /// The world-space point where the suspension of a wheel is attached to the chassis,
+    /// computed by the last vehicle update.
+    pub fn wheelHardPoint(&self, i: usize) -> Option<RawVector> {
+        self.wheels
+            .get(i)
+            .map(|w| w.contact.hard_point_ws.coords.into())
+    }
+
+    /// The handle of the collider touched by a wheel during the last vehicle update.
+    pub fn wheelGroundObject(&self, i: usize) -> Option<FlatHandle> {
+        self.wheels
+            .get(i)
+            .and_then(|w| w.contact.ground_object)
+            .map(|h| utils::flat_handle(h.0))
+    }
+}
+
+impl RawDynamicRayCastVehicleController {
+    /// Computes and applies the forward and side friction impulses of the wheels.
+    fn update_friction(&mut self, bodies: &mut RigidBodySet, colliders: &ColliderSet, dt: Real) {
+        let chassis = match bodies.get(self.chassis) {
+            Some(chassis) => chassis,
+            None => return,
+        };
+        let ground_body = |wheel: &Wheel| {
+            wheel
+                .contact
+                .ground_object
+                .and_then(|h| colliders.get(h))
+                .and_then(|co| co.parent())
+        };
+
+        let mut forward_ws = vec![Vector::zeros(); self.wheels.len()];
+        let mut axle_ws = vec![Vector::zeros(); self.wheels.len()];
+        let mut sliding = false;
+
+        for (i, wheel) in self.wheels.iter_mut().enumerate() {
+            wheel.forward_impulse = 0.0;
+            wheel.side_impulse = 0.0;
+            wheel.skid_info = 1.0;
+
+            if !wheel.contact.is_in_contact {
+                continue;
+            }
+
+            let ground = ground_body(wheel).and_then(|h| bodies.get(h));
+            let normal = wheel.contact.contact_normal_ws;
+            let point = wheel.contact.contact_point_ws;
+
+            // Project the axle on the ground plane.
+            let axle = wheel.wheel_axle_ws - normal * wheel.wheel_axle_ws.dot(&normal);
+            axle_ws[i] = axle.try_normalize(1.0e-6).unwrap_or(axle);
+            forward_ws[i] = normal
+                .cross(&axle_ws[i])
+                .try_normalize(1.0e-6)
+                .unwrap_or_else(Vector::zeros);
+
+            // The side impulse only cancels a fraction of the lateral velocity at each step.
+            wheel.side_impulse =
+                velocity_impulse(chassis, ground, &point, &axle_ws[i], 0.2, Real::MAX)
+                    * wheel.side_friction_stiffness;
+
+            wheel.forward_impulse = if wheel.engine_force != 0.0 {
+                wheel.engine_force * dt
+            } else {
+                velocity_impulse(chassis, ground, &point, &forward_ws[i], 1.0, wheel.brake)
+            };
+
+            let max_impulse = wheel.wheel_suspension_force * dt * wheel.friction_slip;
+            let x = wheel.forward_impulse * 0.5;
+            let y = wheel.side_impulse;
+            let impulse_squared = x * x + y * y;
+
+            if impulse_squared > max_impulse * max_impulse {
+                sliding = true;
+                wheel.skid_info = max_impulse / impulse_squared.sqrt();
+            }
+        }
+
+        if sliding {
+            for wheel in &mut self.wheels {
+                if wheel.side_impulse != 0.0 && wheel.skid_info < 1.0 {
+                    wheel.forward_impulse *= wheel.skid_info;
+                    wheel.side_impulse *= wheel.skid_info;
+                }
+            }
+        }
+
+        let up_ws = chassis.position().rotation * Vector::ith(self.index_up_axis, 1.0);
+        let center_of_mass = chassis.mass_properties().world_com(chassis.position());
+
+        for (i, wheel) in self.wheels.iter().enumerate() {
+            let point = wheel.contact.contact_point_ws;
+            let ground = ground_body(wheel);
+
+            if let Some(chassis) = bodies.get_mut(self.chassis) {
+                if wheel.forward_impulse != 0.0 {
+                    chassis.apply_impulse_at_point(
+                        forward_ws[i] * wheel.forward_impulse,
+                        point,
+                        false,
+                    );
+                }
+
+                if wheel.side_impulse != 0.0 {
+                    // Apply the side impulse closer to the center of mass to reduce rolling.
+                    let mut arm = point - center_of_mass;
+                    arm -= up_ws * (up_ws.dot(&arm) * (1.0 - wheel.roll_influence));
+                    chassis.apply_impulse_at_point(
+                        axle_ws[i] * wheel.side_impulse,
+                        center_of_mass + arm,
+                        false,
+                    );
+                }
+            }
+
+            // The ground receives the reaction of the forward and side impulses.
+            if wheel.forward_impulse != 0.0 || wheel.side_impulse != 0.0 {
+                if let Some(ground) = ground.and_then(|h| bodies.get_mut(h)) {
+                    let impulse =
+                        forward_ws[i] * wheel.forward_impulse + axle_ws[i] * wheel.side_impulse;
+                    ground.apply_impulse_at_point(-impulse, point, false);
+                }
+            }
+        }
+    }
+}
@@ -1,14 +1,20 @@
-use crate::dynamics::{RawImpulseJointSet, RawJointAxis, RawJointType, RawMotorModel};
+use crate::dynamics::{
+    DistanceJoint, RawImpulseJointSet, RawJointAxis, RawJointType, RawMotorModel,
+};
 use crate::math::{RawRotation, RawVector};
 use crate::utils::{self, FlatHandle};
 use rapier::dynamics::JointAxis;
+use rapier::math::Isometry;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
 impl RawImpulseJointSet {
     /// The type of this joint.
     pub fn jointType(&self, handle: FlatHandle) -> RawJointType {
-        self.map(handle, |j| j.data.locked_axes.into())
+        match self.distance_joint(handle) {
+            Some(distance_joint) => (*distance_joint).into(),
+            None => self.map_data(handle, |data| data.locked_axes.into()),
+        }
     }
 
     /// The unique integer identifier of the first rigid-body this joint it attached to.
@@ -23,12 +29,12 @@ impl RawImpulseJointSet {
 
     /// The angular part of the joint’s local frame relative to the first rigid-body it is attached to.
     pub fn jointFrameX1(&self, handle: FlatHandle) -> RawRotation {
-        self.map(handle, |j| j.data.local_frame1.rotation.into())
+        self.map_data(handle, |data| data.local_frame1.rotation.into())
     }
 
     /// The angular part of the joint’s local frame relative to the second rigid-body it is attached to.
     pub fn jointFrameX2(&self, handle: FlatHandle) -> RawRotation {
-        self.map(handle, |j| j.data.local_frame2.rotation.into())
+        self.map_data(handle, |data| data.local_frame2.rotation.into())
     }
 
     /// The position of the first anchor of this joint.
@@ -36,7 +42,7 @@ impl RawImpulseJointSet {
     /// The first anchor gives the position of the points application point on the
     /// local frame of the first rigid-body it is attached to.
     pub fn jointAnchor1(&self, handle: FlatHandle) -> RawVector {
-        self.map(handle, |j| j.data.local_frame1.translation.vector.into())
+        self.map_data(handle, |data| data.local_frame1.translation.vector.into())
     }
 
     /// The position of the second anchor of this joint.
@@ -44,56 +50,78 @@ impl RawImpulseJointSet {
     /// The second anchor gives the position of the points application point on the
     /// local frame of the second rigid-body it is attached to.
     pub fn jointAnchor2(&self, handle: FlatHandle) -> RawVector {
-        self.map(handle, |j| j.data.local_frame2.translation.vector.into())
+        self.map_data(handle, |data| data.local_frame2.translation.vector.into())
     }
 
     /// Sets the position of the first local anchor
     pub fn jointSetAnchor1(&mut self, handle: FlatHandle, newPos: &RawVector) {
-        self.map_mut(handle, |j| {
-            j.data.set_local_anchor1(newPos.0.into());
+        self.map_data_mut(handle, |data| {
+            data.set_local_anchor1(newPos.0.into());
         });
     }
 
     /// Sets the position of the second local anchor
     pub fn jointSetAnchor2(&mut self, handle: FlatHandle, newPos: &RawVector) {
-        self.map_mut(handle, |j| {
-            j.data.set_local_anchor2(newPos.0.into());
+        self.map_data_mut(handle, |data| {
+            data.set_local_anchor2(newPos.0.into());
         })
     }
 
+    /// Sets the joint’s local frame relative to the first rigid-body it is attached to.
+    ///
+    /// # Parameters
+    /// - `anchor`: the new position of the first anchor.
+    /// - `axes`: the new orientation of the joint’s first local frame.
+    pub fn jointSetFrame1(&mut self, handle: FlatHandle, anchor: &RawVector, axes: &RawRotation) {
+        self.map_data_mut(handle, |data| {
+            data.set_local_frame1(Isometry::from_parts(anchor.0.into(), axes.0));
+        });
+    }
+
+    /// Sets the joint’s local frame relative to the second rigid-body it is attached to.
+    ///
+    /// # Parameters
+    /// - `anchor`: the new position of the second anchor.
+    /// - `axes`: the new orientation of the joint’s second local frame.
+    pub fn jointSetFrame2(&mut self, handle: FlatHandle, anchor: &RawVector, axes: &RawRotation) {
+        self.map_data_mut(handle, |data| {
+            data.set_local_frame2(Isometry::from_parts(anchor.0.into(), axes.0));
+        });
+    }
+
     /// Are contacts between the rigid-bodies attached by this joint enabled?
     pub fn jointContactsEnabled(&self, handle: FlatHandle) -> bool {
-        self.map(handle, |j| j.data.contacts_enabled)
+        self.map_data(handle, |data| data.contacts_enabled)
     }
 
     /// Sets whether contacts are enabled between the rigid-bodies attached by this joint.
     pub fn jointSetContactsEnabled(&mut self, handle: FlatHandle, enabled: bool) {
-        self.map_mut(handle, |j| {
-            j.data.contacts_enabled = enabled;
+        self.map_data_mut(handle, |data| {
+            data.contacts_enabled = enabled;
         });
     }
 
     /// Are the limits for this joint enabled?
     pub fn jointLimitsEnabled(&self, handle: FlatHandle, axis: RawJointAxis) -> bool {
-        self.map(handle, |j| {
-            j.data.limit_axes.contains(JointAxis::from(axis).into())
+        self.map_data(handle, |data| {
+            data.limit_axes.contains(JointAxis::from(axis).into())
         })
     }
 
     /// Return the lower limit along the given joint axis.
     pub fn jointLimitsMin(&self, handle: FlatHandle, axis: RawJointAxis) -> f32 {
-        self.map(handle, |j| j.data.limits[axis as usize].min)
+        self.map_data(handle, |data| data.limits[axis as usize].min)
     }
 
     /// If this is a prismatic joint, returns its upper limit.
     pub fn jointLimitsMax(&self, handle: FlatHandle, axis: RawJointAxis) -> f32 {
-        self.map(handle, |j| j.data.limits[axis as usize].max)
+        self.map_data(handle, |data| data.limits[axis as usize].max)
     }
 
     /// Enables and sets the joint limits
     pub fn jointSetLimits(&mut self, handle: FlatHandle, axis: RawJointAxis, min: f32, max: f32) {
-        self.map_mut(handle, |j| {
-            j.data.set_limits(axis.into(), [min, max]);
+        self.map_data_mut(handle, |data| {
+            data.set_limits(axis.into(), [min, max]);
         });
     }
 
@@ -103,8 +131,8 @@ impl RawImpulseJointSet {
         axis: RawJointAxis,
         model: RawMotorModel,
     ) {
-        self.map_mut(handle, |j| {
-            j.data.motors[axis as usize].model = model.into()
+        self.map_data_mut(handle, |data| {
+            data.motors[axis as usize].model = model.into()
         })
     }
 
@@ -177,6 +205,59 @@ impl RawImpulseJointSet {
     }
     */
 
+    /// Is the motor of this joint along the given axis enabled?
+    pub fn jointMotorEnabled(&self, handle: FlatHandle, axis: RawJointAxis) -> bool {
+        self.map_data(handle, |data| {
+            data.motor_axes.contains(JointAxis::from(axis).into())
+        })
+    }
+
+    /// The model of the motor of this joint along the given axis.
+    pub fn jointMotorModel(&self, handle: FlatHandle, axis: RawJointAxis) -> RawMotorModel {
+        self.map_data(handle, |data| data.motors[axis as usize].model.into())
+    }
+
+    /// The target position of the motor of this joint along the given axis.
+    pub fn jointMotorTargetPos(&self, handle: FlatHandle, axis: RawJointAxis) -> f32 {
+        self.map_data(handle, |data| data.motors[axis as usize].target_pos)
+    }
+
+    /// The target velocity of the motor of this joint along the given axis.
+    pub fn jointMotorTargetVel(&self, handle: FlatHandle, axis: RawJointAxis) -> f32 {
+        self.map_data(handle, |data| data.motors[axis as usize].target_vel)
+    }
+
+    /// The stiffness of the motor of this joint along the given axis.
+    pub fn jointMotorStiffness(&self, handle: FlatHandle, axis: RawJointAxis) -> f32 {
+        self.map_data(handle, |data| data.motors[axis as usize].stiffness)
+    }
+
+    /// The damping of the motor of this joint along the given axis.
+    pub fn jointMotorDamping(&self, handle: FlatHandle, axis: RawJointAxis) -> f32 {
+        self.map_data(handle, |data| data.motors[axis as usize].damping)
+    }
+
+    /// The maximum force the motor of this joint can deliver along the given axis.
+    pub fn jointMotorMaxForce(&self, handle: FlatHandle, axis: RawJointAxis) -> f32 {
+        self.map_data(handle, |data| data.motors[axis as usize].max_force)
+    }
+
+    /// Sets the maximum force the motor of this joint can deliver along the given axis.
+    pub fn jointSetMotorMaxForce(&mut self, handle: FlatHandle, axis: RawJointAxis, maxForce: f32) {
+        self.map_data_mut(handle, |data| {
+            data.motors[axis as usize].max_force = maxForce;
+        })
+    }
+
+    /// The impulse applied by the solver along the given axis of this joint during the last
+    /// timestep, including the contributions of its limits and motor.
+    ///
+    /// Linear axes give a linear impulse, and angular axes give an angular impulse, both
+    /// expressed in the local frame of this joint.
+    pub fn jointImpulse(&self, handle: FlatHandle, axis: RawJointAxis) -> f32 {
+        self.map(handle, |j| j.impulses[axis as usize])
+    }
+
     pub fn jointConfigureMotorVelocity(
         &mut self,
         handle: FlatHandle,
@@ -207,9 +288,68 @@ impl RawImpulseJointSet {
         stiffness: f32,
         damping: f32,
     ) {
-        self.map_mut(handle, |j| {
-            j.data
-                .set_motor(axis.into(), targetPos, targetVel, stiffness, damping);
+        self.map_data_mut(handle, |data| {
+            data.set_motor(axis.into(), targetPos, targetVel, stiffness, damping);
         })
     }
+
+    /// The maximum distance allowed between the anchors of this rope joint.
+    pub fn jointRopeLength(&self, handle: FlatHandle) -> f32 {
+        match self.distance_joint(handle) {
+            Some(DistanceJoint::Rope { length }) => *length,
+            _ => 0.0,
+        }
+    }
+
+    /// Sets the maximum distance allowed between the anchors of this rope joint.
+    pub fn jointSetRopeLength(&mut self, handle: FlatHandle, length: f32) {
+        if let Some(DistanceJoint::Rope { length: l }) = self.distance_joint_mut(handle) {
+            *l = length;
+        }
+    }
+
+    /// The distance this spring joint tends to restore between its anchors.
+    pub fn jointSpringRestLength(&self, handle: FlatHandle) -> f32 {
+        match self.distance_joint(handle) {
+            Some(DistanceJoint::Spring { rest_length, .. }) => *rest_length,
+            _ => 0.0,
+        }
+    }
+
+    /// Sets the distance this spring joint tends to restore between its anchors.
+    pub fn jointSetSpringRestLength(&mut self, handle: FlatHandle, restLength: f32) {
+        if let Some(DistanceJoint::Spring { rest_length, .. }) = self.distance_joint_mut(handle) {
+            *rest_length = restLength;
+        }
+    }
+
+    /// The stiffness of this spring joint.
+    pub fn jointSpringStiffness(&self, handle: FlatHandle) -> f32 {
+        match self.distance_joint(handle) {
+            Some(DistanceJoint::Spring { stiffness, .. }) => *stiffness,
+            _ => 0.0,
+        }
+    }
+
+    /// Sets the stiffness of this spring joint.
+    pub fn jointSetSpringStiffness(&mut self, handle: FlatHandle, stiffness: f32) {
+        if let Some(DistanceJoint::Spring { stiffness: s, .. }) = self.distance_joint_mut(handle) {
+            *s = stiffness;
+        }
+    }
+
+    /// The damping coefficient of this spring joint.
+    pub fn jointSpringDamping(&self, handle: FlatHandle) -> f32 {
+        match self.distance_joint(handle) {
+            Some(DistanceJoint::Spring { damping, .. }) => *damping,
+            _ => 0.0,
+        }
+    }
+
+    /// Sets the damping coefficient of this spring joint.
+    pub fn jointSetSpringDamping(&mut self, handle: FlatHandle, damping: f32) {
+        if let Some(DistanceJoint::Spring { damping: d, .. }) = self.distance_joint_mut(handle) {
+            *d = damping;
+        }
+    }
 }
@@ -1,12 +1,266 @@
-use crate::dynamics::RawGenericJoint;
+use crate::dynamics::{inv_effective_mass, DistanceJoint, RawGenericJoint, RawRigidBodySet};
 use crate::utils::{self, FlatHandle};
-use rapier::dynamics::{ImpulseJoint, ImpulseJointSet};
+use rapier::dynamics::{
+    GenericJoint, GenericJointBuilder, ImpulseJoint, ImpulseJointHandle, ImpulseJointSet,
+    JointAxesMask, RigidBody, RigidBodyHandle,
+};
+use rapier::math::{Point, Real, DIM};
+use std::collections::HashMap;
 use wasm_bindgen::prelude::*;
 
+/// The maximum force and torque a breakable joint can apply before breaking.
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub(crate) struct BreakThresholds {
+    pub max_force: Real,
+    pub max_torque: Real,
+}
+
+/// A joint removed because it applied a force or torque exceeding its break thresholds.
+#[derive(Copy, Clone)]
+pub(crate) struct BrokenJoint {
+    pub handle: ImpulseJointHandle,
+    pub body1: RigidBodyHandle,
+    pub body2: RigidBodyHandle,
+    pub force: Real,
+    pub torque: Real,
+}
+
+/// The fraction of the excess length of a rope corrected at each timestep.
+const ROPE_CORRECTION_FACTOR: Real = 0.2;
+
 #[wasm_bindgen]
-pub struct RawImpulseJointSet(pub(crate) ImpulseJointSet);
+pub struct RawImpulseJointSet(
+    pub(crate) ImpulseJointSet,
+    // The joints attached to a disabled rigid-body. They are replaced by free joints
+    // until all their rigid-bodies are enabled again.
+    pub(crate) HashMap<ImpulseJointHandle, GenericJoint>,
+    // The thresholds of the breakable joints.
+    pub(crate) HashMap<ImpulseJointHandle, BreakThresholds>,
+    // The joints that broke during the last timestep.
+    pub(crate) Vec<ImpulseJointHandle>,
+    // The rope and spring joints. They are inserted as free joints, and enforced by
+    // `apply_distance_joints` before each timestep.
+    pub(crate) HashMap<ImpulseJointHandle, DistanceJoint>,
+);
 
 impl RawImpulseJointSet {
+    pub(crate) fn from_parts(
+        joints: ImpulseJointSet,
+        neutralized: impl IntoIterator<Item = (ImpulseJointHandle, GenericJoint)>,
+        break_thresholds: impl IntoIterator<Item = (ImpulseJointHandle, BreakThresholds)>,
+        distance_joints: impl IntoIterator<Item = (ImpulseJointHandle, DistanceJoint)>,
+    ) -> Self {
+        RawImpulseJointSet(
+            joints,
+            neutralized.into_iter().collect(),
+            break_thresholds.into_iter().collect(),
+            vec![],
+            distance_joints.into_iter().collect(),
+        )
+    }
+
+    /// The rope and spring joints, in handle order.
+    pub(crate) fn distance_joints(&self) -> Vec<(ImpulseJointHandle, DistanceJoint)> {
+        let mut joints: Vec<_> = self.4.iter().map(|(h, j)| (*h, *j)).collect();
+        joints.sort_by_key(|(h, _)| h.0.into_raw_parts());
+        joints
+    }
+
+    pub(crate) fn distance_joint(&self, handle: FlatHandle) -> Option<&DistanceJoint> {
+        self.4.get(&utils::impulse_joint_handle(handle))
+    }
+
+    pub(crate) fn distance_joint_mut(&mut self, handle: FlatHandle) -> Option<&mut DistanceJoint> {
+        self.4.get_mut(&utils::impulse_joint_handle(handle))
+    }
+
+    /// Applies the impulses of the rope and spring joints for a timestep of length `dt`.
+    ///
+    /// This must be called before each simulation step, after `sync_disabled_bodies`. These
+    /// impulses are applied outside of the constraints solver: ropes are only corrected by
+    /// `ROPE_CORRECTION_FACTOR` at each timestep, springs are integrated explicitly, and neither
+    /// is seen by CCD. A sleeping rigid-body is woken up if it receives an impulse.
+    pub(crate) fn apply_distance_joints(&self, bodies: &mut RawRigidBodySet, dt: Real) {
+        if dt <= 0.0 {
+            return;
+        }
+
+        for (handle, distance_joint) in self.distance_joints() {
+            let joint = match self.0.get(handle) {
+                Some(joint) => joint,
+                None => continue,
+            };
+
+            if !bodies.is_enabled(joint.body1) || !bodies.is_enabled(joint.body2) {
+                continue;
+            }
+
+            let (rb1, rb2) = match (bodies.0.get(joint.body1), bodies.0.get(joint.body2)) {
+                (Some(rb1), Some(rb2)) => (rb1, rb2),
+                _ => continue,
+            };
+
+            let is_awake = |rb: &RigidBody| rb.is_dynamic() && !rb.is_sleeping();
+            if !is_awake(rb1) && !is_awake(rb2) {
+                continue;
+            }
+
+            let anchor1 = rb1.position() * Point::from(joint.data.local_frame1.translation.vector);
+            let anchor2 = rb2.position() * Point::from(joint.data.local_frame2.translation.vector);
+            let delta = anchor2 - anchor1;
+            let dist = delta.norm();
+
+            if dist <= Real::EPSILON {
+                continue;
+            }
+
+            let dir = delta / dist;
+            // Positive if the anchors are moving away from each other.
+            let rel_vel =
+                dir.dot(&(rb2.velocity_at_point(&anchor2) - rb1.velocity_at_point(&anchor1)));
+
+            let impulse = match distance_joint {
+                DistanceJoint::Rope { length } => {
+                    // The relative velocity which brings the anchors back to `length` at the
+                    // end of the timestep.
+                    let max_rel_vel = if dist <= length {
+                        (length - dist) / dt
+                    } else {
+                        -(dist - length) * ROPE_CORRECTION_FACTOR / dt
+                    };
+                    let denominator = inv_effective_mass(rb1, &anchor1, &dir)
+                        + inv_effective_mass(rb2, &anchor2, &dir);
+
+                    if rel_vel <= max_rel_vel || denominator <= 0.0 {
+                        continue;
+                    }
+
+                    (rel_vel - max_rel_vel) / denominator
+                }
+                DistanceJoint::Spring {
+                    rest_length,
+                    stiffness,
+                    damping,
+                } => (stiffness * (dist - rest_length) + damping * rel_vel) * dt,
+            };
+
+            if let Some(rb1) = bodies.0.get_mut(joint.body1) {
+                rb1.apply_impulse_at_point(dir * impulse, anchor1, true);
+            }
+            if let Some(rb2) = bodies.0.get_mut(joint.body2) {
+                rb2.apply_impulse_at_point(-dir * impulse, anchor2, true);
+            }
+        }
+    }
+
+    /// The thresholds of the breakable joints, in handle order.
+    pub(crate) fn break_thresholds(&self) -> Vec<(ImpulseJointHandle, BreakThresholds)> {
+        let mut thresholds: Vec<_> = self.2.iter().map(|(h, t)| (*h, *t)).collect();
+        thresholds.sort_by_key(|(h, _)| h.0.into_raw_parts());
+        thresholds
+    }
+
+    /// Removes the breakable joints which applied, during the last timestep of length `dt`,
+    /// a force or a torque greater than their thresholds.
+    ///
+    /// This must be called after each simulation step. Returns the broken joints, in handle order.
+    pub(crate) fn break_overloaded_joints(&mut self, dt: Real) -> Vec<BrokenJoint> {
+        let joints = &self.0;
+        // Forget the thresholds of the joints removed with their rigid-bodies.
+        self.2.retain(|handle, _| joints.get(*handle).is_some());
+        self.4.retain(|handle, _| joints.get(*handle).is_some());
+        self.3.clear();
+
+        if dt <= 0.0 {
+            return vec![];
+        }
+
+        let mut broken = vec![];
+
+        for (handle, thresholds) in self.2.iter() {
+            if let Some(joint) = self.0.get(*handle) {
+                // The first `DIM` impulses are linear, the other ones are angular.
+                let linear = joint.impulses.iter().take(DIM);
+                let angular = joint.impulses.iter().skip(DIM);
+                let force = linear.map(|i| i * i).sum::<Real>().sqrt() / dt;
+                let torque = angular.map(|i| i * i).sum::<Real>().sqrt() / dt;
+
+                if force > thresholds.max_force || torque > thresholds.max_torque {
+                    broken.push(BrokenJoint {
+                        handle: *handle,
+                        body1: joint.body1,
+                        body2: joint.body2,
+                        force,
+                        torque,
+                    });
+                }
+            }
+        }
+
+        broken.sort_by_key(|joint| joint.handle.0.into_raw_parts());
+
+        for joint in &broken {
+            self.0.remove(joint.handle, true);
+            self.1.remove(&joint.handle);
+            self.2.remove(&joint.handle);
+            self.3.push(joint.handle);
+            self.4.remove(&joint.handle);
+        }
+
+        broken
+    }
+
+    /// The joints replaced by free joints because they are attached to a disabled rigid-body,
+    /// in handle order.
+    pub(crate) fn neutralized_joints(&self) -> Vec<(ImpulseJointHandle, GenericJoint)> {
+        let mut neutralized: Vec<_> = self.1.iter().map(|(h, j)| (*h, *j)).collect();
+        neutralized.sort_by_key(|(h, _)| h.0.into_raw_parts());
+        neutralized
+    }
+
+    /// The data of a joint, ignoring the fact that it may be neutralized because it is
+    /// attached to a disabled rigid-body.
+    pub(crate) fn joint_data(&self, handle: ImpulseJointHandle) -> Option<&GenericJoint> {
+        self.1
+            .get(&handle)
+            .or_else(|| self.0.get(handle).map(|joint| &joint.data))
+    }
+
+    /// Replaces the joints attached to disabled rigid-bodies by free joints, and restores the
+    /// joints which rigid-bodies are all enabled again.
+    ///
+    /// This must be called before each simulation step.
+    pub(crate) fn sync_disabled_bodies(&mut self, bodies: &RawRigidBodySet) {
+        let joints = &mut self.0;
+        self.1.retain(|handle, data| match joints.get_mut(*handle) {
+            Some(joint) => {
+                if bodies.is_enabled(joint.body1) && bodies.is_enabled(joint.body2) {
+                    joint.data = *data;
+                    false
+                } else {
+                    true
+                }
+            }
+            None => false,
+        });
+
+        let to_neutralize: Vec<_> = bodies
+            .1
+            .keys()
+            .flat_map(|body| self.0.attached_joints(*body))
+            .map(|(_, _, handle, _)| handle)
+            .filter(|handle| !self.1.contains_key(handle))
+            .collect();
+
+        for handle in to_neutralize {
+            if let Some(joint) = self.0.get_mut(handle) {
+                let free_joint = GenericJointBuilder::new(JointAxesMask::empty()).build();
+                let data = std::mem::replace(&mut joint.data, free_joint);
+                self.1.entry(handle).or_insert(data);
+            }
+        }
+    }
+
     pub(crate) fn map<T>(&self, handle: FlatHandle, f: impl FnOnce(&ImpulseJoint) -> T) -> T {
         let body = self.0.get(utils::impulse_joint_handle(handle)).expect(
             "Invalid ImpulseJoint reference. It may have been removed from the physics World.",
@@ -14,15 +268,33 @@ impl RawImpulseJointSet {
         f(body)
     }
 
-    pub(crate) fn map_mut<T>(
+    /// Applies `f` to the data of a joint, or to its original data if it is neutralized because
+    /// it is attached to a disabled rigid-body.
+    pub(crate) fn map_data<T>(&self, handle: FlatHandle, f: impl FnOnce(&GenericJoint) -> T) -> T {
+        let data = self.joint_data(utils::impulse_joint_handle(handle)).expect(
+            "Invalid ImpulseJoint reference. It may have been removed from the physics World.",
+        );
+        f(data)
+    }
+
+    /// Applies `f` to the data of a joint, or to its original data if it is neutralized because
+    /// it is attached to a disabled rigid-body, so that the modification survives its restoration.
+    pub(crate) fn map_data_mut<T>(
         &mut self,
         handle: FlatHandle,
-        f: impl FnOnce(&mut ImpulseJoint) -> T,
+        f: impl FnOnce(&mut GenericJoint) -> T,
     ) -> T {
-        let body = self.0.get_mut(utils::impulse_joint_handle(handle)).expect(
-            "Invalid ImpulseJoint reference. It may have been removed from the physics World.",
-        );
-        f(body)
+        let handle = utils::impulse_joint_handle(handle);
+        let data = match self.1.get_mut(&handle) {
+            Some(data) => data,
+            None => {
+                let joint = self.0.get_mut(handle).expect(
+                    "Invalid ImpulseJoint reference. It may have been removed from the physics World.",
+                );
+                &mut joint.data
+            }
+        };
+        f(data)
     }
 }
 
@@ -30,7 +302,13 @@ impl RawImpulseJointSet {
 impl RawImpulseJointSet {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
-        RawImpulseJointSet(ImpulseJointSet::new())
+        RawImpulseJointSet(
+            ImpulseJointSet::new(),
+            HashMap::new(),
+            HashMap::new(),
+            vec![],
+            HashMap::new(),
+        )
     }
 
     pub fn createJoint(
@@ -40,21 +318,77 @@ impl RawImpulseJointSet {
         parent2: FlatHandle,
         wake_up: bool,
     ) -> FlatHandle {
-        utils::flat_handle(
-            self.0
-                .insert(
-                    utils::body_handle(parent1),
-                    utils::body_handle(parent2),
-                    params.0.clone(),
-                    wake_up,
-                )
-                .0,
-        )
+        let handle = self.0.insert(
+            utils::body_handle(parent1),
+            utils::body_handle(parent2),
+            params.0,
+            wake_up,
+        );
+
+        if let Some(distance_joint) = params.1 {
+            self.4.insert(handle, distance_joint);
+        }
+
+        utils::flat_handle(handle.0)
     }
 
     pub fn remove(&mut self, handle: FlatHandle, wakeUp: bool) {
         let handle = utils::impulse_joint_handle(handle);
         self.0.remove(handle, wakeUp);
+        self.1.remove(&handle);
+        self.2.remove(&handle);
+        self.4.remove(&handle);
+    }
+
+    /// Makes this joint breakable: it is removed automatically at the end of the first timestep
+    /// during which it applies a force or a torque greater than the given thresholds.
+    ///
+    /// # Parameters
+    /// - `maxForce`: the maximum force this joint can apply. Can be `Infinity`.
+    /// - `maxTorque`: the maximum torque this joint can apply. Can be `Infinity`.
+    pub fn jointSetBreakThresholds(&mut self, handle: FlatHandle, maxForce: f32, maxTorque: f32) {
+        let handle = utils::impulse_joint_handle(handle);
+        if self.0.get(handle).is_none() {
+            return;
+        }
+
+        if maxForce == f32::INFINITY && maxTorque == f32::INFINITY {
+            self.2.remove(&handle);
+        } else {
+            let thresholds = BreakThresholds {
+                max_force: maxForce,
+                max_torque: maxTorque,
+            };
+            self.2.insert(handle, thresholds);
+        }
+    }
+
+    /// The maximum force this joint can apply before breaking, or `Infinity` if it isn’t breakable.
+    pub fn jointBreakForce(&self, handle: FlatHandle) -> f32 {
+        self.2
+            .get(&utils::impulse_joint_handle(handle))
+            .map(|t| t.max_force)
+            .unwrap_or(f32::INFINITY)
+    }
+
+    /// The maximum torque this joint can apply before breaking, or `Infinity` if it isn’t breakable.
+    pub fn jointBreakTorque(&self, handle: FlatHandle) -> f32 {
+        self.2
+            .get(&utils::impulse_joint_handle(handle))
+            .map(|t| t.max_torque)
+            .unwrap_or(f32::INFINITY)
+    }
+
+    /// Applies the given JavaScript function to the integer handle of each joint that broke,
+    /// and was removed from this set, during the last timestep.
+    ///
+    /// # Parameters
+    /// - `f(handle)`: the function to apply to the integer handle of each broken joint.
+    pub fn forEachBrokenJointHandle(&self, f: &js_sys::Function) {
+        let this = JsValue::null();
+        for handle in &self.3 {
+            let _ = f.call1(&this, &JsValue::from(utils::flat_handle(handle.0)));
+        }
     }
 
     pub fn len(&self) -> usize {
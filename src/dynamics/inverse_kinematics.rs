@@ -0,0 +1,278 @@
+use super::multibody_joint::{
+    joint_coordinates, joint_displacement, joint_pose, update_link_positions,
+};
+use crate::dynamics::{RawMultibodyJointSet, RawRigidBodySet};
+use crate::math::{RawRotation, RawVector};
+use crate::utils::{self, FlatHandle};
+use na::{DMatrix, DVector};
+use rapier::dynamics::GenericJoint;
+use rapier::math::{Isometry, Real, ANG_DIM, DIM, SPATIAL_DIM};
+use std::ops::Range;
+use wasm_bindgen::prelude::*;
+
+/// The step used to approximate the jacobian of the kinematic chain by finite differences.
+const FINITE_DIFFERENCE_STEP: Real = 1.0e-3;
+
+/// The result of an inverse-kinematics resolution.
+#[wasm_bindgen]
+pub struct RawInverseKinematicsResult {
+    converged: bool,
+    joints: Vec<FlatHandle>,
+    positions: Vec<Vec<f32>>,
+}
+
+#[wasm_bindgen]
+impl RawInverseKinematicsResult {
+    /// Did the solver reach the target pose within the requested tolerances?
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+
+    /// The number of joints with at least one degree of freedom between the root of the
+    /// multibody and the target link.
+    pub fn numJoints(&self) -> usize {
+        self.joints.len()
+    }
+
+    /// The integer handle of the `i`-th joint of the kinematic chain, starting from the root.
+    pub fn joint(&self, i: usize) -> Option<FlatHandle> {
+        self.joints.get(i).copied()
+    }
+
+    /// The generalized coordinates computed for the `i`-th joint of the kinematic chain.
+    pub fn positions(&self, i: usize) -> Option<Vec<f32>> {
+        self.positions.get(i).cloned()
+    }
+}
+
+#[wasm_bindgen]
+impl RawMultibodyJointSet {
+    /// Computes the generalized coordinates of the joints between the root of a multibody and
+    /// one of its links so that this link reaches the given world-space pose.
+    ///
+    /// The problem is solved iteratively with the damped least-squares method. The root of the
+    /// multibody is never moved, and the coordinates are clamped to the limits of their joints.
+    /// The jacobian of the kinematic chain is approximated by finite differences, with a fixed
+    /// step of `1.0e-3` on each generalized coordinate, which may slow down the convergence for
+    /// multibodies much smaller or larger than one unit of length.
+    ///
+    /// # Parameters
+    /// - `handle`: the joint attaching the target link to its parent.
+    /// - `bodies`: the set of rigid-bodies attached by the joints of this multibody.
+    /// - `targetTranslation`: the world-space position the target link must reach.
+    /// - `targetRotation`: the world-space orientation the target link must reach.
+    /// - `constrainedAxes`: the bit mask of the world-space axes of the target pose that have to
+    ///   be reached. Use the linear axes only to ignore `targetRotation`.
+    /// - `maxIters`: the maximum number of iterations of the solver.
+    /// - `damping`: the damping factor of the solver. Greater values improve the stability of
+    ///   the resolution close to singularities, at the cost of a slower convergence.
+    /// - `epsilonLinear`: the distance to the target position below which the solver stops.
+    /// - `epsilonAngular`: the angle to the target orientation below which the solver stops.
+    /// - `apply`: if `true`, the computed coordinates are applied to the joints, and their
+    ///   rigid-bodies are teleported accordingly.
+    pub fn jointInverseKinematics(
+        &mut self,
+        handle: FlatHandle,
+        bodies: &mut RawRigidBodySet,
+        targetTranslation: &RawVector,
+        targetRotation: &RawRotation,
+        constrainedAxes: u8,
+        maxIters: usize,
+        damping: f32,
+        epsilonLinear: f32,
+        epsilonAngular: f32,
+        apply: bool,
+    ) -> RawInverseKinematicsResult {
+        let target = Isometry::from_parts(targetTranslation.0.into(), targetRotation.0);
+
+        self.map_multibody_mut(handle, |multibody, link_id| {
+            // Collect the links between the root (excluded) and the target link.
+            let mut link_ids = vec![];
+            let mut root_id = link_id;
+
+            while let Some(parent_id) = multibody.link(root_id).unwrap().parent_id() {
+                link_ids.push(root_id);
+                root_id = parent_id;
+            }
+
+            link_ids.reverse();
+
+            let root = multibody.link(root_id).unwrap();
+            let mut chain = KinematicChain {
+                root_pose: bodies
+                    .0
+                    .get(root.rigid_body_handle())
+                    .map(|rb| *rb.position())
+                    .unwrap_or_else(Isometry::identity),
+                joints: vec![],
+                ranges: vec![],
+            };
+            let mut coords = vec![];
+
+            for id in &link_ids {
+                let joint = multibody.link(*id).unwrap().joint();
+                let start = coords.len();
+                coords.extend(joint_coordinates(joint));
+                chain.joints.push(joint.data);
+                chain.ranges.push(start..coords.len());
+            }
+
+            let converged = chain.solve(
+                &mut coords,
+                &target,
+                constrainedAxes,
+                maxIters,
+                damping,
+                epsilonLinear,
+                epsilonAngular,
+            );
+
+            let mut result = RawInverseKinematicsResult {
+                converged,
+                joints: vec![],
+                positions: vec![],
+            };
+
+            for (id, range) in link_ids.iter().zip(chain.ranges.iter()) {
+                if !range.is_empty() {
+                    let body = multibody.link(*id).unwrap().rigid_body_handle();
+                    result.joints.push(utils::flat_handle(body.0));
+                    result.positions.push(coords[range.clone()].to_vec());
+                }
+            }
+
+            if apply && !link_ids.is_empty() {
+                for (id, range) in link_ids.iter().zip(chain.ranges.iter()) {
+                    let joint = &mut multibody.link_mut(*id).unwrap().joint;
+                    let disp = joint_displacement(joint, &coords[range.clone()]);
+                    joint.apply_displacement(&disp);
+                }
+
+                update_link_positions(multibody, link_ids[0], &mut bodies.0);
+            }
+
+            result
+        })
+    }
+}
+
+/// The joints between the root of a multibody and one of its links.
+struct KinematicChain {
+    root_pose: Isometry<Real>,
+    joints: Vec<GenericJoint>,
+    /// The range of the generalized coordinates of each joint in the coordinates of the chain.
+    ranges: Vec<Range<usize>>,
+}
+
+impl KinematicChain {
+    /// The world-space pose of the last link of the chain for the given generalized coordinates.
+    fn end_pose(&self, coords: &[Real]) -> Isometry<Real> {
+        self.joints
+            .iter()
+            .zip(self.ranges.iter())
+            .fold(self.root_pose, |pose, (joint, range)| {
+                pose * joint_pose(joint, &coords[range.clone()])
+            })
+    }
+
+    /// Updates `coords` so the last link of the chain reaches `target`.
+    ///
+    /// Returns `true` if the target was reached within the given tolerances.
+    fn solve(
+        &self,
+        coords: &mut [Real],
+        target: &Isometry<Real>,
+        constrained_axes: u8,
+        max_iters: usize,
+        damping: Real,
+        epsilon_linear: Real,
+        epsilon_angular: Real,
+    ) -> bool {
+        let num_lin = (0..DIM)
+            .filter(|i| constrained_axes & (1 << i) != 0)
+            .count();
+
+        for iter in 0..=max_iters {
+            let error = pose_error(&self.end_pose(coords), target, constrained_axes);
+            let (lin_error, ang_error) = error.split_at(num_lin);
+
+            if norm(lin_error) <= epsilon_linear && norm(ang_error) <= epsilon_angular {
+                return true;
+            }
+
+            if iter == max_iters || coords.is_empty() {
+                break;
+            }
+
+            // Approximate the jacobian of the end pose by finite differences.
+            let mut jacobian = DMatrix::zeros(error.len(), coords.len());
+
+            for j in 0..coords.len() {
+                let mut perturbed = coords.to_vec();
+                perturbed[j] += FINITE_DIFFERENCE_STEP;
+                let perturbed_error =
+                    pose_error(&self.end_pose(&perturbed), target, constrained_axes);
+
+                for i in 0..error.len() {
+                    jacobian[(i, j)] = (error[i] - perturbed_error[i]) / FINITE_DIFFERENCE_STEP;
+                }
+            }
+
+            // Damped least-squares step: J^T (J J^T + λ² I)^-1 e
+            let lhs = &jacobian * jacobian.transpose()
+                + DMatrix::identity(error.len(), error.len()) * (damping * damping);
+            let step = match lhs.cholesky() {
+                Some(chol) => jacobian.transpose() * chol.solve(&DVector::from_vec(error)),
+                None => break,
+            };
+
+            for (coord, delta) in coords.iter_mut().zip(step.iter()) {
+                *coord += *delta;
+            }
+
+            for (joint, range) in self.joints.iter().zip(self.ranges.iter()) {
+                clamp_to_limits(joint, &mut coords[range.clone()]);
+            }
+        }
+
+        false
+    }
+}
+
+/// The components of the displacement from `pose` to `target` along the constrained axes.
+///
+/// The linear components come first, followed by the angular components.
+fn pose_error(pose: &Isometry<Real>, target: &Isometry<Real>, constrained_axes: u8) -> Vec<Real> {
+    let lin = target.translation.vector - pose.translation.vector;
+    let ang = (target.rotation * pose.rotation.inverse()).scaled_axis();
+
+    let lin_error = (0..DIM)
+        .filter(|i| constrained_axes & (1 << i) != 0)
+        .map(|i| lin[i]);
+    let ang_error = (0..ANG_DIM)
+        .filter(|i| constrained_axes & (1 << (DIM + i)) != 0)
+        .map(|i| ang[i]);
+    lin_error.chain(ang_error).collect()
+}
+
+/// Clamps the generalized coordinates of a joint to its limits.
+fn clamp_to_limits(joint: &GenericJoint, coords: &mut [Real]) {
+    let locked_bits = joint.locked_axes.bits();
+    let limit_bits = joint.limit_axes.bits();
+    let mut coords = coords.iter_mut();
+
+    for i in 0..SPATIAL_DIM {
+        if locked_bits & (1 << i) == 0 {
+            if let Some(coord) = coords.next() {
+                if limit_bits & (1 << i) != 0 {
+                    let limits = &joint.limits[i];
+                    *coord = coord.max(limits.min).min(limits.max);
+                }
+            }
+        }
+    }
+}
+
+fn norm(v: &[Real]) -> Real {
+    v.iter().map(|x| x * x).sum::<Real>().sqrt()
+}
@@ -3,10 +3,10 @@ use na::Unit;
 #[cfg(feature = "dim3")]
 use rapier::dynamics::SphericalJointBuilder;
 use rapier::dynamics::{
-    FixedJointBuilder, GenericJoint, JointAxesMask, JointAxis, MotorModel, PrismaticJointBuilder,
-    RevoluteJointBuilder,
+    FixedJointBuilder, GenericJoint, GenericJointBuilder, JointAxesMask, JointAxis, MotorModel,
+    PrismaticJointBuilder, RevoluteJointBuilder,
 };
-use rapier::math::Isometry;
+use rapier::math::{Isometry, Real};
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -16,6 +16,8 @@ pub enum RawJointType {
     Fixed,
     Prismatic,
     Generic,
+    Rope,
+    Spring,
 }
 
 #[wasm_bindgen]
@@ -26,6 +28,33 @@ pub enum RawJointType {
     Prismatic,
     Spherical,
     Generic,
+    Rope,
+    Spring,
+}
+
+/// A joint acting on the distance between its anchors.
+///
+/// Rapier can’t constrain this distance, so these joints are inserted as free joints and
+/// their impulses are applied by `RawImpulseJointSet::apply_distance_joints`.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub(crate) enum DistanceJoint {
+    Rope {
+        length: Real,
+    },
+    Spring {
+        rest_length: Real,
+        stiffness: Real,
+        damping: Real,
+    },
+}
+
+impl From<DistanceJoint> for RawJointType {
+    fn from(joint: DistanceJoint) -> RawJointType {
+        match joint {
+            DistanceJoint::Rope { .. } => RawJointType::Rope,
+            DistanceJoint::Spring { .. } => RawJointType::Spring,
+        }
+    }
 }
 
 /// The type of this joint.
@@ -99,6 +128,15 @@ impl From<RawMotorModel> for MotorModel {
     }
 }
 
+impl From<MotorModel> for RawMotorModel {
+    fn from(model: MotorModel) -> RawMotorModel {
+        match model {
+            MotorModel::AccelerationBased => RawMotorModel::AccelerationBased,
+            MotorModel::ForceBased => RawMotorModel::ForceBased,
+        }
+    }
+}
+
 #[cfg(feature = "dim2")]
 #[wasm_bindgen]
 #[derive(Copy, Clone)]
@@ -137,7 +175,11 @@ impl From<RawJointAxis> for JointAxis {
 }
 
 #[wasm_bindgen]
-pub struct RawGenericJoint(pub(crate) GenericJoint);
+pub struct RawGenericJoint(
+    pub(crate) GenericJoint,
+    // Set for the joints acting on the distance between their anchors.
+    pub(crate) Option<DistanceJoint>,
+);
 
 #[wasm_bindgen]
 impl RawGenericJoint {
@@ -153,6 +195,7 @@ impl RawGenericJoint {
                 .local_anchor1(anchor1.0.into())
                 .local_anchor2(anchor2.0.into())
                 .into(),
+            None,
         )
     }
 
@@ -180,7 +223,7 @@ impl RawGenericJoint {
             joint = joint.limits([limitsMin, limitsMax]);
         }
 
-        Some(Self(joint.into()))
+        Some(Self(joint.into(), None))
     }
 
     /// Creates a new joint descriptor that builds a Prismatic joint.
@@ -207,7 +250,7 @@ impl RawGenericJoint {
             joint = joint.limits([limitsMin, limitsMax]);
         }
 
-        Some(Self(joint.into()))
+        Some(Self(joint.into(), None))
     }
 
     /// Creates a new joint descriptor that builds a Fixed joint.
@@ -226,6 +269,7 @@ impl RawGenericJoint {
                 .local_frame1(pos1)
                 .local_frame2(pos2)
                 .into(),
+            None,
         )
     }
 
@@ -240,6 +284,7 @@ impl RawGenericJoint {
                 .local_anchor1(anchor1.0.into())
                 .local_anchor2(anchor2.0.into())
                 .into(),
+            None,
         ))
     }
 
@@ -259,6 +304,111 @@ impl RawGenericJoint {
                 .local_anchor1(anchor1.0.into())
                 .local_anchor2(anchor2.0.into())
                 .into(),
+            None,
         ))
     }
+
+    /// Creates a new joint descriptor that builds rope joints.
+    ///
+    /// A rope joint prevents the distance between the anchors of the two attached
+    /// rigid-bodies from exceeding `length`, but doesn’t prevent them from getting closer.
+    /// It can only be inserted as an impulse joint.
+    pub fn rope(length: f32, anchor1: &RawVector, anchor2: &RawVector) -> RawGenericJoint {
+        Self(
+            GenericJointBuilder::new(JointAxesMask::empty())
+                .local_anchor1(anchor1.0.into())
+                .local_anchor2(anchor2.0.into())
+                .build(),
+            Some(DistanceJoint::Rope { length }),
+        )
+    }
+
+    /// Creates a new joint descriptor that builds spring joints.
+    ///
+    /// A spring joint applies a force proportional to the difference between the distance
+    /// separating the anchors of the two attached rigid-bodies and `restLength`, damped by
+    /// their relative velocity. It can only be inserted as an impulse joint.
+    ///
+    /// # Parameters
+    /// - `restLength`: the distance the spring tends to restore between the two anchors.
+    /// - `stiffness`: the spring stiffness, in force per unit of length.
+    /// - `damping`: the damping coefficient, in force per unit of relative velocity.
+    pub fn spring(
+        restLength: f32,
+        stiffness: f32,
+        damping: f32,
+        anchor1: &RawVector,
+        anchor2: &RawVector,
+    ) -> RawGenericJoint {
+        Self(
+            GenericJointBuilder::new(JointAxesMask::empty())
+                .local_anchor1(anchor1.0.into())
+                .local_anchor2(anchor2.0.into())
+                .build(),
+            Some(DistanceJoint::Spring {
+                rest_length: restLength,
+                stiffness,
+                damping,
+            }),
+        )
+    }
+
+    /// Creates a new joint descriptor that builds generic joints.
+    ///
+    /// A generic joint locks the relative degrees of freedom specified by `lockedAxes`
+    /// between its local frames. Limits and motors can then be added to its free axes
+    /// with `setLimits` and `setMotor`.
+    ///
+    /// # Parameters
+    /// - `lockedAxes`: the bit mask of the locked axes: `1 << axis` for each locked `RawJointAxis`.
+    pub fn generic(
+        anchor1: &RawVector,
+        axes1: &RawRotation,
+        anchor2: &RawVector,
+        axes2: &RawRotation,
+        lockedAxes: u8,
+    ) -> RawGenericJoint {
+        let pos1 = Isometry::from_parts(anchor1.0.into(), axes1.0);
+        let pos2 = Isometry::from_parts(anchor2.0.into(), axes2.0);
+        Self(
+            GenericJointBuilder::new(JointAxesMask::from_bits_truncate(lockedAxes))
+                .local_frame1(pos1)
+                .local_frame2(pos2)
+                .build(),
+            None,
+        )
+    }
+
+    /// Enables and sets the limits of this joint along the given axis.
+    pub fn setLimits(&mut self, axis: RawJointAxis, min: f32, max: f32) {
+        self.0.set_limits(axis.into(), [min, max]);
+    }
+
+    /// Enables and configures the motor of this joint along the given axis.
+    pub fn setMotor(
+        &mut self,
+        axis: RawJointAxis,
+        targetPos: f32,
+        targetVel: f32,
+        stiffness: f32,
+        damping: f32,
+    ) {
+        self.0
+            .set_motor(axis.into(), targetPos, targetVel, stiffness, damping);
+    }
+
+    /// Sets the model of the motor of this joint along the given axis.
+    pub fn setMotorModel(&mut self, axis: RawJointAxis, model: RawMotorModel) {
+        self.0.motors[axis as usize].model = model.into();
+    }
+
+    /// Sets the maximum force the motor of this joint can deliver along the given axis.
+    pub fn setMotorMaxForce(&mut self, axis: RawJointAxis, maxForce: f32) {
+        self.0.motors[axis as usize].max_force = maxForce;
+    }
+
+    /// Sets whether contacts are enabled between the rigid-bodies attached by this joint.
+    pub fn setContactsEnabled(&mut self, enabled: bool) {
+        self.0.contacts_enabled = enabled;
+    }
 }
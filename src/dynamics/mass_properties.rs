@@ -0,0 +1,43 @@
+#[cfg(feature = "dim3")]
+use crate::math::RawRotation;
+use crate::math::RawVector;
+use rapier::dynamics::MassProperties;
+use wasm_bindgen::prelude::*;
+
+/// The mass, center-of-mass, and angular inertia of a rigid-body or a collider.
+#[wasm_bindgen]
+#[derive(Copy, Clone)]
+pub struct RawMassProperties(pub(crate) MassProperties);
+
+#[wasm_bindgen]
+impl RawMassProperties {
+    /// The mass.
+    pub fn mass(&self) -> f32 {
+        self.0.mass()
+    }
+
+    /// The center-of-mass, expressed in the local-space of the rigid-body or collider.
+    pub fn localCom(&self) -> RawVector {
+        RawVector(self.0.local_com.coords)
+    }
+
+    /// The principal angular inertia.
+    #[cfg(feature = "dim2")]
+    pub fn principalInertia(&self) -> f32 {
+        self.0.principal_inertia()
+    }
+
+    /// The principal angular inertia, i.e., the angular inertia along each axis of
+    /// `principalInertiaFrame`.
+    #[cfg(feature = "dim3")]
+    pub fn principalInertia(&self) -> RawVector {
+        RawVector(self.0.principal_inertia())
+    }
+
+    /// The orientation of the principal angular inertia axes, expressed in the local-space
+    /// of the rigid-body or collider.
+    #[cfg(feature = "dim3")]
+    pub fn principalInertiaFrame(&self) -> RawRotation {
+        RawRotation(self.0.principal_inertia_local_frame)
+    }
+}
@@ -3,9 +3,13 @@
 pub use self::ccd_solver::*;
 pub use self::impulse_joint_set::*;
 pub use self::integration_parameters::*;
+pub use self::inverse_kinematics::*;
 pub use self::island_manager::*;
 pub use self::joint::*;
+pub use self::mass_properties::*;
+pub(crate) use self::multibody_joint::joint_coordinates;
 pub use self::multibody_joint_set::*;
+pub use self::ragdoll::*;
 pub use self::rigid_body::*;
 pub use self::rigid_body_set::*;
 
@@ -13,9 +17,12 @@ mod ccd_solver;
 mod impulse_joint;
 mod impulse_joint_set;
 mod integration_parameters;
+mod inverse_kinematics;
 mod island_manager;
 mod joint;
+mod mass_properties;
 mod multibody_joint;
 mod multibody_joint_set;
+mod ragdoll;
 mod rigid_body;
 mod rigid_body_set;
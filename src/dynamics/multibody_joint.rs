@@ -1,7 +1,12 @@
-use crate::dynamics::{RawJointAxis, RawJointType, RawMultibodyJointSet};
+use crate::dynamics::{
+    link_dofs, RawJointAxis, RawJointType, RawMotorModel, RawMultibodyJointSet, RawRigidBodySet,
+};
 use crate::math::{RawRotation, RawVector};
-use crate::utils::FlatHandle;
-use rapier::dynamics::JointAxis;
+use crate::utils::{self, FlatHandle};
+use rapier::dynamics::{GenericJoint, JointAxis, Multibody, MultibodyJoint, RigidBodySet};
+#[cfg(feature = "dim3")]
+use rapier::math::Rotation;
+use rapier::math::{AngVector, Isometry, Point, Real, Vector, ANG_DIM, DIM};
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -11,15 +16,26 @@ impl RawMultibodyJointSet {
         self.map(handle, |j| j.data.locked_axes.into())
     }
 
-    // /// The unique integer identifier of the first rigid-body this joint it attached to.
-    // pub fn jointBodyHandle1(&self, handle: FlatHandle) -> u32 {
-    //     self.map(handle, |j| j.body1.into_raw_parts().0)
-    // }
-    //
-    // /// The unique integer identifier of the second rigid-body this joint is attached to.
-    // pub fn jointBodyHandle2(&self, handle: FlatHandle) -> u32 {
-    //     self.map(handle, |j| j.body2.into_raw_parts().0)
-    // }
+    /// The unique integer identifier of the first rigid-body this joint it attached to, i.e.,
+    /// the rigid-body of the parent link.
+    ///
+    /// Returns `undefined` if this is the joint of the root of a multibody.
+    pub fn jointBodyHandle1(&self, handle: FlatHandle) -> Option<FlatHandle> {
+        self.map_multibody(handle, |multibody, link_id| {
+            let parent_id = multibody.link(link_id)?.parent_id()?;
+            let parent = multibody.link(parent_id)?;
+            Some(utils::flat_handle(parent.rigid_body_handle().0))
+        })
+    }
+
+    /// The unique integer identifier of the second rigid-body this joint is attached to, i.e.,
+    /// the rigid-body of the child link.
+    pub fn jointBodyHandle2(&self, handle: FlatHandle) -> FlatHandle {
+        self.map_multibody(handle, |multibody, link_id| {
+            let link = multibody.link(link_id).unwrap();
+            utils::flat_handle(link.rigid_body_handle().0)
+        })
+    }
 
     /// The angular part of the joint’s local frame relative to the first rigid-body it is attached to.
     pub fn jointFrameX1(&self, handle: FlatHandle) -> RawRotation {
@@ -47,6 +63,44 @@ impl RawMultibodyJointSet {
         self.map(handle, |j| j.data.local_frame2.translation.vector.into())
     }
 
+    /// Sets the position of the first local anchor
+    pub fn jointSetAnchor1(&mut self, handle: FlatHandle, newPos: &RawVector) {
+        self.map_mut(handle, |j| {
+            j.data.set_local_anchor1(newPos.0.into());
+        });
+    }
+
+    /// Sets the position of the second local anchor
+    pub fn jointSetAnchor2(&mut self, handle: FlatHandle, newPos: &RawVector) {
+        self.map_mut(handle, |j| {
+            j.data.set_local_anchor2(newPos.0.into());
+        });
+    }
+
+    /// Sets the joint’s local frame relative to the first rigid-body it is attached to.
+    ///
+    /// # Parameters
+    /// - `anchor`: the new position of the first anchor.
+    /// - `axes`: the new orientation of the joint’s first local frame.
+    pub fn jointSetFrame1(&mut self, handle: FlatHandle, anchor: &RawVector, axes: &RawRotation) {
+        self.map_mut(handle, |j| {
+            j.data
+                .set_local_frame1(Isometry::from_parts(anchor.0.into(), axes.0));
+        });
+    }
+
+    /// Sets the joint’s local frame relative to the second rigid-body it is attached to.
+    ///
+    /// # Parameters
+    /// - `anchor`: the new position of the second anchor.
+    /// - `axes`: the new orientation of the joint’s second local frame.
+    pub fn jointSetFrame2(&mut self, handle: FlatHandle, anchor: &RawVector, axes: &RawRotation) {
+        self.map_mut(handle, |j| {
+            j.data
+                .set_local_frame2(Isometry::from_parts(anchor.0.into(), axes.0));
+        });
+    }
+
     /// Are contacts between the rigid-bodies attached by this joint enabled?
     pub fn jointContactsEnabled(&self, handle: FlatHandle) -> bool {
         self.map(handle, |j| j.data.contacts_enabled)
@@ -59,6 +113,161 @@ impl RawMultibodyJointSet {
         });
     }
 
+    /// The index of the link attached to its parent by this joint, in its multibody.
+    ///
+    /// The root of a multibody has the index 0, and the parent of a link always has a smaller
+    /// index than the link itself.
+    pub fn jointLinkId(&self, handle: FlatHandle) -> usize {
+        self.map_multibody(handle, |_, link_id| link_id)
+    }
+
+    /// The number of degrees of freedom of this joint, i.e., the number of its generalized
+    /// coordinates.
+    pub fn jointNumDofs(&self, handle: FlatHandle) -> usize {
+        self.map(handle, |j| j.ndofs())
+    }
+
+    /// The generalized coordinates of this joint.
+    ///
+    /// There is one coordinate per free axis of the joint: the free linear axes come first,
+    /// followed by the free angular axes, each in the order of `RawJointAxis`. If all the
+    /// angular axes are free, the angular coordinates form the rotation vector of the joint.
+    pub fn jointPositions(&self, handle: FlatHandle) -> Vec<f32> {
+        self.map(handle, |j| joint_coordinates(j))
+    }
+
+    /// Sets the generalized coordinates of this joint.
+    ///
+    /// The rigid-bodies of the link attached by this joint and of all its descendants are
+    /// teleported accordingly.
+    ///
+    /// # Parameters
+    /// - `bodies`: the set of rigid-bodies attached by the joints of this multibody.
+    /// - `positions`: the new generalized coordinates of this joint, in the same order as
+    ///   `jointPositions`. Missing coordinates are left unchanged.
+    pub fn jointSetPositions(
+        &mut self,
+        handle: FlatHandle,
+        bodies: &mut RawRigidBodySet,
+        positions: Vec<f32>,
+    ) {
+        self.map_multibody_mut(handle, |multibody, link_id| {
+            let joint = &mut multibody.link_mut(link_id).unwrap().joint;
+            let disp = joint_displacement(joint, &positions);
+
+            if !disp.is_empty() {
+                joint.apply_displacement(&disp);
+                update_link_positions(multibody, link_id, &mut bodies.0);
+            }
+        })
+    }
+
+    /// The generalized velocities of this joint, in the same order as `jointPositions`.
+    pub fn jointVelocities(&self, handle: FlatHandle) -> Vec<f32> {
+        self.map_multibody(handle, |multibody, link_id| {
+            let velocities = multibody.generalized_velocity();
+            link_dofs(multibody, link_id)
+                .map(|i| velocities[i])
+                .collect()
+        })
+    }
+
+    /// Sets the generalized velocities of this joint.
+    ///
+    /// # Parameters
+    /// - `bodies`: the set of rigid-bodies attached by the joints of this multibody.
+    /// - `velocities`: the new generalized velocities of this joint, in the same order as
+    ///   `jointPositions`. Missing velocities are left unchanged.
+    pub fn jointSetVelocities(
+        &mut self,
+        handle: FlatHandle,
+        bodies: &mut RawRigidBodySet,
+        velocities: Vec<f32>,
+    ) {
+        self.map_multibody_mut(handle, |multibody, link_id| {
+            let dofs = link_dofs(multibody, link_id);
+            let mut generalized_velocity = multibody.generalized_velocity_mut();
+
+            for (i, vel) in dofs.zip(velocities.iter()) {
+                generalized_velocity[i] = *vel;
+            }
+
+            wake_up_links(multibody, &mut bodies.0);
+        })
+    }
+
+    /// Adds generalized forces along the degrees of freedom of this joint.
+    ///
+    /// Each generalized force is converted into a pair of opposite forces (for linear axes)
+    /// or torques (for angular axes) applied to the two rigid-bodies attached by this joint.
+    /// Just like the forces added to rigid-bodies, they are applied at each timestep until the
+    /// user forces of these rigid-bodies are reset.
+    ///
+    /// # Parameters
+    /// - `bodies`: the set of rigid-bodies attached by the joints of this multibody.
+    /// - `forces`: the generalized forces, in the same order as `jointPositions`.
+    pub fn jointAddGeneralizedForces(
+        &self,
+        handle: FlatHandle,
+        bodies: &mut RawRigidBodySet,
+        forces: Vec<f32>,
+    ) {
+        self.map_multibody(handle, |multibody, link_id| {
+            let link = multibody.link(link_id).unwrap();
+            let data = &link.joint().data;
+            let body2 = link.rigid_body_handle();
+            let body1 = link
+                .parent_id()
+                .map(|parent_id| multibody.link(parent_id).unwrap().rigid_body_handle());
+
+            let pos2 = match bodies.0.get(body2) {
+                Some(rb) => *rb.position(),
+                None => return,
+            };
+            let pos1 = body1
+                .and_then(|h| bodies.0.get(h))
+                .map(|rb| *rb.position())
+                .unwrap_or_else(Isometry::identity);
+
+            // The generalized coordinates are expressed in the first local frame of the joint.
+            let frame1 = pos1 * data.local_frame1;
+            let anchor2 = pos2 * Point::from(data.local_frame2.translation.vector);
+            let locked_bits = data.locked_axes.bits();
+            let mut forces = forces.iter().copied();
+            let mut force = Vector::zeros();
+            let mut torque: AngVector<Real> = na::zero();
+
+            for i in 0..DIM {
+                if locked_bits & (1 << i) == 0 {
+                    if let Some(f) = forces.next() {
+                        force += frame1.rotation * Vector::ith(i, f);
+                    }
+                }
+            }
+
+            for i in 0..ANG_DIM {
+                if locked_bits & (1 << (DIM + i)) == 0 {
+                    if let Some(t) = forces.next() {
+                        #[cfg(feature = "dim2")]
+                        {
+                            torque += t;
+                        }
+                        #[cfg(feature = "dim3")]
+                        {
+                            torque += frame1.rotation * Vector::ith(i, t);
+                        }
+                    }
+                }
+            }
+
+            bodies.add_user_wrench(body2, force, anchor2, torque, true);
+
+            if let Some(body1) = body1 {
+                bodies.add_user_wrench(body1, -force, anchor2, -torque, true);
+            }
+        })
+    }
+
     /// Are the limits for this joint enabled?
     pub fn jointLimitsEnabled(&self, handle: FlatHandle, axis: RawJointAxis) -> bool {
         self.map(handle, |j| {
@@ -76,16 +285,67 @@ impl RawMultibodyJointSet {
         self.map(handle, |j| j.data.limits[axis as usize].max)
     }
 
-    // pub fn jointConfigureMotorModel(
-    //     &mut self,
-    //     handle: FlatHandle,
-    //     axis: RawJointAxis,
-    //     model: RawMotorModel,
-    // ) {
-    //     self.map_mut(handle, |j| {
-    //         j.data.motors[axis as usize].model = model.into()
-    //     })
-    // }
+    /// Enables and sets the joint limits
+    pub fn jointSetLimits(&mut self, handle: FlatHandle, axis: RawJointAxis, min: f32, max: f32) {
+        self.map_mut(handle, |j| {
+            j.data.set_limits(axis.into(), [min, max]);
+        });
+    }
+
+    /// Is the motor of this joint along the given axis enabled?
+    pub fn jointMotorEnabled(&self, handle: FlatHandle, axis: RawJointAxis) -> bool {
+        self.map(handle, |j| {
+            j.data.motor_axes.contains(JointAxis::from(axis).into())
+        })
+    }
+
+    /// The model of the motor of this joint along the given axis.
+    pub fn jointMotorModel(&self, handle: FlatHandle, axis: RawJointAxis) -> RawMotorModel {
+        self.map(handle, |j| j.data.motors[axis as usize].model.into())
+    }
+
+    /// The target position of the motor of this joint along the given axis.
+    pub fn jointMotorTargetPos(&self, handle: FlatHandle, axis: RawJointAxis) -> f32 {
+        self.map(handle, |j| j.data.motors[axis as usize].target_pos)
+    }
+
+    /// The target velocity of the motor of this joint along the given axis.
+    pub fn jointMotorTargetVel(&self, handle: FlatHandle, axis: RawJointAxis) -> f32 {
+        self.map(handle, |j| j.data.motors[axis as usize].target_vel)
+    }
+
+    /// The stiffness of the motor of this joint along the given axis.
+    pub fn jointMotorStiffness(&self, handle: FlatHandle, axis: RawJointAxis) -> f32 {
+        self.map(handle, |j| j.data.motors[axis as usize].stiffness)
+    }
+
+    /// The damping of the motor of this joint along the given axis.
+    pub fn jointMotorDamping(&self, handle: FlatHandle, axis: RawJointAxis) -> f32 {
+        self.map(handle, |j| j.data.motors[axis as usize].damping)
+    }
+
+    /// The maximum force the motor of this joint can deliver along the given axis.
+    pub fn jointMotorMaxForce(&self, handle: FlatHandle, axis: RawJointAxis) -> f32 {
+        self.map(handle, |j| j.data.motors[axis as usize].max_force)
+    }
+
+    /// Sets the maximum force the motor of this joint can deliver along the given axis.
+    pub fn jointSetMotorMaxForce(&mut self, handle: FlatHandle, axis: RawJointAxis, maxForce: f32) {
+        self.map_mut(handle, |j| {
+            j.data.motors[axis as usize].max_force = maxForce;
+        })
+    }
+
+    pub fn jointConfigureMotorModel(
+        &mut self,
+        handle: FlatHandle,
+        axis: RawJointAxis,
+        model: RawMotorModel,
+    ) {
+        self.map_mut(handle, |j| {
+            j.data.motors[axis as usize].model = model.into()
+        })
+    }
 
     /*
     #[cfg(feature = "dim3")]
@@ -156,41 +416,161 @@ impl RawMultibodyJointSet {
     }
     */
 
-    // pub fn jointConfigureMotorVelocity(
-    //     &mut self,
-    //     handle: FlatHandle,
-    //     axis: RawJointAxis,
-    //     targetVel: f32,
-    //     factor: f32,
-    // ) {
-    //     self.jointConfigureMotor(handle, axis, 0.0, targetVel, 0.0, factor)
-    // }
-    //
-    // pub fn jointConfigureMotorPosition(
-    //     &mut self,
-    //     handle: FlatHandle,
-    //     axis: RawJointAxis,
-    //     targetPos: f32,
-    //     stiffness: f32,
-    //     damping: f32,
-    // ) {
-    //     self.jointConfigureMotor(handle, axis, targetPos, 0.0, stiffness, damping)
-    // }
-
-    // pub fn jointConfigureMotor(
-    //     &mut self,
-    //     handle: FlatHandle,
-    //     axis: RawJointAxis,
-    //     targetPos: f32,
-    //     targetVel: f32,
-    //     stiffness: f32,
-    //     damping: f32,
-    // ) {
-    //     self.map_mut(handle, |j| {
-    //         j.data.motors[axis as usize].target_pos = targetPos;
-    //         j.data.motors[axis as usize].target_vel = targetVel;
-    //         j.data.motors[axis as usize].stiffness = stiffness;
-    //         j.data.motors[axis as usize].damping = damping;
-    //     })
-    // }
+    pub fn jointConfigureMotorVelocity(
+        &mut self,
+        handle: FlatHandle,
+        axis: RawJointAxis,
+        targetVel: f32,
+        factor: f32,
+    ) {
+        self.jointConfigureMotor(handle, axis, 0.0, targetVel, 0.0, factor)
+    }
+
+    pub fn jointConfigureMotorPosition(
+        &mut self,
+        handle: FlatHandle,
+        axis: RawJointAxis,
+        targetPos: f32,
+        stiffness: f32,
+        damping: f32,
+    ) {
+        self.jointConfigureMotor(handle, axis, targetPos, 0.0, stiffness, damping)
+    }
+
+    pub fn jointConfigureMotor(
+        &mut self,
+        handle: FlatHandle,
+        axis: RawJointAxis,
+        targetPos: f32,
+        targetVel: f32,
+        stiffness: f32,
+        damping: f32,
+    ) {
+        self.map_mut(handle, |j| {
+            j.data
+                .set_motor(axis.into(), targetPos, targetVel, stiffness, damping);
+        })
+    }
+}
+
+/// The generalized coordinates of a multibody joint, computed from its current pose.
+pub(crate) fn joint_coordinates(joint: &MultibodyJoint) -> Vec<Real> {
+    let data = &joint.data;
+    let pos = data.local_frame1.inverse() * joint.body_to_parent() * data.local_frame2;
+    let angles = pos.rotation.scaled_axis();
+    let locked_bits = data.locked_axes.bits();
+
+    let lin_coords = (0..DIM)
+        .filter(|i| locked_bits & (1 << i) == 0)
+        .map(|i| pos.translation.vector[i]);
+    let ang_coords = (0..ANG_DIM)
+        .filter(|i| locked_bits & (1 << (DIM + i)) == 0)
+        .map(|i| angles[i]);
+    lin_coords.chain(ang_coords).collect()
+}
+
+/// The pose of the rigid-body of the child link of a multibody joint relative to the rigid-body
+/// of its parent link, if the generalized coordinates of the joint were equal to `coords`.
+pub(crate) fn joint_pose(data: &GenericJoint, coords: &[Real]) -> Isometry<Real> {
+    let locked_bits = data.locked_axes.bits();
+    let mut coords = coords.iter().copied();
+    let mut translation = Vector::zeros();
+    let mut angles: AngVector<Real> = na::zero();
+
+    for i in 0..DIM {
+        if locked_bits & (1 << i) == 0 {
+            translation[i] = coords.next().unwrap_or(0.0);
+        }
+    }
+
+    for i in 0..ANG_DIM {
+        if locked_bits & (1 << (DIM + i)) == 0 {
+            #[cfg(feature = "dim2")]
+            {
+                angles = coords.next().unwrap_or(0.0);
+            }
+            #[cfg(feature = "dim3")]
+            {
+                angles[i] = coords.next().unwrap_or(0.0);
+            }
+        }
+    }
+
+    data.local_frame1 * Isometry::new(translation, angles) * data.local_frame2.inverse()
+}
+
+/// The displacement to apply to the generalized coordinates of a multibody joint so they
+/// become equal to `target`.
+pub(crate) fn joint_displacement(joint: &MultibodyJoint, target: &[Real]) -> Vec<Real> {
+    let current = joint_coordinates(joint);
+    #[allow(unused_mut)]
+    let mut disp: Vec<Real> = current
+        .iter()
+        .enumerate()
+        .map(|(i, c)| target.get(i).map(|t| t - c).unwrap_or(0.0))
+        .collect();
+
+    // With three angular degrees of freedom, the joint rotation is displaced by composing
+    // rotations, not by adding angles.
+    #[cfg(feature = "dim3")]
+    {
+        let locked_ang_bits = joint.data.locked_axes.bits() >> DIM;
+        let n = current.len();
+
+        if locked_ang_bits.count_ones() == 0 && target.len() >= n {
+            let rotation = |coords: &[Real]| Rotation::new(Vector::from_column_slice(coords));
+            let delta =
+                rotation(&target[n - ANG_DIM..n]) * rotation(&current[n - ANG_DIM..]).inverse();
+            disp[n - ANG_DIM..].copy_from_slice(delta.scaled_axis().as_slice());
+        }
+    }
+
+    disp
+}
+
+/// Teleports the rigid-bodies of the given link and of all its descendants so they match the
+/// generalized coordinates of their joints.
+pub(crate) fn update_link_positions(
+    multibody: &Multibody,
+    link_id: usize,
+    bodies: &mut RigidBodySet,
+) {
+    let mut poses: Vec<Option<Isometry<Real>>> = vec![None; multibody.num_links()];
+
+    // The parent of a link always has a smaller index than the link itself.
+    for i in link_id..multibody.num_links() {
+        let link = multibody.link(i).unwrap();
+        let parent_pose = match link.parent_id() {
+            None => Isometry::identity(),
+            Some(parent_id) if i == link_id => {
+                let parent = multibody.link(parent_id).unwrap();
+                bodies
+                    .get(parent.rigid_body_handle())
+                    .map(|rb| *rb.position())
+                    .unwrap_or_else(Isometry::identity)
+            }
+            Some(parent_id) => match poses[parent_id] {
+                Some(pose) => pose,
+                // This link isn't a descendant of the moved link.
+                None => continue,
+            },
+        };
+
+        let pose = parent_pose * link.joint().body_to_parent();
+        poses[i] = Some(pose);
+
+        if let Some(rb) = bodies.get_mut(link.rigid_body_handle()) {
+            rb.set_position(pose, true);
+        }
+    }
+}
+
+fn wake_up_links(multibody: &Multibody, bodies: &mut RigidBodySet) {
+    for i in 0..multibody.num_links() {
+        let handle = multibody.link(i).unwrap().rigid_body_handle();
+
+        if let Some(rb) = bodies.get_mut(handle) {
+            rb.wake_up(true);
+        }
+    }
 }
@@ -1,6 +1,7 @@
 use crate::dynamics::RawGenericJoint;
 use crate::utils::{self, FlatHandle};
-use rapier::dynamics::{MultibodyJoint, MultibodyJointSet};
+use rapier::dynamics::{Multibody, MultibodyJoint, MultibodyJointSet};
+use std::ops::Range;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -26,6 +27,41 @@ impl RawMultibodyJointSet {
             .expect("Invalid Joint reference. It may have been removed from the physics World.");
         f(&mut body.link_mut(link_id).unwrap().joint)
     }
+
+    /// Applies `f` to the multibody containing the given joint, and to the index of the
+    /// link attached to its parent by this joint.
+    pub(crate) fn map_multibody<T>(
+        &self,
+        handle: FlatHandle,
+        f: impl FnOnce(&Multibody, usize) -> T,
+    ) -> T {
+        let (body, link_id) = self
+            .0
+            .get(utils::multibody_joint_handle(handle))
+            .expect("Invalid Joint reference. It may have been removed from the physics World.");
+        f(body, link_id)
+    }
+
+    pub(crate) fn map_multibody_mut<T>(
+        &mut self,
+        handle: FlatHandle,
+        f: impl FnOnce(&mut Multibody, usize) -> T,
+    ) -> T {
+        let (body, link_id) = self
+            .0
+            .get_mut(utils::multibody_joint_handle(handle))
+            .expect("Invalid Joint reference. It may have been removed from the physics World.");
+        f(body, link_id)
+    }
+}
+
+/// The range of the degrees of freedom of the given link in the generalized coordinates
+/// of its multibody.
+pub(crate) fn link_dofs(multibody: &Multibody, link_id: usize) -> Range<usize> {
+    let start: usize = (0..link_id)
+        .map(|i| multibody.link(i).unwrap().joint().ndofs())
+        .sum();
+    start..start + multibody.link(link_id).unwrap().joint().ndofs()
 }
 
 #[wasm_bindgen]
@@ -35,21 +71,33 @@ impl RawMultibodyJointSet {
         RawMultibodyJointSet(MultibodyJointSet::new())
     }
 
+    /// Inserts a new multibody joint.
+    ///
+    /// Throws an error if `params` describes a rope or spring joint: these joints are only
+    /// enforced as impulse joints.
     pub fn createJoint(
         &mut self,
         params: &RawGenericJoint,
         parent1: FlatHandle,
         parent2: FlatHandle,
         wakeUp: bool,
-    ) -> FlatHandle {
+    ) -> Result<FlatHandle, JsValue> {
         // TODO: avoid the unwrap?
         let parent1 = utils::body_handle(parent1);
         let parent2 = utils::body_handle(parent2);
 
-        self.0
-            .insert(parent1, parent2, params.0.clone(), wakeUp)
+        if params.1.is_some() {
+            return Err(js_sys::Error::new(
+                "rope and spring joints can only be created as impulse joints",
+            )
+            .into());
+        }
+
+        Ok(self
+            .0
+            .insert(parent1, parent2, params.0, wakeUp)
             .map(|h| utils::flat_handle(h.0))
-            .unwrap_or(FlatHandle::MAX)
+            .unwrap_or(FlatHandle::MAX))
     }
 
     pub fn remove(&mut self, handle: FlatHandle, wakeUp: bool) {
@@ -72,6 +120,36 @@ impl RawMultibodyJointSet {
         }
     }
 
+    /// The integer handle of the multibody joint attaching the given rigid-body to its parent link.
+    ///
+    /// Returns `undefined` if the rigid-body isn't part of any multibody. The root of a multibody
+    /// has a joint too, attaching it to the ground.
+    pub fn jointForRigidBody(&self, body: FlatHandle) -> Option<FlatHandle> {
+        // The handle of a multibody joint is the handle of its child rigid-body.
+        self.0
+            .get(utils::multibody_joint_handle(body))
+            .map(|_| body)
+    }
+
+    /// Applies the given JavaScript function to the integer handle of each link of the multibody
+    /// containing the given joint.
+    ///
+    /// The links are visited in increasing link index order, so the parent of a link is always
+    /// visited before that link. Each link is identified by the handle of the joint attaching it
+    /// to its parent, which is also the handle of its rigid-body.
+    ///
+    /// # Parameters
+    /// - `f(handle)`: the function to apply to the integer handle of each link of the multibody.
+    pub fn forEachMultibodyLink(&self, handle: FlatHandle, f: &js_sys::Function) {
+        let this = JsValue::null();
+        self.map_multibody(handle, |multibody, _| {
+            for i in 0..multibody.num_links() {
+                let body = multibody.link(i).unwrap().rigid_body_handle();
+                let _ = f.call1(&this, &JsValue::from(utils::flat_handle(body.0)));
+            }
+        })
+    }
+
     /// Applies the given JavaScript function to the integer handle of each joint attached to the given rigid-body.
     ///
     /// # Parameters
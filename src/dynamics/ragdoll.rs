@@ -0,0 +1,380 @@
+use crate::dynamics::{RawImpulseJointSet, RawMultibodyJointSet, RawRigidBodySet};
+use crate::geometry::RawColliderSet;
+use crate::math::RawVector;
+use crate::utils::{self, FlatHandle};
+use rapier::dynamics::{
+    GenericJoint, GenericJointBuilder, JointAxesMask, JointAxis, RigidBodyBuilder, RigidBodyType,
+};
+use rapier::geometry::{ColliderBuilder, SharedShape};
+use rapier::math::{Isometry, Point, Real, Rotation, Vector};
+#[cfg(feature = "dim3")]
+use std::f32::consts::PI;
+use wasm_bindgen::prelude::*;
+
+enum RagdollJoint {
+    Root,
+    #[cfg(feature = "dim3")]
+    Spherical {
+        swing: Real,
+        twist: [Real; 2],
+    },
+    Revolute {
+        #[cfg(feature = "dim3")]
+        axis: Vector<Real>,
+        limits: [Real; 2],
+    },
+}
+
+struct RagdollBone {
+    parent: Option<usize>,
+    start: Point<Real>,
+    end: Point<Real>,
+    radius: Real,
+    joint: RagdollJoint,
+}
+
+impl RagdollBone {
+    /// The world-space pose of the rigid-body of this bone, centered on the bone with its local
+    /// `y` axis pointing from the start to the end of the bone.
+    fn pose(&self) -> Isometry<Real> {
+        let center = na::center(&self.start, &self.end);
+        let dir = self.end - self.start;
+
+        if dir.norm() <= Real::EPSILON {
+            return Isometry::from_parts(center.coords.into(), Rotation::identity());
+        }
+
+        #[cfg(feature = "dim2")]
+        let rotation = Rotation::rotation_between(&Vector::y(), &dir);
+        #[cfg(feature = "dim3")]
+        let rotation = Rotation::rotation_between(&Vector::y(), &dir)
+            .unwrap_or_else(|| Rotation::from_axis_angle(&Vector::x_axis(), PI));
+
+        Isometry::from_parts(center.coords.into(), rotation)
+    }
+
+    /// The world-space frame of the joint attaching this bone to its parent, at the rest pose.
+    fn joint_frame(&self) -> Isometry<Real> {
+        #[cfg(feature = "dim2")]
+        let rotation = Rotation::identity();
+        #[cfg(feature = "dim3")]
+        let rotation = {
+            // The `x` axis of the joint frame is the twist axis of spherical joints, or the
+            // rotation axis of revolute joints.
+            let x = match &self.joint {
+                RagdollJoint::Revolute { axis, .. } => *axis,
+                _ => self.end - self.start,
+            };
+            Rotation::rotation_between(&Vector::x(), &x).unwrap_or_else(|| {
+                if x.dot(&Vector::x()) < 0.0 {
+                    Rotation::from_axis_angle(&Vector::y_axis(), PI)
+                } else {
+                    Rotation::identity()
+                }
+            })
+        };
+
+        Isometry::from_parts(self.start.coords.into(), rotation)
+    }
+
+    /// The joint attaching this bone to its parent, with frames expressed relative to the
+    /// rigid-bodies of the parent and of this bone.
+    fn generic_joint(&self, parent_pose: &Isometry<Real>) -> Option<GenericJoint> {
+        let (locked_axes, limits) = match self.joint {
+            RagdollJoint::Root => return None,
+            #[cfg(feature = "dim3")]
+            RagdollJoint::Spherical { swing, twist } => {
+                let mut limits = vec![(JointAxis::AngX, twist)];
+                if swing < PI {
+                    limits.push((JointAxis::AngY, [-swing, swing]));
+                    limits.push((JointAxis::AngZ, [-swing, swing]));
+                }
+                (
+                    JointAxesMask::X | JointAxesMask::Y | JointAxesMask::Z,
+                    limits,
+                )
+            }
+            #[cfg(feature = "dim2")]
+            RagdollJoint::Revolute { limits } => (
+                JointAxesMask::X | JointAxesMask::Y,
+                vec![(JointAxis::AngX, limits)],
+            ),
+            #[cfg(feature = "dim3")]
+            RagdollJoint::Revolute { limits, .. } => (
+                JointAxesMask::X
+                    | JointAxesMask::Y
+                    | JointAxesMask::Z
+                    | JointAxesMask::ANG_Y
+                    | JointAxesMask::ANG_Z,
+                vec![(JointAxis::AngX, limits)],
+            ),
+        };
+
+        let frame = self.joint_frame();
+        let mut joint = GenericJointBuilder::new(locked_axes).build();
+        joint.set_local_frame1(parent_pose.inv_mul(&frame));
+        joint.set_local_frame2(self.pose().inv_mul(&frame));
+        // Adjacent bones don’t collide with each other.
+        joint.contacts_enabled = false;
+
+        for (axis, limits) in limits {
+            joint.set_limits(axis, limits);
+        }
+
+        Some(joint)
+    }
+}
+
+/// A description of the skeleton of a ragdoll, used to create its rigid-bodies, colliders,
+/// and joints.
+///
+/// Bones are given in world-space, at the rest pose of the ragdoll. The joint limits are
+/// expressed relative to this rest pose.
+#[wasm_bindgen]
+pub struct RawRagdollBuilder {
+    bones: Vec<RagdollBone>,
+}
+
+impl Default for RawRagdollBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RawRagdollBuilder {
+    fn add_bone(
+        &mut self,
+        parent: Option<u32>,
+        start: &RawVector,
+        end: &RawVector,
+        radius: f32,
+        joint: RagdollJoint,
+    ) -> Option<u32> {
+        let parent = match parent {
+            Some(parent) if parent as usize >= self.bones.len() => return None,
+            parent => parent.map(|p| p as usize),
+        };
+
+        self.bones.push(RagdollBone {
+            parent,
+            start: start.0.into(),
+            end: end.0.into(),
+            radius,
+            joint,
+        });
+        Some(self.bones.len() as u32 - 1)
+    }
+}
+
+#[wasm_bindgen]
+impl RawRagdollBuilder {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        RawRagdollBuilder { bones: vec![] }
+    }
+
+    /// The number of bones added to this ragdoll.
+    pub fn numBones(&self) -> usize {
+        self.bones.len()
+    }
+
+    /// Adds a bone that isn’t attached to any parent bone.
+    ///
+    /// Returns the index of the new bone.
+    ///
+    /// # Parameters
+    /// - `start`, `end`: the world-space extremities of the bone.
+    /// - `radius`: the radius of the capsule collider of the bone.
+    pub fn addRootBone(&mut self, start: &RawVector, end: &RawVector, radius: f32) -> u32 {
+        self.add_bone(None, start, end, radius, RagdollJoint::Root)
+            .unwrap()
+    }
+
+    /// Adds a bone attached to its parent by a spherical joint located at the start of the bone.
+    ///
+    /// Returns the index of the new bone, or `undefined` if `parent` isn’t a valid bone index.
+    ///
+    /// # Parameters
+    /// - `parent`: the index of the parent bone.
+    /// - `start`, `end`: the world-space extremities of the bone.
+    /// - `radius`: the radius of the capsule collider of the bone.
+    /// - `swingLimit`: the maximum rotation of the bone, relative to its parent, around each of
+    ///   the two axes orthogonal to its rest direction. These two rotations are limited
+    ///   independently, so a diagonal swing can exceed `swingLimit`. The swing isn’t limited
+    ///   if this is greater than or equal to π.
+    /// - `twistMin`, `twistMax`: the limits of the rotation of the bone around its own axis.
+    #[cfg(feature = "dim3")]
+    pub fn addSphericalBone(
+        &mut self,
+        parent: u32,
+        start: &RawVector,
+        end: &RawVector,
+        radius: f32,
+        swingLimit: f32,
+        twistMin: f32,
+        twistMax: f32,
+    ) -> Option<u32> {
+        let joint = RagdollJoint::Spherical {
+            swing: swingLimit,
+            twist: [twistMin, twistMax],
+        };
+        self.add_bone(Some(parent), start, end, radius, joint)
+    }
+
+    /// Adds a bone attached to its parent by a revolute joint located at the start of the bone.
+    ///
+    /// Returns the index of the new bone, or `undefined` if `parent` isn’t a valid bone index.
+    ///
+    /// # Parameters
+    /// - `parent`: the index of the parent bone.
+    /// - `start`, `end`: the world-space extremities of the bone.
+    /// - `radius`: the radius of the capsule collider of the bone.
+    /// - `min`, `max`: the limits of the angle of the bone relative to its rest pose.
+    #[cfg(feature = "dim2")]
+    pub fn addRevoluteBone(
+        &mut self,
+        parent: u32,
+        start: &RawVector,
+        end: &RawVector,
+        radius: f32,
+        min: f32,
+        max: f32,
+    ) -> Option<u32> {
+        let joint = RagdollJoint::Revolute { limits: [min, max] };
+        self.add_bone(Some(parent), start, end, radius, joint)
+    }
+
+    /// Adds a bone attached to its parent by a revolute joint located at the start of the bone.
+    ///
+    /// Returns the index of the new bone, or `undefined` if `parent` isn’t a valid bone index.
+    ///
+    /// # Parameters
+    /// - `parent`: the index of the parent bone.
+    /// - `start`, `end`: the world-space extremities of the bone.
+    /// - `radius`: the radius of the capsule collider of the bone.
+    /// - `axis`: the world-space rotation axis of the joint.
+    /// - `min`, `max`: the limits of the angle of the bone relative to its rest pose.
+    #[cfg(feature = "dim3")]
+    pub fn addRevoluteBone(
+        &mut self,
+        parent: u32,
+        start: &RawVector,
+        end: &RawVector,
+        radius: f32,
+        axis: &RawVector,
+        min: f32,
+        max: f32,
+    ) -> Option<u32> {
+        let joint = RagdollJoint::Revolute {
+            axis: axis.0,
+            limits: [min, max],
+        };
+        self.add_bone(Some(parent), start, end, radius, joint)
+    }
+
+    /// Creates the rigid-bodies, colliders, and joints of the ragdoll.
+    ///
+    /// Each bone is converted into a dynamic rigid-body with a capsule collider. The joints
+    /// between adjacent bones disable the contacts between them, so the collision groups of the
+    /// colliders are left to their default value.
+    ///
+    /// # Parameters
+    /// - `density`: the density of the colliders.
+    /// - `multibody`: if `true`, the joints are inserted into `multibodyJoints` instead of
+    ///   `impulseJoints`.
+    pub fn build(
+        &self,
+        bodies: &mut RawRigidBodySet,
+        colliders: &mut RawColliderSet,
+        impulseJoints: &mut RawImpulseJointSet,
+        multibodyJoints: &mut RawMultibodyJointSet,
+        density: f32,
+        multibody: bool,
+    ) -> RawRagdoll {
+        let mut ragdoll = RawRagdoll {
+            bodies: vec![],
+            colliders: vec![],
+            joints: vec![],
+            multibody,
+        };
+        let mut body_handles = vec![];
+
+        for bone in &self.bones {
+            let rb = RigidBodyBuilder::new(RigidBodyType::Dynamic)
+                .position(bone.pose())
+                .build();
+            let body_handle = bodies.0.insert(rb);
+            let half_height = na::distance(&bone.start, &bone.end) / 2.0;
+            let co = ColliderBuilder::new(SharedShape::capsule_y(half_height, bone.radius))
+                .density(density)
+                .build();
+            let co_handle = colliders
+                .0
+                .insert_with_parent(co, body_handle, &mut bodies.0);
+
+            body_handles.push(body_handle);
+            ragdoll.bodies.push(utils::flat_handle(body_handle.0));
+            ragdoll.colliders.push(utils::flat_handle(co_handle.0));
+        }
+
+        for (bone, body_handle) in self.bones.iter().zip(body_handles.iter()) {
+            let joint_handle = bone.parent.and_then(|parent| {
+                let joint = bone.generic_joint(&self.bones[parent].pose())?;
+                let parent_handle = body_handles[parent];
+
+                if multibody {
+                    multibodyJoints
+                        .0
+                        .insert(parent_handle, *body_handle, joint, true)
+                        .map(|h| utils::flat_handle(h.0))
+                } else {
+                    let h = impulseJoints
+                        .0
+                        .insert(parent_handle, *body_handle, joint, true);
+                    Some(utils::flat_handle(h.0))
+                }
+            });
+            ragdoll.joints.push(joint_handle);
+        }
+
+        ragdoll
+    }
+}
+
+/// The rigid-bodies, colliders, and joints created for each bone of a ragdoll.
+#[wasm_bindgen]
+pub struct RawRagdoll {
+    bodies: Vec<FlatHandle>,
+    colliders: Vec<FlatHandle>,
+    joints: Vec<Option<FlatHandle>>,
+    multibody: bool,
+}
+
+#[wasm_bindgen]
+impl RawRagdoll {
+    /// Are the joints of this ragdoll multibody joints?
+    pub fn isMultibody(&self) -> bool {
+        self.multibody
+    }
+
+    /// The number of bones of this ragdoll.
+    pub fn numBones(&self) -> usize {
+        self.bodies.len()
+    }
+
+    /// The handle of the rigid-body of the `i`-th bone.
+    pub fn body(&self, i: usize) -> Option<FlatHandle> {
+        self.bodies.get(i).copied()
+    }
+
+    /// The handle of the collider of the `i`-th bone.
+    pub fn collider(&self, i: usize) -> Option<FlatHandle> {
+        self.colliders.get(i).copied()
+    }
+
+    /// The handle of the joint attaching the `i`-th bone to its parent, or `undefined` for
+    /// root bones.
+    pub fn joint(&self, i: usize) -> Option<FlatHandle> {
+        self.joints.get(i).copied().flatten()
+    }
+}
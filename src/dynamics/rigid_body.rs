@@ -1,10 +1,62 @@
-use crate::dynamics::{RawRigidBodySet, RawRigidBodyType};
+use crate::dynamics::{RawMassProperties, RawRigidBodySet, RawRigidBodyType};
 use crate::geometry::RawColliderSet;
+#[cfg(feature = "dim3")]
+use crate::math::RawSdpMatrix3;
 use crate::math::{RawRotation, RawVector};
 use crate::utils::{self, FlatHandle};
-use rapier::dynamics::MassProperties;
+use rapier::dynamics::{MassProperties, RigidBody};
+#[cfg(feature = "dim3")]
+use rapier::math::Matrix;
+use rapier::math::{Point, Real, Vector};
 use wasm_bindgen::prelude::*;
 
+#[cfg(feature = "dim2")]
+fn world_angular_inertia(rb: &RigidBody) -> Real {
+    rb.mass_properties().principal_inertia()
+}
+
+#[cfg(feature = "dim3")]
+fn world_angular_inertia(rb: &RigidBody) -> Matrix<Real> {
+    let rot = rb.position().rotation.to_rotation_matrix().into_inner();
+    rot * rb.mass_properties().reconstruct_inertia_matrix() * rot.transpose()
+}
+
+/// The inverse of the effective mass of a rigid-body along `dir`, at the world-space `point`.
+///
+/// This is zero if the rigid-body isn’t dynamic.
+#[cfg(feature = "dim2")]
+pub(crate) fn inv_effective_mass(rb: &RigidBody, point: &Point<Real>, dir: &Vector<Real>) -> Real {
+    if !rb.is_dynamic() {
+        return 0.0;
+    }
+
+    let mprops = rb.mass_properties();
+    let arm = point - mprops.world_com(rb.position());
+    let inv_inertia_sqrt = mprops.world_inv_inertia_sqrt(&rb.position().rotation);
+    let torque = arm.perp(dir);
+    mprops.inv_mass + inv_inertia_sqrt * inv_inertia_sqrt * torque * torque
+}
+
+/// The inverse of the effective mass of a rigid-body along `dir`, at the world-space `point`.
+///
+/// This is zero if the rigid-body isn’t dynamic.
+#[cfg(feature = "dim3")]
+pub(crate) fn inv_effective_mass(rb: &RigidBody, point: &Point<Real>, dir: &Vector<Real>) -> Real {
+    if !rb.is_dynamic() {
+        return 0.0;
+    }
+
+    let mprops = rb.mass_properties();
+    let arm = point - mprops.world_com(rb.position());
+    let s = mprops.world_inv_inertia_sqrt(&rb.position().rotation);
+    let inv_inertia_sqrt = Matrix::new(
+        s.m11, s.m12, s.m13, s.m12, s.m22, s.m23, s.m13, s.m23, s.m33,
+    );
+    let inv_inertia = inv_inertia_sqrt * inv_inertia_sqrt;
+    let ang = (inv_inertia * arm.cross(dir)).cross(&arm);
+    mprops.inv_mass + dir.dot(&ang)
+}
+
 #[wasm_bindgen]
 impl RawRigidBodySet {
     /// The world-space translation of this rigid-body.
@@ -361,6 +413,80 @@ impl RawRigidBodySet {
         self.map(handle, |rb| rb.mass())
     }
 
+    /// The mass-properties of this rigid-body, including the contributions of its colliders,
+    /// expressed in its local-space.
+    pub fn rbMassProperties(&self, handle: FlatHandle) -> RawMassProperties {
+        self.map(handle, |rb| RawMassProperties(*rb.mass_properties()))
+    }
+
+    /// The world-space center-of-mass of this rigid-body.
+    pub fn rbCenterOfMass(&self, handle: FlatHandle) -> RawVector {
+        self.map(handle, |rb| {
+            RawVector(rb.mass_properties().world_com(rb.position()).coords)
+        })
+    }
+
+    /// The angular inertia of this rigid-body.
+    ///
+    /// This doesn’t take locked rotations into account.
+    #[cfg(feature = "dim2")]
+    pub fn rbWorldAngularInertia(&self, handle: FlatHandle) -> f32 {
+        self.map(handle, world_angular_inertia)
+    }
+
+    /// The world-space angular inertia tensor of this rigid-body, relative to its center-of-mass.
+    ///
+    /// This doesn’t take locked rotations into account.
+    #[cfg(feature = "dim3")]
+    pub fn rbWorldAngularInertia(&self, handle: FlatHandle) -> RawSdpMatrix3 {
+        self.map(handle, |rb| RawSdpMatrix3(world_angular_inertia(rb)))
+    }
+
+    /// The kinetic energy of this rigid-body.
+    pub fn rbKineticEnergy(&self, handle: FlatHandle) -> f32 {
+        self.map(handle, |rb| rb.kinetic_energy())
+    }
+
+    /// The world-space velocity of the given world-space point, assuming it is attached to
+    /// this rigid-body.
+    ///
+    /// # Parameters
+    /// - `point`: the world-space point.
+    pub fn rbVelocityAtPoint(&self, handle: FlatHandle, point: &RawVector) -> RawVector {
+        self.map(handle, |rb| {
+            RawVector(rb.velocity_at_point(&point.0.into()))
+        })
+    }
+
+    /// The sum of the forces added by the user to this rigid-body since its last call to
+    /// `rbResetForces`, including the forces added with `rbAddForceAtPoint`.
+    pub fn rbUserForce(&self, handle: FlatHandle) -> RawVector {
+        self.map(handle, |_| {
+            let forces = self.2.get(&utils::body_handle(handle));
+            RawVector(forces.map(|f| f.force).unwrap_or_else(na::zero))
+        })
+    }
+
+    /// The sum of the torques added by the user to this rigid-body since its last call to
+    /// `rbResetTorques`, including the torques resulting from `rbAddForceAtPoint`.
+    #[cfg(feature = "dim2")]
+    pub fn rbUserTorque(&self, handle: FlatHandle) -> f32 {
+        self.map(handle, |_| {
+            let forces = self.2.get(&utils::body_handle(handle));
+            forces.map(|f| f.torque).unwrap_or(0.0)
+        })
+    }
+
+    /// The sum of the torques added by the user to this rigid-body since its last call to
+    /// `rbResetTorques`, including the torques resulting from `rbAddForceAtPoint`.
+    #[cfg(feature = "dim3")]
+    pub fn rbUserTorque(&self, handle: FlatHandle) -> RawVector {
+        self.map(handle, |_| {
+            let forces = self.2.get(&utils::body_handle(handle));
+            RawVector(forces.map(|f| f.torque).unwrap_or_else(na::zero))
+        })
+    }
+
     /// Wakes this rigid-body up.
     ///
     /// A dynamic rigid-body that does not move during several consecutive frames will
@@ -393,27 +519,56 @@ impl RawRigidBodySet {
 
     /// The status of this rigid-body: fixed, dynamic, or kinematic.
     pub fn rbBodyType(&self, handle: FlatHandle) -> RawRigidBodyType {
-        self.map(handle, |rb| rb.body_type().into())
+        self.body_type(handle).into()
     }
 
     /// Set a new status for this rigid-body: fixed, dynamic, or kinematic.
     pub fn rbSetBodyType(&mut self, handle: FlatHandle, status: RawRigidBodyType) {
+        // The type of a disabled rigid-body is applied when it is enabled again.
+        if let Some(disabled) = self.1.get_mut(&utils::body_handle(handle)) {
+            disabled.body_type = status.into();
+            return;
+        }
+
         self.map_mut(handle, |rb| rb.set_body_type(status.into()));
     }
 
+    /// Is this rigid-body enabled?
+    pub fn rbIsEnabled(&self, handle: FlatHandle) -> bool {
+        self.is_enabled(utils::body_handle(handle))
+    }
+
+    /// Enables or disables this rigid-body.
+    ///
+    /// A disabled rigid-body is made fixed and all its colliders are disabled, so it doesn’t
+    /// interact with anything and is ignored by scene queries. The impulse joints attached to
+    /// it are ignored by the physics pipeline until it is enabled again. Its type and velocities
+    /// are restored when it is enabled again.
+    ///
+    /// Multibody joints attached to a disabled rigid-body are still simulated: if it is a link of
+    /// a multibody, it keeps following the motion of this multibody.
+    pub fn rbSetEnabled(
+        &mut self,
+        handle: FlatHandle,
+        enabled: bool,
+        colliders: &mut RawColliderSet,
+    ) {
+        self.set_enabled(utils::body_handle(handle), enabled, colliders)
+    }
+
     /// Is this rigid-body fixed?
     pub fn rbIsFixed(&self, handle: FlatHandle) -> bool {
-        self.map(handle, |rb| rb.is_fixed())
+        self.body_type(handle).is_fixed()
     }
 
     /// Is this rigid-body kinematic?
     pub fn rbIsKinematic(&self, handle: FlatHandle) -> bool {
-        self.map(handle, |rb| rb.is_kinematic())
+        self.body_type(handle).is_kinematic()
     }
 
     /// Is this rigid-body dynamic?
     pub fn rbIsDynamic(&self, handle: FlatHandle) -> bool {
-        self.map(handle, |rb| rb.is_dynamic())
+        self.body_type(handle).is_dynamic()
     }
 
     /// The linear damping coefficient of this rigid-body.
@@ -446,14 +601,22 @@ impl RawRigidBodySet {
     pub fn rbResetForces(&mut self, handle: FlatHandle, wakeUp: bool) {
         self.map_mut(handle, |rb| {
             rb.reset_forces(wakeUp);
-        })
+        });
+
+        if let Some(forces) = self.2.get_mut(&utils::body_handle(handle)) {
+            forces.force = na::zero();
+        }
     }
 
     /// Resets to zero all user-added torques added to this rigid-body.
     pub fn rbResetTorques(&mut self, handle: FlatHandle, wakeUp: bool) {
         self.map_mut(handle, |rb| {
             rb.reset_torques(wakeUp);
-        })
+        });
+
+        if let Some(forces) = self.2.get_mut(&utils::body_handle(handle)) {
+            forces.torque = na::zero();
+        }
     }
 
     /// Adds a force at the center-of-mass of this rigid-body.
@@ -464,7 +627,8 @@ impl RawRigidBodySet {
     pub fn rbAddForce(&mut self, handle: FlatHandle, force: &RawVector, wakeUp: bool) {
         self.map_mut(handle, |rb| {
             rb.add_force(force.0, wakeUp);
-        })
+        });
+        self.accumulate_user_forces(utils::body_handle(handle), force.0, na::zero());
     }
 
     /// Applies an impulse at the center-of-mass of this rigid-body.
@@ -487,7 +651,8 @@ impl RawRigidBodySet {
     pub fn rbAddTorque(&mut self, handle: FlatHandle, torque: f32, wakeUp: bool) {
         self.map_mut(handle, |rb| {
             rb.add_torque(torque, wakeUp);
-        })
+        });
+        self.accumulate_user_forces(utils::body_handle(handle), na::zero(), torque);
     }
 
     /// Adds a torque at the center-of-mass of this rigid-body.
@@ -499,7 +664,8 @@ impl RawRigidBodySet {
     pub fn rbAddTorque(&mut self, handle: FlatHandle, torque: &RawVector, wakeUp: bool) {
         self.map_mut(handle, |rb| {
             rb.add_torque(torque.0, wakeUp);
-        })
+        });
+        self.accumulate_user_forces(utils::body_handle(handle), na::zero(), torque.0);
     }
 
     /// Applies an impulsive torque at the center-of-mass of this rigid-body.
@@ -544,9 +710,15 @@ impl RawRigidBodySet {
         point: &RawVector,
         wakeUp: bool,
     ) {
-        self.map_mut(handle, |rb| {
+        let arm = self.map_mut(handle, |rb| {
             rb.add_force_at_point(force.0, point.0.into(), wakeUp);
-        })
+            point.0 - rb.mass_properties().world_com(rb.position()).coords
+        });
+        #[cfg(feature = "dim2")]
+        let torque = arm.perp(&force.0);
+        #[cfg(feature = "dim3")]
+        let torque = arm.cross(&force.0);
+        self.accumulate_user_forces(utils::body_handle(handle), force.0, torque);
     }
 
     /// Applies an impulse at the given world-space point of this rigid-body.
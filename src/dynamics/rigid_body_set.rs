@@ -2,9 +2,94 @@ use crate::dynamics::{RawImpulseJointSet, RawIslandManager, RawMultibodyJointSet
 use crate::geometry::RawColliderSet;
 use crate::math::{RawRotation, RawVector};
 use crate::utils::{self, FlatHandle};
-use rapier::dynamics::{MassProperties, RigidBody, RigidBodyBuilder, RigidBodySet, RigidBodyType};
+use js_sys::{Float32Array, Float64Array};
+use rapier::dynamics::{
+    MassProperties, RigidBody, RigidBodyBuilder, RigidBodyHandle, RigidBodySet, RigidBodyType,
+};
+use rapier::math::{AngVector, Point, Real, Vector};
+use std::collections::HashMap;
 use wasm_bindgen::prelude::*;
 
+/// The number of floats written for each rigid-body by `RawRigidBodySet::fillTransforms`.
+#[cfg(feature = "dim2")]
+pub const TRANSFORM_STRIDE: usize = 3;
+/// The number of floats written for each rigid-body by `RawRigidBodySet::fillTransforms`.
+#[cfg(feature = "dim3")]
+pub const TRANSFORM_STRIDE: usize = 7;
+
+/// Flat buffers containing the handle and position of rigid-bodies, filled by
+/// `RawRigidBodySet::fillTransforms` and `RawRigidBodySet::fillActiveTransforms`.
+///
+/// The `i`-th rigid-body has the handle `handles[i]`, and its translation and rotation (an angle in
+/// 2D, a quaternion `x, y, z, w` in 3D) are given by the `i`-th group of `TRANSFORM_STRIDE` floats
+/// of `transforms`. The buffers are kept between two fills, so they are only reallocated when the
+/// number of rigid-bodies grows.
+#[wasm_bindgen]
+pub struct RawTransformsDump {
+    handles: Vec<FlatHandle>,
+    transforms: Vec<f32>,
+}
+
+impl Default for RawTransformsDump {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RawTransformsDump {
+    fn clear(&mut self) {
+        self.handles.clear();
+        self.transforms.clear();
+    }
+
+    fn push(&mut self, handle: RigidBodyHandle, rb: &RigidBody) {
+        let pos = rb.position();
+        self.handles.push(utils::flat_handle(handle.0));
+        self.transforms
+            .extend_from_slice(pos.translation.vector.as_slice());
+
+        #[cfg(feature = "dim2")]
+        self.transforms.push(pos.rotation.angle());
+        #[cfg(feature = "dim3")]
+        self.transforms
+            .extend_from_slice(pos.rotation.coords.as_slice());
+    }
+}
+
+#[wasm_bindgen]
+impl RawTransformsDump {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        RawTransformsDump {
+            handles: vec![],
+            transforms: vec![],
+        }
+    }
+
+    /// The number of rigid-bodies written into this dump.
+    pub fn numBodies(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// The handle of each rigid-body.
+    ///
+    /// This is a view into the WASM memory: it is invalidated by the next fill of this dump, and
+    /// by any allocation of WASM memory, so it must be read right away.
+    pub fn handles(&self) -> Float64Array {
+        // SAFETY: the view is only valid as long as the WASM memory isn’t grown, as documented.
+        unsafe { Float64Array::view(&self.handles) }
+    }
+
+    /// The translation and rotation of each rigid-body, as `TRANSFORM_STRIDE` consecutive floats.
+    ///
+    /// This is a view into the WASM memory: it is invalidated by the next fill of this dump, and
+    /// by any allocation of WASM memory, so it must be read right away.
+    pub fn transforms(&self) -> Float32Array {
+        // SAFETY: the view is only valid as long as the WASM memory isn’t grown, as documented.
+        unsafe { Float32Array::view(&self.transforms) }
+    }
+}
+
 #[wasm_bindgen]
 pub enum RawRigidBodyType {
     Dynamic,
@@ -35,10 +120,169 @@ impl Into<RawRigidBodyType> for RigidBodyType {
     }
 }
 
+/// The state a disabled rigid-body had before being disabled.
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub(crate) struct DisabledBody {
+    pub body_type: RigidBodyType,
+    pub linvel: Vector<Real>,
+    pub angvel: AngVector<Real>,
+}
+
+/// The force and torque added by the user to a rigid-body since its last reset.
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub(crate) struct UserForces {
+    pub force: Vector<Real>,
+    pub torque: AngVector<Real>,
+}
+
+impl Default for UserForces {
+    fn default() -> Self {
+        Self {
+            force: na::zero(),
+            torque: na::zero(),
+        }
+    }
+}
+
 #[wasm_bindgen]
-pub struct RawRigidBodySet(pub(crate) RigidBodySet);
+pub struct RawRigidBodySet(
+    pub(crate) RigidBodySet,
+    // The disabled rigid-bodies. They are made fixed, and their colliders are disabled,
+    // until they are enabled again.
+    pub(crate) HashMap<RigidBodyHandle, DisabledBody>,
+    // The user forces accumulated on each rigid-body, since rapier doesn’t expose them.
+    pub(crate) HashMap<RigidBodyHandle, UserForces>,
+);
 
 impl RawRigidBodySet {
+    pub(crate) fn from_parts(
+        bodies: RigidBodySet,
+        disabled: impl IntoIterator<Item = (RigidBodyHandle, DisabledBody)>,
+        user_forces: impl IntoIterator<Item = (RigidBodyHandle, UserForces)>,
+    ) -> Self {
+        RawRigidBodySet(
+            bodies,
+            disabled.into_iter().collect(),
+            user_forces.into_iter().collect(),
+        )
+    }
+
+    /// The user forces accumulated on the rigid-bodies, in handle order.
+    pub(crate) fn user_forces(&self) -> Vec<(RigidBodyHandle, UserForces)> {
+        let mut forces: Vec<_> = self.2.iter().map(|(h, f)| (*h, *f)).collect();
+        forces.sort_by_key(|(h, _)| h.into_raw_parts());
+        forces
+    }
+
+    /// Records a force and torque added by the user to a rigid-body.
+    ///
+    /// Like rapier, this ignores the forces added to non-dynamic rigid-bodies.
+    pub(crate) fn accumulate_user_forces(
+        &mut self,
+        handle: RigidBodyHandle,
+        force: Vector<Real>,
+        torque: AngVector<Real>,
+    ) {
+        if self.0.get(handle).map(|rb| rb.is_dynamic()) == Some(true) {
+            let forces = self.2.entry(handle).or_default();
+            forces.force += force;
+            forces.torque += torque;
+        }
+    }
+
+    /// Adds to a rigid-body a force applied at a world-space point and a torque, and records
+    /// them as user forces.
+    pub(crate) fn add_user_wrench(
+        &mut self,
+        handle: RigidBodyHandle,
+        force: Vector<Real>,
+        point: Point<Real>,
+        torque: AngVector<Real>,
+        wake_up: bool,
+    ) {
+        let arm = match self.0.get_mut(handle) {
+            Some(rb) => {
+                rb.add_force_at_point(force, point, wake_up);
+                rb.add_torque(torque, wake_up);
+                point - rb.mass_properties().world_com(rb.position())
+            }
+            None => return,
+        };
+        #[cfg(feature = "dim2")]
+        let torque = torque + arm.perp(&force);
+        #[cfg(feature = "dim3")]
+        let torque = torque + arm.cross(&force);
+        self.accumulate_user_forces(handle, force, torque);
+    }
+
+    /// The disabled rigid-bodies, in handle order.
+    pub(crate) fn disabled_bodies(&self) -> Vec<(RigidBodyHandle, DisabledBody)> {
+        let mut disabled: Vec<_> = self.1.iter().map(|(h, d)| (*h, *d)).collect();
+        disabled.sort_by_key(|(h, _)| h.into_raw_parts());
+        disabled
+    }
+
+    /// The type of a rigid-body, or the type it will be given back when it is enabled again if
+    /// it is disabled.
+    pub(crate) fn body_type(&self, handle: FlatHandle) -> RigidBodyType {
+        if let Some(disabled) = self.1.get(&utils::body_handle(handle)) {
+            return disabled.body_type;
+        }
+
+        self.map(handle, |rb| rb.body_type())
+    }
+
+    pub(crate) fn is_enabled(&self, handle: RigidBodyHandle) -> bool {
+        !self.1.contains_key(&handle)
+    }
+
+    pub(crate) fn set_enabled(
+        &mut self,
+        handle: RigidBodyHandle,
+        enabled: bool,
+        colliders: &mut RawColliderSet,
+    ) {
+        let rb = match self.0.get_mut(handle) {
+            Some(rb) => rb,
+            None => return,
+        };
+
+        if enabled {
+            let disabled = match self.1.remove(&handle) {
+                Some(disabled) => disabled,
+                None => return,
+            };
+            rb.set_body_type(disabled.body_type);
+            rb.set_linvel(disabled.linvel, false);
+            rb.set_angvel(disabled.angvel, false);
+            rb.wake_up(true);
+        } else {
+            if self.1.contains_key(&handle) {
+                return;
+            }
+
+            let disabled = DisabledBody {
+                body_type: rb.body_type(),
+                linvel: *rb.linvel(),
+                #[cfg(feature = "dim2")]
+                angvel: rb.angvel(),
+                #[cfg(feature = "dim3")]
+                angvel: *rb.angvel(),
+            };
+            rb.set_linvel(Vector::zeros(), false);
+            #[cfg(feature = "dim2")]
+            rb.set_angvel(0.0, false);
+            #[cfg(feature = "dim3")]
+            rb.set_angvel(Vector::zeros(), false);
+            rb.set_body_type(RigidBodyType::Fixed);
+            self.1.insert(handle, disabled);
+        }
+
+        for co_handle in rb.colliders() {
+            colliders.set_parent_enabled(*co_handle, enabled);
+        }
+    }
+
     pub(crate) fn map<T>(&self, handle: FlatHandle, f: impl FnOnce(&RigidBody) -> T) -> T {
         let body = self.0.get(utils::body_handle(handle)).expect(
             "Invalid RigidBody reference. It may have been removed from the physics World.",
@@ -62,7 +306,7 @@ impl RawRigidBodySet {
 impl RawRigidBodySet {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
-        RawRigidBodySet(RigidBodySet::new())
+        RawRigidBodySet(RigidBodySet::new(), HashMap::new(), HashMap::new())
     }
 
     #[cfg(feature = "dim3")]
@@ -195,6 +439,9 @@ impl RawRigidBodySet {
             &mut articulations.0,
             true,
         );
+        self.1.remove(&handle);
+        self.2.remove(&handle);
+        colliders.cleanup_disabled_colliders();
     }
 
     /// The number of rigid-bodies on this set.
@@ -217,4 +464,31 @@ impl RawRigidBodySet {
             let _ = f.call1(&this, &JsValue::from(utils::flat_handle(handle.0)));
         }
     }
+
+    /// Writes the handle and position of every rigid-body of this set into `out`.
+    pub fn fillTransforms(&self, out: &mut RawTransformsDump) {
+        out.clear();
+        for (handle, rb) in self.0.iter() {
+            out.push(handle, rb);
+        }
+    }
+
+    /// Writes the handle and position of every active (i.e. non-sleeping) dynamic and kinematic
+    /// rigid-body into `out`.
+    pub fn fillActiveTransforms(&self, islands: &RawIslandManager, out: &mut RawTransformsDump) {
+        let active = islands.0.active_dynamic_bodies();
+        let active_kinematic = islands.0.active_kinematic_bodies();
+        out.clear();
+
+        for handle in active.iter().chain(active_kinematic.iter()) {
+            if let Some(rb) = self.0.get(*handle) {
+                out.push(*handle, rb);
+            }
+        }
+    }
+
+    /// The number of floats written for each rigid-body by `fillTransforms` and `fillActiveTransforms`.
+    pub fn transformStride() -> usize {
+        TRANSFORM_STRIDE
+    }
 }
@@ -1,3 +1,4 @@
+use crate::dynamics::RawMassProperties;
 use crate::geometry::shape::SharedShapeUtility;
 use crate::geometry::{
     RawColliderSet, RawPointProjection, RawRayIntersection, RawShape, RawShapeColliderTOI,
@@ -359,6 +360,11 @@ impl RawColliderSet {
         self.map(handle, |co| co.mass())
     }
 
+    /// The mass-properties of this collider, expressed in its local-space.
+    pub fn coMassProperties(&self, handle: FlatHandle) -> RawMassProperties {
+        self.map(handle, |co| RawMassProperties(co.mass_properties()))
+    }
+
     /// The volume of this collider.
     pub fn coVolume(&self, handle: FlatHandle) -> f32 {
         self.map(handle, |co| co.volume())
@@ -366,6 +372,10 @@ impl RawColliderSet {
 
     /// The collision groups of this collider.
     pub fn coCollisionGroups(&self, handle: FlatHandle) -> u32 {
+        if let Some(disabled) = self.1.get(&utils::collider_handle(handle)) {
+            return super::pack_interaction_groups(disabled.collision_groups);
+        }
+
         self.map(handle, |co| {
             super::pack_interaction_groups(co.collision_groups())
         })
@@ -373,11 +383,40 @@ impl RawColliderSet {
 
     /// The solver groups of this collider.
     pub fn coSolverGroups(&self, handle: FlatHandle) -> u32 {
+        if let Some(disabled) = self.1.get(&utils::collider_handle(handle)) {
+            return super::pack_interaction_groups(disabled.solver_groups);
+        }
+
         self.map(handle, |co| {
             super::pack_interaction_groups(co.solver_groups())
         })
     }
 
+    /// Is this collider enabled?
+    ///
+    /// This doesn’t take into account whether the rigid-body it is attached to is enabled.
+    pub fn coIsEnabled(&self, handle: FlatHandle) -> bool {
+        self.1
+            .get(&utils::collider_handle(handle))
+            .map(|disabled| !disabled.by_user)
+            .unwrap_or(true)
+    }
+
+    /// The user-defined 32-bit integer of this collider.
+    ///
+    /// These are the 32 least significant bits of its 128-bit user-data.
+    pub fn coUserData(&self, handle: FlatHandle) -> u32 {
+        self.map(handle, |co| co.user_data as u32)
+    }
+
+    /// The 128-bit user-data of this collider, as four 32-bit words starting with the least
+    /// significant one.
+    pub fn coUserData128(&self, handle: FlatHandle) -> Vec<u32> {
+        self.map(handle, |co| {
+            (0..4).map(|i| (co.user_data >> (32 * i)) as u32).collect()
+        })
+    }
+
     /// The physics hooks enabled for this collider.
     pub fn coActiveHooks(&self, handle: FlatHandle) -> u32 {
         self.map(handle, |co| co.active_hooks().bits())
@@ -623,14 +662,62 @@ impl RawColliderSet {
 
     pub fn coSetCollisionGroups(&mut self, handle: FlatHandle, groups: u32) {
         let groups = super::unpack_interaction_groups(groups);
+
+        // The groups of a disabled collider are applied when it is enabled again.
+        if let Some(disabled) = self.1.get_mut(&utils::collider_handle(handle)) {
+            disabled.collision_groups = groups;
+            return;
+        }
+
         self.map_mut(handle, |co| co.set_collision_groups(groups))
     }
 
     pub fn coSetSolverGroups(&mut self, handle: FlatHandle, groups: u32) {
         let groups = super::unpack_interaction_groups(groups);
+
+        if let Some(disabled) = self.1.get_mut(&utils::collider_handle(handle)) {
+            disabled.solver_groups = groups;
+            return;
+        }
+
         self.map_mut(handle, |co| co.set_solver_groups(groups))
     }
 
+    /// Enables or disables this collider.
+    ///
+    /// A disabled collider doesn’t generate any contact or intersection and is ignored by scene
+    /// queries, but it still contributes to the mass of the rigid-body it is attached to.
+    pub fn coSetEnabled(&mut self, handle: FlatHandle, enabled: bool) {
+        self.set_enabled(utils::collider_handle(handle), enabled)
+    }
+
+    /// Sets the user-defined 32-bit integer of this collider.
+    ///
+    /// This sets the 32 least significant bits of its 128-bit user-data, and clears the others.
+    ///
+    /// # Parameters
+    /// - `data`: an integer that can be used by the user to attach any data to this collider.
+    pub fn coSetUserData(&mut self, handle: FlatHandle, data: u32) {
+        self.map_mut(handle, |co| {
+            co.user_data = data as u128;
+        })
+    }
+
+    /// Sets the 128-bit user-data of this collider.
+    ///
+    /// # Parameters
+    /// - `data`: up to four 32-bit words, starting with the least significant one. Missing
+    ///   words are set to zero.
+    pub fn coSetUserData128(&mut self, handle: FlatHandle, data: &[u32]) {
+        self.map_mut(handle, |co| {
+            co.user_data = data
+                .iter()
+                .take(4)
+                .enumerate()
+                .fold(0, |acc, (i, word)| acc | ((*word as u128) << (32 * i)));
+        })
+    }
+
     pub fn coSetActiveHooks(&mut self, handle: FlatHandle, hooks: u32) {
         let hooks = ActiveHooks::from_bits(hooks).unwrap_or(ActiveHooks::empty());
         self.map_mut(handle, |co| co.set_active_hooks(hooks));
@@ -3,6 +3,7 @@ use crate::geometry::RawShape;
 use crate::math::{RawRotation, RawVector};
 use crate::utils::{self, FlatHandle};
 use rapier::prelude::*;
+use std::collections::HashMap;
 use wasm_bindgen::prelude::*;
 
 // NOTE: this MUST match the same enum on the TS side.
@@ -12,10 +13,95 @@ enum MassPropsMode {
     MassProps,
 }
 
+/// The interaction groups a disabled collider had before being disabled.
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub(crate) struct DisabledCollider {
+    pub collision_groups: InteractionGroups,
+    pub solver_groups: InteractionGroups,
+    /// Was this collider disabled with `coSetEnabled`?
+    pub by_user: bool,
+    /// Was this collider disabled because the rigid-body it is attached to was disabled?
+    pub by_parent: bool,
+}
+
 #[wasm_bindgen]
-pub struct RawColliderSet(pub(crate) ColliderSet);
+pub struct RawColliderSet(
+    pub(crate) ColliderSet,
+    // The disabled colliders. They don’t interact with anything because their
+    // interaction groups are set to `InteractionGroups::none()` until they are
+    // enabled again.
+    pub(crate) HashMap<ColliderHandle, DisabledCollider>,
+);
 
 impl RawColliderSet {
+    pub(crate) fn from_parts(
+        colliders: ColliderSet,
+        disabled: impl IntoIterator<Item = (ColliderHandle, DisabledCollider)>,
+    ) -> Self {
+        RawColliderSet(colliders, disabled.into_iter().collect())
+    }
+
+    /// The disabled colliders, in handle order.
+    pub(crate) fn disabled_colliders(&self) -> Vec<(ColliderHandle, DisabledCollider)> {
+        let mut disabled: Vec<_> = self.1.iter().map(|(h, d)| (*h, *d)).collect();
+        disabled.sort_by_key(|(h, _)| h.0.into_raw_parts());
+        disabled
+    }
+
+    /// Is this collider enabled, and attached to an enabled rigid-body (if any)?
+    pub(crate) fn is_enabled(&self, handle: ColliderHandle) -> bool {
+        !self.1.contains_key(&handle)
+    }
+
+    pub(crate) fn has_disabled_colliders(&self) -> bool {
+        !self.1.is_empty()
+    }
+
+    pub(crate) fn set_enabled(&mut self, handle: ColliderHandle, enabled: bool) {
+        self.update_disabled_state(handle, |disabled| disabled.by_user = !enabled)
+    }
+
+    pub(crate) fn set_parent_enabled(&mut self, handle: ColliderHandle, enabled: bool) {
+        self.update_disabled_state(handle, |disabled| disabled.by_parent = !enabled)
+    }
+
+    fn update_disabled_state(
+        &mut self,
+        handle: ColliderHandle,
+        update: impl FnOnce(&mut DisabledCollider),
+    ) {
+        let co = match self.0.get_mut(handle) {
+            Some(co) => co,
+            None => return,
+        };
+        let was_disabled = self.1.contains_key(&handle);
+        let mut disabled = self.1.get(&handle).copied().unwrap_or(DisabledCollider {
+            collision_groups: co.collision_groups(),
+            solver_groups: co.solver_groups(),
+            by_user: false,
+            by_parent: false,
+        });
+        update(&mut disabled);
+
+        if disabled.by_user || disabled.by_parent {
+            if !was_disabled {
+                co.set_collision_groups(InteractionGroups::none());
+                co.set_solver_groups(InteractionGroups::none());
+            }
+            self.1.insert(handle, disabled);
+        } else if was_disabled {
+            co.set_collision_groups(disabled.collision_groups);
+            co.set_solver_groups(disabled.solver_groups);
+            self.1.remove(&handle);
+        }
+    }
+
+    /// Forgets the disabled colliders that have been removed from this set.
+    pub(crate) fn cleanup_disabled_colliders(&mut self) {
+        let colliders = &self.0;
+        self.1.retain(|handle, _| colliders.get(*handle).is_some());
+    }
+
     pub(crate) fn map<T>(&self, handle: FlatHandle, f: impl FnOnce(&Collider) -> T) -> T {
         let collider = self
             .0
@@ -106,11 +192,14 @@ impl RawColliderSet {
         let collider = builder.build();
 
         if hasParent {
-            Some(utils::flat_handle(
-                self.0
-                    .insert_with_parent(collider, utils::body_handle(parent), &mut bodies.0)
-                    .0,
-            ))
+            let parent = utils::body_handle(parent);
+            let handle = self.0.insert_with_parent(collider, parent, &mut bodies.0);
+
+            if !bodies.is_enabled(parent) {
+                self.set_parent_enabled(handle, false);
+            }
+
+            Some(utils::flat_handle(handle.0))
         } else {
             Some(utils::flat_handle(self.0.insert(collider).0))
         }
@@ -121,7 +210,7 @@ impl RawColliderSet {
 impl RawColliderSet {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
-        RawColliderSet(ColliderSet::new())
+        RawColliderSet(ColliderSet::new(), HashMap::new())
     }
 
     pub fn len(&self) -> usize {
@@ -248,6 +337,7 @@ impl RawColliderSet {
     ) {
         let handle = utils::collider_handle(handle);
         self.0.remove(handle, &mut islands.0, &mut bodies.0, wakeUp);
+        self.1.remove(&handle);
     }
 
     /// Checks if a collider with the given integer handle exists.
@@ -1,7 +1,10 @@
+use crate::geometry::RawColliderSet;
 use crate::math::RawVector;
 use crate::utils::{self, FlatHandle};
+use js_sys::{Float32Array, Float64Array, Uint32Array};
 use rapier::geometry::{ContactManifold, ContactPair, NarrowPhase};
 use rapier::math::Real;
+use rapier::parry::utils::IsometryOpt;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -57,6 +60,164 @@ impl RawNarrowPhase {
         let handle2 = utils::collider_handle(handle2);
         self.0.intersection_pair(handle1, handle2) == Some(true)
     }
+
+    /// Writes every contact manifold with at least one contact point, from all the
+    /// contact pairs with active contacts, into the flat buffers of `out`.
+    pub fn dumpContacts(&self, colliders: &RawColliderSet, out: &mut RawContactsDump) {
+        out.clear();
+
+        for pair in self.0.contact_pairs() {
+            if !pair.has_any_active_contact {
+                continue;
+            }
+
+            let (co1, co2) = match (
+                colliders.0.get(pair.collider1),
+                colliders.0.get(pair.collider2),
+            ) {
+                (Some(co1), Some(co2)) => (co1, co2),
+                _ => continue,
+            };
+
+            for manifold in &pair.manifolds {
+                if manifold.points.is_empty() {
+                    continue;
+                }
+
+                // The local points and normal are expressed relative to the sub-shapes of
+                // compound colliders.
+                let world_pos1 = manifold.subshape_pos1.prepend_to(co1.position());
+                let world_pos2 = manifold.subshape_pos2.prepend_to(co2.position());
+                let normal = world_pos1 * manifold.local_n1;
+                out.manifold_colliders
+                    .push(utils::flat_handle(pair.collider1.0));
+                out.manifold_colliders
+                    .push(utils::flat_handle(pair.collider2.0));
+                out.manifold_normals.extend_from_slice(normal.as_slice());
+
+                for contact in &manifold.points {
+                    let point1 = world_pos1 * contact.local_p1;
+                    let point2 = world_pos2 * contact.local_p2;
+                    out.points1.extend_from_slice(point1.coords.as_slice());
+                    out.points2.extend_from_slice(point2.coords.as_slice());
+                    out.dists.push(contact.dist);
+                    out.impulses.push(contact.data.impulse);
+                }
+
+                out.manifold_offsets.push(out.dists.len() as u32);
+            }
+        }
+    }
+}
+
+/// Flat buffers describing all the contact manifolds of a narrow-phase, filled by
+/// `RawNarrowPhase::dumpContacts`.
+///
+/// The `i`-th manifold involves the colliders `manifoldColliders[2 * i]` and
+/// `manifoldColliders[2 * i + 1]`, and its world-space normal is given by the `i`-th group of
+/// 2 (in 2D) or 3 (in 3D) floats of `manifoldNormals`. Its contact points are the points with indices in
+/// `manifoldOffsets[i]..manifoldOffsets[i + 1]`.
+#[wasm_bindgen]
+pub struct RawContactsDump {
+    manifold_colliders: Vec<FlatHandle>,
+    manifold_normals: Vec<f32>,
+    manifold_offsets: Vec<u32>,
+    points1: Vec<f32>,
+    points2: Vec<f32>,
+    dists: Vec<f32>,
+    impulses: Vec<f32>,
+}
+
+impl Default for RawContactsDump {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RawContactsDump {
+    fn clear(&mut self) {
+        self.manifold_colliders.clear();
+        self.manifold_normals.clear();
+        self.manifold_offsets.clear();
+        self.manifold_offsets.push(0);
+        self.points1.clear();
+        self.points2.clear();
+        self.dists.clear();
+        self.impulses.clear();
+    }
+}
+
+#[wasm_bindgen]
+impl RawContactsDump {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        RawContactsDump {
+            manifold_colliders: vec![],
+            manifold_normals: vec![],
+            manifold_offsets: vec![0],
+            points1: vec![],
+            points2: vec![],
+            dists: vec![],
+            impulses: vec![],
+        }
+    }
+
+    pub fn numManifolds(&self) -> usize {
+        self.manifold_offsets.len() - 1
+    }
+
+    pub fn numContacts(&self) -> usize {
+        self.dists.len()
+    }
+
+    /// The handles of the two colliders involved in each manifold.
+    pub fn manifoldColliders(&self) -> Float64Array {
+        let output = Float64Array::new_with_length(self.manifold_colliders.len() as u32);
+        output.copy_from(&self.manifold_colliders);
+        output
+    }
+
+    /// The world-space normal of each manifold, pointing outward the first collider.
+    pub fn manifoldNormals(&self) -> Float32Array {
+        let output = Float32Array::new_with_length(self.manifold_normals.len() as u32);
+        output.copy_from(&self.manifold_normals);
+        output
+    }
+
+    /// The range of contact points of each manifold (`numManifolds() + 1` elements).
+    pub fn manifoldOffsets(&self) -> Uint32Array {
+        let output = Uint32Array::new_with_length(self.manifold_offsets.len() as u32);
+        output.copy_from(&self.manifold_offsets);
+        output
+    }
+
+    /// The world-space position of each contact point on the first collider.
+    pub fn points1(&self) -> Float32Array {
+        let output = Float32Array::new_with_length(self.points1.len() as u32);
+        output.copy_from(&self.points1);
+        output
+    }
+
+    /// The world-space position of each contact point on the second collider.
+    pub fn points2(&self) -> Float32Array {
+        let output = Float32Array::new_with_length(self.points2.len() as u32);
+        output.copy_from(&self.points2);
+        output
+    }
+
+    /// The signed distance between the two colliders at each contact point (negative if they penetrate).
+    pub fn dists(&self) -> Float32Array {
+        let output = Float32Array::new_with_length(self.dists.len() as u32);
+        output.copy_from(&self.dists);
+        output
+    }
+
+    /// The normal impulse applied by the constraints solver at each contact point during the last step.
+    pub fn impulses(&self) -> Float32Array {
+        let output = Float32Array::new_with_length(self.impulses.len() as u32);
+        output.copy_from(&self.impulses);
+        output
+    }
 }
 
 #[wasm_bindgen]
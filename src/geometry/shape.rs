@@ -219,6 +219,25 @@ pub enum RawShapeType {
 #[wasm_bindgen]
 pub struct RawShape(pub(crate) SharedShape);
 
+/// The shapes and local positions of the parts of a compound shape, accumulated before
+/// the compound shape is created with `RawShape::compound`.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct RawCompoundParts(Vec<(Isometry<Real>, SharedShape)>);
+
+#[wasm_bindgen]
+impl RawCompoundParts {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, shape: &RawShape, translation: &RawVector, rotation: &RawRotation) {
+        let pos = Isometry::from_parts(translation.0.into(), rotation.0);
+        self.0.push((pos, shape.0.clone()));
+    }
+}
+
 #[wasm_bindgen]
 impl RawShape {
     #[cfg(feature = "dim2")]
@@ -378,6 +397,23 @@ impl RawShape {
         SharedShape::round_convex_mesh(vertices, &indices, borderRadius).map(|s| Self(s))
     }
 
+    /// Creates a compound shape from the parts accumulated in `parts`.
+    ///
+    /// Returns `None` if `parts` is empty, or if one of the parts is itself a composite shape
+    /// (compound, polyline, triangle mesh, or heightfield).
+    pub fn compound(parts: &RawCompoundParts) -> Option<RawShape> {
+        if parts.0.is_empty()
+            || parts
+                .0
+                .iter()
+                .any(|(_, shape)| shape.as_composite_shape().is_some())
+        {
+            return None;
+        }
+
+        Some(Self(SharedShape::compound(parts.0.clone())))
+    }
+
     pub fn castShape(
         &self,
         shapePos1: &RawVector,
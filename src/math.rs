@@ -1,7 +1,9 @@
 //! Linear algebra primitives.
 
 #[cfg(feature = "dim3")]
-use na::{Quaternion, Unit};
+use js_sys::Float32Array;
+#[cfg(feature = "dim3")]
+use na::{Matrix3, Quaternion, Unit};
 use rapier::math::{Rotation, Vector};
 use wasm_bindgen::prelude::*;
 
@@ -227,3 +229,21 @@ impl RawVector {
         Self(self.0.zyx())
     }
 }
+
+#[cfg(feature = "dim3")]
+#[wasm_bindgen]
+#[repr(transparent)]
+#[derive(Copy, Clone)]
+/// A symmetric 3x3 matrix, e.g., an angular inertia tensor.
+pub struct RawSdpMatrix3(pub(crate) Matrix3<f32>);
+
+#[cfg(feature = "dim3")]
+#[wasm_bindgen]
+impl RawSdpMatrix3 {
+    /// The six elements of the upper-triangular part of this matrix, in the order
+    /// `m11, m12, m13, m22, m23, m33`.
+    pub fn elements(&self) -> Float32Array {
+        let m = &self.0;
+        Float32Array::from(&[m.m11, m.m12, m.m13, m.m22, m.m23, m.m33][..])
+    }
+}
@@ -0,0 +1,293 @@
+use crate::dynamics::{
+    joint_coordinates, RawImpulseJointSet, RawMultibodyJointSet, RawRigidBodySet,
+};
+use crate::geometry::{RawColliderSet, RawNarrowPhase};
+use crate::utils::{self, FlatHandle};
+use js_sys::Float64Array;
+use rapier::data::Index;
+use rapier::dynamics::{Multibody, RigidBody, RigidBodyHandle};
+use rapier::geometry::{Collider, ColliderSet};
+use rapier::math::{Isometry, Real, Vector};
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+
+/// A 64-bit FNV-1a hasher.
+///
+/// Unlike the hashers from the standard library, its output is guaranteed to be the same
+/// on every platform and for every version of this library.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        for byte in value.to_le_bytes().iter() {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    fn write_bool(&mut self, value: bool) {
+        self.write_u32(value as u32)
+    }
+
+    fn write_real(&mut self, value: Real) {
+        self.write_u32(value.to_bits())
+    }
+
+    fn write_reals<'a>(&mut self, values: impl IntoIterator<Item = &'a Real>) {
+        for value in values {
+            self.write_real(*value)
+        }
+    }
+
+    fn write_index(&mut self, index: Index) {
+        let (i, g) = index.into_raw_parts();
+        self.write_u32(i);
+        self.write_u32(g);
+    }
+
+    fn write_vector(&mut self, vector: &Vector<Real>) {
+        self.write_reals(vector.iter())
+    }
+
+    fn write_isometry(&mut self, pos: &Isometry<Real>) {
+        self.write_vector(&pos.translation.vector);
+        #[cfg(feature = "dim2")]
+        {
+            self.write_real(pos.rotation.re);
+            self.write_real(pos.rotation.im);
+        }
+        #[cfg(feature = "dim3")]
+        self.write_reals(pos.rotation.coords.iter());
+    }
+
+    fn write_body(&mut self, handle: RigidBodyHandle, rb: &RigidBody) {
+        self.write_index(handle.0);
+        self.write_u32(rb.body_type() as u32);
+        self.write_bool(rb.is_sleeping());
+        self.write_isometry(rb.position());
+        self.write_vector(rb.linvel());
+        #[cfg(feature = "dim2")]
+        self.write_real(rb.angvel());
+        #[cfg(feature = "dim3")]
+        self.write_vector(rb.angvel());
+    }
+
+    fn write_multibody(&mut self, multibody: &Multibody) {
+        for link in multibody.links() {
+            self.write_index(link.rigid_body_handle().0);
+            self.write_reals(&joint_coordinates(link.joint()));
+        }
+        self.write_reals(multibody.generalized_velocity().iter());
+    }
+
+    fn write_collider(&mut self, handle: Index, co: &Collider) {
+        self.write_index(handle);
+        self.write_bool(co.is_sensor());
+        self.write_isometry(co.position());
+    }
+
+    fn write_body_colliders(&mut self, rb: &RigidBody, colliders: &ColliderSet) {
+        for handle in rb.colliders() {
+            if let Some(co) = colliders.get(*handle) {
+                self.write_collider(handle.0, co);
+            }
+        }
+    }
+
+    fn finish(&self) -> String {
+        format!("{:016x}", self.0)
+    }
+}
+
+/// A set of dynamic rigid-bodies interacting with each other through contacts or joints,
+/// and its checksum.
+struct IslandChecksum {
+    bodies: Vec<FlatHandle>,
+    hash: String,
+}
+
+/// A checksum of the state of a physics world.
+///
+/// The checksum only depends on the position, velocities, and sleep state of the rigid-bodies,
+/// on the position of the colliders, on the accumulated impulses of the impulse joints, and on
+/// the joint coordinates and velocities of the multibodies. All these elements are hashed in
+/// handle order (multibodies in the handle order of their root rigid-body) so two physics worlds
+/// are guaranteed to have the same checksum if they contain the same elements with the same state.
+#[wasm_bindgen]
+pub struct RawWorldChecksum {
+    hash: String,
+    islands: Vec<IslandChecksum>,
+}
+
+#[wasm_bindgen]
+impl RawWorldChecksum {
+    /// Computes the checksum of a physics world.
+    ///
+    /// # Parameters
+    /// - `withIslands`: if `true`, a sub-checksum is also computed for every set of dynamic
+    ///   rigid-bodies interacting with each other through active contacts or joints. This can
+    ///   be used to localize the part of the world where two simulations started to diverge.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        impulse_joints: &RawImpulseJointSet,
+        multibody_joints: &RawMultibodyJointSet,
+        narrow_phase: &RawNarrowPhase,
+        withIslands: bool,
+    ) -> Self {
+        let mut hasher = Fnv1a::new();
+
+        for (handle, rb) in bodies.0.iter() {
+            hasher.write_body(handle, rb);
+        }
+
+        for (handle, co) in colliders.0.iter() {
+            hasher.write_collider(handle.0, co);
+        }
+
+        for (handle, joint) in impulse_joints.0.iter() {
+            hasher.write_index(handle.0);
+            hasher.write_index(joint.body1.0);
+            hasher.write_index(joint.body2.0);
+            hasher.write_reals(joint.impulses.iter());
+        }
+
+        let mut multibodies: Vec<_> = multibody_joints.0.multibodies().collect();
+        multibodies.sort_by_key(|multibody| multibody.root().rigid_body_handle().into_raw_parts());
+        for multibody in multibodies {
+            hasher.write_multibody(multibody);
+        }
+
+        let islands = if withIslands {
+            compute_islands(
+                bodies,
+                colliders,
+                impulse_joints,
+                multibody_joints,
+                narrow_phase,
+            )
+        } else {
+            vec![]
+        };
+
+        Self {
+            hash: hasher.finish(),
+            islands,
+        }
+    }
+
+    /// The checksum of the whole physics world, as a 16-characters hexadecimal string.
+    pub fn hash(&self) -> String {
+        self.hash.clone()
+    }
+
+    /// The number of islands with a sub-checksum.
+    pub fn numIslands(&self) -> usize {
+        self.islands.len()
+    }
+
+    /// The checksum of the `i`-th island, as a 16-characters hexadecimal string.
+    pub fn islandHash(&self, i: usize) -> Option<String> {
+        self.islands.get(i).map(|island| island.hash.clone())
+    }
+
+    /// The handles of the rigid-bodies of the `i`-th island, sorted in increasing handle order.
+    pub fn islandBodies(&self, i: usize) -> Float64Array {
+        let bodies = self
+            .islands
+            .get(i)
+            .map(|island| &island.bodies[..])
+            .unwrap_or(&[]);
+        Float64Array::from(bodies)
+    }
+}
+
+fn find_root(parents: &mut [usize], mut i: usize) -> usize {
+    while parents[i] != i {
+        parents[i] = parents[parents[i]];
+        i = parents[i];
+    }
+    i
+}
+
+fn union(parents: &mut [usize], i: usize, j: usize) {
+    let root_i = find_root(parents, i);
+    let root_j = find_root(parents, j);
+    // Keep the smallest index as the root so islands are ordered by their first rigid-body.
+    parents[root_i.max(root_j)] = root_i.min(root_j);
+}
+
+/// Groups the dynamic rigid-bodies linked together by active contacts or joints, and computes
+/// a checksum for each group.
+fn compute_islands(
+    bodies: &RawRigidBodySet,
+    colliders: &RawColliderSet,
+    impulse_joints: &RawImpulseJointSet,
+    multibody_joints: &RawMultibodyJointSet,
+    narrow_phase: &RawNarrowPhase,
+) -> Vec<IslandChecksum> {
+    let dynamic_bodies: Vec<_> = bodies.0.iter().filter(|(_, rb)| rb.is_dynamic()).collect();
+    let ids: HashMap<_, _> = dynamic_bodies
+        .iter()
+        .enumerate()
+        .map(|(id, (handle, _))| (*handle, id))
+        .collect();
+    let mut parents: Vec<_> = (0..dynamic_bodies.len()).collect();
+    let mut link = |body1: Option<RigidBodyHandle>, body2: Option<RigidBodyHandle>| {
+        let id1 = body1.and_then(|h| ids.get(&h));
+        let id2 = body2.and_then(|h| ids.get(&h));
+        if let (Some(id1), Some(id2)) = (id1, id2) {
+            union(&mut parents, *id1, *id2);
+        }
+    };
+
+    for pair in narrow_phase.0.contact_pairs() {
+        if pair.has_any_active_contact {
+            let body1 = colliders.0.get(pair.collider1).and_then(|co| co.parent());
+            let body2 = colliders.0.get(pair.collider2).and_then(|co| co.parent());
+            link(body1, body2);
+        }
+    }
+
+    for (_, joint) in impulse_joints.0.iter() {
+        link(Some(joint.body1), Some(joint.body2));
+    }
+
+    for (handle, _) in &dynamic_bodies {
+        for (body1, body2, _) in multibody_joints.0.attached_joints(*handle) {
+            link(Some(body1), Some(body2));
+        }
+    }
+
+    // Group the rigid-bodies by island, in handle order.
+    let mut island_ids = HashMap::new();
+    let mut islands: Vec<(Vec<FlatHandle>, Fnv1a)> = vec![];
+
+    for (id, (handle, rb)) in dynamic_bodies.iter().enumerate() {
+        let root = find_root(&mut parents, id);
+        let island_id = *island_ids.entry(root).or_insert_with(|| {
+            islands.push((vec![], Fnv1a::new()));
+            islands.len() - 1
+        });
+        let (island_bodies, hasher) = &mut islands[island_id];
+        island_bodies.push(utils::flat_handle(handle.0));
+        hasher.write_body(*handle, rb);
+        hasher.write_body_colliders(rb, &colliders.0);
+    }
+
+    islands
+        .into_iter()
+        .map(|(bodies, hasher)| IslandChecksum {
+            bodies,
+            hash: hasher.finish(),
+        })
+        .collect()
+}
@@ -1,21 +1,191 @@
+use crate::dynamics::BrokenJoint;
 use crate::math::RawVector;
 use crate::utils;
 use crate::utils::FlatHandle;
-use rapier::crossbeam::channel::Receiver;
-use rapier::geometry::{CollisionEvent, ContactForceEvent};
-use rapier::pipeline::ChannelEventCollector;
+use rapier::crossbeam::channel::{Receiver, Sender, TrySendError};
+use rapier::dynamics::{RigidBodyHandle, RigidBodySet};
+use rapier::geometry::{
+    ColliderSet, CollisionEvent, CollisionEventFlags, ContactForceEvent, ContactPair,
+};
+use rapier::math::{Point, Real, Vector};
+use rapier::pipeline::EventHandler;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use wasm_bindgen::prelude::*;
 
+/// The deepest contact point between two colliders at the time their collision started.
+#[derive(Copy, Clone, Debug)]
+struct CollisionContact {
+    point: Point<Real>,
+    normal: Vector<Real>,
+    relative_velocity: Vector<Real>,
+}
+
+impl CollisionContact {
+    fn from_contact_pair(
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        pair: &ContactPair,
+    ) -> Option<Self> {
+        let co1 = colliders.get(pair.collider1)?;
+        let co2 = colliders.get(pair.collider2)?;
+        let (manifold, contact) = pair
+            .manifolds
+            .iter()
+            .flat_map(|m| m.points.iter().map(move |pt| (m, pt)))
+            .min_by(|a, b| {
+                a.1.dist
+                    .partial_cmp(&b.1.dist)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })?;
+
+        let point = co1.position() * contact.local_p1;
+        let velocity_at_point = |parent: Option<RigidBodyHandle>| {
+            parent
+                .and_then(|h| bodies.get(h))
+                .map(|rb| rb.velocity_at_point(&point))
+                .unwrap_or_else(Vector::zeros)
+        };
+
+        Some(Self {
+            point,
+            normal: co1.position() * manifold.local_n1,
+            relative_velocity: velocity_at_point(co2.parent()) - velocity_at_point(co1.parent()),
+        })
+    }
+}
+
+/// A collision event, with the contact point recorded when the collision started.
+type CollisionEventWithContact = (CollisionEvent, Option<CollisionContact>);
+
+/// Collects the events generated by the physics engine into channels.
+///
+/// Unlike rapier’s `ChannelEventCollector`, this also records the deepest contact point
+/// of a collision when it starts, and never blocks when sending to a bounded channel:
+/// events that don’t fit are dropped and counted instead.
+pub(crate) struct EventCollector {
+    collision_event_sender: Sender<CollisionEventWithContact>,
+    contact_force_event_sender: Sender<ContactForceEvent>,
+    joint_broken_event_sender: Sender<BrokenJoint>,
+    dropped_events: AtomicUsize,
+}
+
+impl EventCollector {
+    fn send<T>(&self, sender: &Sender<T>, event: T) {
+        if let Err(TrySendError::Full(_)) = sender.try_send(event) {
+            self.dropped_events.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn handle_joint_broken_event(&self, joint: BrokenJoint) {
+        self.send(&self.joint_broken_event_sender, joint);
+    }
+}
+
+impl EventHandler for EventCollector {
+    fn handle_collision_event(
+        &self,
+        bodies: &RigidBodySet,
+        colliders: &ColliderSet,
+        event: CollisionEvent,
+        contact_pair: Option<&ContactPair>,
+    ) {
+        let contact = if event.started() {
+            contact_pair
+                .and_then(|pair| CollisionContact::from_contact_pair(bodies, colliders, pair))
+        } else {
+            None
+        };
+
+        self.send(&self.collision_event_sender, (event, contact));
+    }
+
+    fn handle_contact_force_event(
+        &self,
+        dt: Real,
+        _bodies: &RigidBodySet,
+        _colliders: &ColliderSet,
+        contact_pair: &ContactPair,
+        total_force_magnitude: Real,
+    ) {
+        let result = ContactForceEvent::from_contact_pair(dt, contact_pair, total_force_magnitude);
+        self.send(&self.contact_force_event_sender, result);
+    }
+}
+
 /// A structure responsible for collecting events generated
 /// by the physics engine.
 #[wasm_bindgen]
 pub struct RawEventQueue {
-    pub(crate) collector: ChannelEventCollector,
-    collision_events: Receiver<CollisionEvent>,
+    pub(crate) collector: EventCollector,
+    collision_events: Receiver<CollisionEventWithContact>,
     contact_force_events: Receiver<ContactForceEvent>,
+    joint_broken_events: Receiver<BrokenJoint>,
     pub(crate) auto_drain: bool,
 }
 
+#[wasm_bindgen]
+pub struct RawCollisionEvent(CollisionEvent, Option<CollisionContact>);
+
+#[wasm_bindgen]
+impl RawCollisionEvent {
+    /// The first collider involved in the collision.
+    pub fn collider1(&self) -> FlatHandle {
+        utils::flat_handle(self.0.collider1().0)
+    }
+
+    /// The second collider involved in the collision.
+    pub fn collider2(&self) -> FlatHandle {
+        utils::flat_handle(self.0.collider2().0)
+    }
+
+    /// Is this a collision start event (`true`) or stop event (`false`)?
+    pub fn started(&self) -> bool {
+        self.0.started()
+    }
+
+    /// Is at least one of the colliders involved in this collision a sensor?
+    pub fn sensor(&self) -> bool {
+        self.flags().contains(CollisionEventFlags::SENSOR)
+    }
+
+    /// Was this collision stopped because one of the colliders was removed?
+    pub fn removed(&self) -> bool {
+        self.flags().contains(CollisionEventFlags::REMOVED)
+    }
+
+    /// The world-space position, on the first collider, of the deepest contact point when
+    /// the collision started.
+    ///
+    /// Returns `undefined` for stop events and for collisions involving a sensor.
+    pub fn contactPoint(&self) -> Option<RawVector> {
+        self.1.map(|c| c.point.coords.into())
+    }
+
+    /// The world-space contact normal, pointing outward the first collider, when the collision
+    /// started.
+    ///
+    /// Returns `undefined` for stop events and for collisions involving a sensor.
+    pub fn contactNormal(&self) -> Option<RawVector> {
+        self.1.map(|c| c.normal.into())
+    }
+
+    /// The velocity of the second collider relative to the first collider at the contact point
+    /// when the collision started.
+    ///
+    /// Returns `undefined` for stop events and for collisions involving a sensor.
+    pub fn relativeVelocity(&self) -> Option<RawVector> {
+        self.1.map(|c| c.relative_velocity.into())
+    }
+}
+
+impl RawCollisionEvent {
+    fn flags(&self) -> CollisionEventFlags {
+        match self.0 {
+            CollisionEvent::Started(_, _, flags) | CollisionEvent::Stopped(_, _, flags) => flags,
+        }
+    }
+}
+
 #[wasm_bindgen]
 pub struct RawContactForceEvent(ContactForceEvent);
 
@@ -56,6 +226,37 @@ impl RawContactForceEvent {
     }
 }
 
+#[wasm_bindgen]
+pub struct RawJointBrokenEvent(BrokenJoint);
+
+#[wasm_bindgen]
+impl RawJointBrokenEvent {
+    /// The handle of the impulse joint that broke. It has already been removed from its set.
+    pub fn joint(&self) -> FlatHandle {
+        utils::flat_handle(self.0.handle.0)
+    }
+
+    /// The first rigid-body the joint was attached to.
+    pub fn body1(&self) -> FlatHandle {
+        utils::flat_handle(self.0.body1.0)
+    }
+
+    /// The second rigid-body the joint was attached to.
+    pub fn body2(&self) -> FlatHandle {
+        utils::flat_handle(self.0.body2.0)
+    }
+
+    /// The magnitude of the force applied by the joint during the timestep it broke.
+    pub fn force(&self) -> f32 {
+        self.0.force
+    }
+
+    /// The magnitude of the torque applied by the joint during the timestep it broke.
+    pub fn torque(&self) -> f32 {
+        self.0.torque
+    }
+}
+
 // #[wasm_bindgen]
 // /// The proximity state of a sensor collider and another collider.
 // pub enum RawIntersection {
@@ -78,51 +279,55 @@ impl RawEventQueue {
     /// RAM if no drain is performed.
     #[wasm_bindgen(constructor)]
     pub fn new(autoDrain: bool) -> Self {
-        let collision_channel = rapier::crossbeam::channel::unbounded();
-        let contact_force_channel = rapier::crossbeam::channel::unbounded();
-        let collector = ChannelEventCollector::new(collision_channel.0, contact_force_channel.0);
+        Self::from_channels(
+            rapier::crossbeam::channel::unbounded(),
+            rapier::crossbeam::channel::unbounded(),
+            rapier::crossbeam::channel::unbounded(),
+            autoDrain,
+        )
+    }
 
-        Self {
-            collector,
-            collision_events: collision_channel.1,
-            contact_force_events: contact_force_channel.1,
-            auto_drain: autoDrain,
+    /// Creates a new event collector that can hold at most `capacity` collision events,
+    /// `capacity` contact force events, and `capacity` joint broken events.
+    ///
+    /// Events generated while the collector is full are dropped. Their number can be
+    /// read with `numDroppedEvents`.
+    ///
+    /// # Parameters
+    /// - `autoDrain`: if true, the collector will be automatically drained before each `world.step(collector)`.
+    /// - `capacity`: the maximum number of events of each kind kept in memory. Throws an error
+    ///   if it is 0, since a collector without capacity would drop every event.
+    pub fn withCapacity(autoDrain: bool, capacity: usize) -> Result<RawEventQueue, JsValue> {
+        if capacity == 0 {
+            return Err(js_sys::Error::new(
+                "the capacity of an event queue must be greater than 0",
+            )
+            .into());
         }
+
+        Ok(Self::from_channels(
+            rapier::crossbeam::channel::bounded(capacity),
+            rapier::crossbeam::channel::bounded(capacity),
+            rapier::crossbeam::channel::bounded(capacity),
+            autoDrain,
+        ))
+    }
+
+    /// The total number of events that were dropped because this collector was full.
+    pub fn numDroppedEvents(&self) -> usize {
+        self.collector.dropped_events.load(Ordering::Relaxed)
     }
 
     /// Applies the given javascript closure on each collision event of this collector, then clear
     /// the internal collision event buffer.
     ///
     /// # Parameters
-    /// - `f(handle1, handle2, started)`:  JavaScript closure applied to each collision event. The
-    /// closure should take three arguments: two integers representing the handles of the colliders
-    /// involved in the collision, and a boolean indicating if the collision started (true) or stopped
-    /// (false).
+    /// - `f(event)`:  JavaScript closure applied to each collision event. The closure should take
+    /// a single `RawCollisionEvent` argument.
     pub fn drainCollisionEvents(&mut self, f: &js_sys::Function) {
         let this = JsValue::null();
-        while let Ok(event) = self.collision_events.try_recv() {
-            match event {
-                CollisionEvent::Started(co1, co2, _) => {
-                    let h1 = utils::flat_handle(co1.0);
-                    let h2 = utils::flat_handle(co2.0);
-                    let _ = f.call3(
-                        &this,
-                        &JsValue::from(h1),
-                        &JsValue::from(h2),
-                        &JsValue::from_bool(true),
-                    );
-                }
-                CollisionEvent::Stopped(co1, co2, _) => {
-                    let h1 = utils::flat_handle(co1.0);
-                    let h2 = utils::flat_handle(co2.0);
-                    let _ = f.call3(
-                        &this,
-                        &JsValue::from(h1),
-                        &JsValue::from(h2),
-                        &JsValue::from_bool(false),
-                    );
-                }
-            }
+        while let Ok((event, contact)) = self.collision_events.try_recv() {
+            let _ = f.call1(&this, &JsValue::from(RawCollisionEvent(event, contact)));
         }
     }
 
@@ -133,8 +338,50 @@ impl RawEventQueue {
         }
     }
 
+    /// Applies the given javascript closure on each joint broken event of this collector, then clear
+    /// the internal joint broken event buffer.
+    ///
+    /// # Parameters
+    /// - `f(event)`:  JavaScript closure applied to each joint broken event. The closure should take
+    /// a single `RawJointBrokenEvent` argument.
+    pub fn drainJointBrokenEvents(&mut self, f: &js_sys::Function) {
+        let this = JsValue::null();
+        while let Ok(event) = self.joint_broken_events.try_recv() {
+            let _ = f.call1(&this, &JsValue::from(RawJointBrokenEvent(event)));
+        }
+    }
+
     /// Removes all events contained by this collector.
     pub fn clear(&self) {
-        while let Ok(_) = self.collision_events.try_recv() {}
+        while self.collision_events.try_recv().is_ok() {}
+        while self.contact_force_events.try_recv().is_ok() {}
+        while self.joint_broken_events.try_recv().is_ok() {}
+    }
+}
+
+impl RawEventQueue {
+    fn from_channels(
+        collision_channel: (
+            Sender<CollisionEventWithContact>,
+            Receiver<CollisionEventWithContact>,
+        ),
+        contact_force_channel: (Sender<ContactForceEvent>, Receiver<ContactForceEvent>),
+        joint_broken_channel: (Sender<BrokenJoint>, Receiver<BrokenJoint>),
+        auto_drain: bool,
+    ) -> Self {
+        let collector = EventCollector {
+            collision_event_sender: collision_channel.0,
+            contact_force_event_sender: contact_force_channel.0,
+            joint_broken_event_sender: joint_broken_channel.0,
+            dropped_events: AtomicUsize::new(0),
+        };
+
+        Self {
+            collector,
+            collision_events: collision_channel.1,
+            contact_force_events: contact_force_channel.1,
+            joint_broken_events: joint_broken_channel.1,
+            auto_drain,
+        }
     }
 }
@@ -1,13 +1,19 @@
+pub use self::checksum::*;
 pub use self::debug_render_pipeline::*;
 pub use self::event_queue::*;
 pub use self::physics_hooks::*;
 pub use self::physics_pipeline::*;
 pub use self::query_pipeline::*;
 pub use self::serialization_pipeline::*;
+#[cfg(feature = "dim3")]
+pub use self::urdf_loader::*;
 
+mod checksum;
 mod debug_render_pipeline;
 mod event_queue;
 mod physics_hooks;
 mod physics_pipeline;
 mod query_pipeline;
 mod serialization_pipeline;
+#[cfg(feature = "dim3")]
+mod urdf_loader;
@@ -1,13 +1,18 @@
-use crate::utils;
-use rapier::geometry::SolverFlags;
+use crate::math::RawVector;
+use crate::utils::{self, FlatHandle};
+use rapier::dynamics::RigidBodyHandle;
+use rapier::geometry::{ColliderHandle, SolverContact, SolverFlags};
+use rapier::math::{Real, Vector};
 use rapier::pipeline::{ContactModificationContext, PairFilterContext, PhysicsHooks};
+use std::cell::RefCell;
+use std::rc::Rc;
 use wasm_bindgen::prelude::*;
 
 pub struct RawPhysicsHooks {
     pub this: js_sys::Object,
     pub filter_contact_pair: js_sys::Function,
     pub filter_intersection_pair: js_sys::Function,
-    // pub modify_solver_contacts: &'a js_sys::Function,
+    pub modify_solver_contacts: js_sys::Function,
 }
 
 #[wasm_bindgen]
@@ -66,145 +71,171 @@ impl PhysicsHooks for RawPhysicsHooks {
             .unwrap_or(false)
     }
 
-    fn modify_solver_contacts(&self, _ctxt: &mut ContactModificationContext) {}
+    fn modify_solver_contacts(&self, ctxt: &mut ContactModificationContext) {
+        let state = Rc::new(RefCell::new(ContactModificationState {
+            collider1: ctxt.collider1,
+            collider2: ctxt.collider2,
+            rigid_body1: ctxt.rigid_body1,
+            rigid_body2: ctxt.rigid_body2,
+            normal: *ctxt.normal,
+            user_data: *ctxt.user_data,
+            solver_contacts: std::mem::take(ctxt.solver_contacts),
+        }));
+
+        let _ = self.modify_solver_contacts.call1(
+            &self.this,
+            &JsValue::from(RawContactModificationContext(state.clone())),
+        );
+
+        // Write the (possibly modified) data back, even if the JS object is still alive.
+        let mut state = state.borrow_mut();
+        *ctxt.normal = state.normal;
+        *ctxt.user_data = state.user_data;
+        std::mem::swap(ctxt.solver_contacts, &mut state.solver_contacts);
+    }
 }
 
-/* NOTE: the following is an attempt to make contact modification work.
- *
-#[wasm_bindgen]
-#[derive(Copy, Clone, Debug)]
-pub struct RawContactManifold(*const ContactManifold);
-pub struct RawSolverContact(*const SolverContact);
+struct ContactModificationState {
+    collider1: ColliderHandle,
+    collider2: ColliderHandle,
+    rigid_body1: Option<RigidBodyHandle>,
+    rigid_body2: Option<RigidBodyHandle>,
+    normal: Vector<Real>,
+    user_data: u32,
+    solver_contacts: Vec<SolverContact>,
+}
 
+/// The contact pair given to the `modifySolverContacts` physics hook.
+///
+/// Instead of pointing directly into the narrow-phase, this holds a copy of the solver contacts,
+/// normal, and user-data of the pair, which are written back once the hook returns. This keeps
+/// it memory-safe even if the JS side doesn’t free it before the next timestep.
 #[wasm_bindgen]
-pub struct RawContactModificationContext {
-    pub collider1: u32,
-    pub collider2: u32,
-    pub rigid_body1: Option<u32>,
-    pub rigid_body2: Option<u32>,
-    pub manifold: *const ContactManifold,
-    pub solver_contacts: *mut Vec<SolverContact>,
-    normal: *mut Vector<Real>,
-    user_data: *mut u32,
-}
+pub struct RawContactModificationContext(Rc<RefCell<ContactModificationState>>);
 
 #[wasm_bindgen]
 impl RawContactModificationContext {
-    pub fn collider1(&self) -> u32 {
-        self.collider1
+    pub fn collider1(&self) -> FlatHandle {
+        utils::flat_handle(self.0.borrow().collider1.0)
+    }
+
+    pub fn collider2(&self) -> FlatHandle {
+        utils::flat_handle(self.0.borrow().collider2.0)
     }
 
-    pub fn collider2(&self) -> u32 {
-        self.collider2
+    pub fn rigid_body1(&self) -> Option<FlatHandle> {
+        self.0.borrow().rigid_body1.map(|h| utils::flat_handle(h.0))
+    }
+
+    pub fn rigid_body2(&self) -> Option<FlatHandle> {
+        self.0.borrow().rigid_body2.map(|h| utils::flat_handle(h.0))
     }
 
     #[wasm_bindgen(getter)]
     pub fn normal(&self) -> RawVector {
-        unsafe { RawVector(*self.normal) }
+        self.0.borrow().normal.into()
     }
 
     #[wasm_bindgen(setter)]
-    pub fn set_normal(&mut self, normal: RawVector) {
-        unsafe {
-            *self.normal = normal.0;
-        }
+    pub fn set_normal(&mut self, normal: &RawVector) {
+        self.0.borrow_mut().normal = normal.0;
     }
 
     #[wasm_bindgen(getter)]
     pub fn user_data(&self) -> u32 {
-        unsafe { *self.user_data }
+        self.0.borrow().user_data
     }
 
     #[wasm_bindgen(setter)]
     pub fn set_user_data(&mut self, user_data: u32) {
-        unsafe {
-            *self.user_data = user_data;
-        }
+        self.0.borrow_mut().user_data = user_data;
     }
 
     pub fn num_solver_contacts(&self) -> usize {
-        unsafe { (*self.solver_contacts).len() }
+        self.0.borrow().solver_contacts.len()
     }
 
     pub fn clear_solver_contacts(&mut self) {
-        unsafe { (*self.solver_contacts).clear() }
+        self.0.borrow_mut().solver_contacts.clear()
     }
 
     pub fn remove_solver_contact(&mut self, i: usize) {
-        unsafe {
-            if i < self.num_solver_contacts() {
-                (*self.solver_contacts).swap_remove(i);
-            }
+        let mut state = self.0.borrow_mut();
+        if i < state.solver_contacts.len() {
+            state.solver_contacts.swap_remove(i);
         }
     }
 
     pub fn solver_contact_point(&self, i: usize) -> Option<RawVector> {
-        unsafe {
-            (*self.solver_contacts)
-                .get(i)
-                .map(|c| c.point.coords.into())
-        }
+        self.0
+            .borrow()
+            .solver_contacts
+            .get(i)
+            .map(|c| c.point.coords.into())
     }
 
     pub fn set_solver_contact_point(&mut self, i: usize, pt: &RawVector) {
-        unsafe {
-            if let Some(c) = (*self.solver_contacts).get_mut(i) {
-                c.point = pt.0.into()
-            }
+        if let Some(c) = self.0.borrow_mut().solver_contacts.get_mut(i) {
+            c.point = pt.0.into()
         }
     }
 
     pub fn solver_contact_dist(&self, i: usize) -> Real {
-        unsafe {
-            (*self.solver_contacts)
-                .get(i)
-                .map(|c| c.dist)
-                .unwrap_or(0.0)
-        }
+        self.0
+            .borrow()
+            .solver_contacts
+            .get(i)
+            .map(|c| c.dist)
+            .unwrap_or(0.0)
     }
 
     pub fn set_solver_contact_dist(&mut self, i: usize, dist: Real) {
-        unsafe {
-            if let Some(c) = (*self.solver_contacts).get_mut(i) {
-                c.dist = dist
-            }
+        if let Some(c) = self.0.borrow_mut().solver_contacts.get_mut(i) {
+            c.dist = dist
         }
     }
 
     pub fn solver_contact_friction(&self, i: usize) -> Real {
-        unsafe { (*self.solver_contacts)[i].friction }
+        self.0
+            .borrow()
+            .solver_contacts
+            .get(i)
+            .map(|c| c.friction)
+            .unwrap_or(0.0)
     }
 
     pub fn set_solver_contact_friction(&mut self, i: usize, friction: Real) {
-        unsafe {
-            if let Some(c) = (*self.solver_contacts).get_mut(i) {
-                c.friction = friction
-            }
+        if let Some(c) = self.0.borrow_mut().solver_contacts.get_mut(i) {
+            c.friction = friction
         }
     }
 
     pub fn solver_contact_restitution(&self, i: usize) -> Real {
-        unsafe { (*self.solver_contacts)[i].restitution }
+        self.0
+            .borrow()
+            .solver_contacts
+            .get(i)
+            .map(|c| c.restitution)
+            .unwrap_or(0.0)
     }
 
     pub fn set_solver_contact_restitution(&mut self, i: usize, restitution: Real) {
-        unsafe {
-            if let Some(c) = (*self.solver_contacts).get_mut(i) {
-                c.restitution = restitution
-            }
+        if let Some(c) = self.0.borrow_mut().solver_contacts.get_mut(i) {
+            c.restitution = restitution
         }
     }
 
-    pub fn solver_contact_tangent_velocity(&self, i: usize) -> RawVector {
-        unsafe { (*self.solver_contacts)[i].tangent_velocity.into() }
+    pub fn solver_contact_tangent_velocity(&self, i: usize) -> Option<RawVector> {
+        self.0
+            .borrow()
+            .solver_contacts
+            .get(i)
+            .map(|c| c.tangent_velocity.into())
     }
 
     pub fn set_solver_contact_tangent_velocity(&mut self, i: usize, vel: &RawVector) {
-        unsafe {
-            if let Some(c) = (*self.solver_contacts).get_mut(i) {
-                c.tangent_velocity = vel.0.into()
-            }
+        if let Some(c) = self.0.borrow_mut().solver_contacts.get_mut(i) {
+            c.tangent_velocity = vel.0
         }
     }
 }
-*/
@@ -31,6 +31,9 @@ impl RawPhysicsPipeline {
         articulations: &mut RawMultibodyJointSet,
         ccd_solver: &mut RawCCDSolver,
     ) {
+        joints.sync_disabled_bodies(bodies);
+        joints.apply_distance_joints(bodies, integrationParameters.0.dt);
+
         self.0.step(
             &gravity.0,
             &integrationParameters.0,
@@ -45,6 +48,8 @@ impl RawPhysicsPipeline {
             &(),
             &(),
         );
+
+        joints.break_overloaded_joints(integrationParameters.0.dt);
     }
 
     pub fn stepWithEvents(
@@ -63,6 +68,7 @@ impl RawPhysicsPipeline {
         hookObject: js_sys::Object,
         hookFilterContactPair: js_sys::Function,
         hookFilterIntersectionPair: js_sys::Function,
+        hookModifySolverContacts: js_sys::Function,
     ) {
         if eventQueue.auto_drain {
             eventQueue.clear();
@@ -72,8 +78,12 @@ impl RawPhysicsPipeline {
             this: hookObject,
             filter_contact_pair: hookFilterContactPair,
             filter_intersection_pair: hookFilterIntersectionPair,
+            modify_solver_contacts: hookModifySolverContacts,
         };
 
+        joints.sync_disabled_bodies(bodies);
+        joints.apply_distance_joints(bodies, integrationParameters.0.dt);
+
         self.0.step(
             &gravity.0,
             &integrationParameters.0,
@@ -88,5 +98,9 @@ impl RawPhysicsPipeline {
             &hooks,
             &eventQueue.collector,
         );
+
+        for joint in joints.break_overloaded_joints(integrationParameters.0.dt) {
+            eventQueue.collector.handle_joint_broken_event(joint);
+        }
     }
 }
@@ -44,7 +44,7 @@ impl RawQueryPipeline {
         filter_exclude_rigid_body: Option<FlatHandle>,
         filter_predicate: &js_sys::Function,
     ) -> Option<RawRayColliderToi> {
-        let (handle, toi) = utils::with_filter(filter_predicate, |predicate| {
+        let (handle, toi) = utils::with_filter(colliders, filter_predicate, |predicate| {
             let query_filter = QueryFilter {
                 flags: QueryFilterFlags::from_bits(filter_flags)
                     .unwrap_or(QueryFilterFlags::empty()),
@@ -76,7 +76,7 @@ impl RawQueryPipeline {
         filter_exclude_rigid_body: Option<FlatHandle>,
         filter_predicate: &js_sys::Function,
     ) -> Option<RawRayColliderIntersection> {
-        let (handle, inter) = utils::with_filter(filter_predicate, |predicate| {
+        let (handle, inter) = utils::with_filter(colliders, filter_predicate, |predicate| {
             let query_filter = QueryFilter {
                 flags: QueryFilterFlags::from_bits(filter_flags)
                     .unwrap_or(QueryFilterFlags::empty()),
@@ -116,7 +116,7 @@ impl RawQueryPipeline {
         filter_exclude_rigid_body: Option<FlatHandle>,
         filter_predicate: &js_sys::Function,
     ) {
-        utils::with_filter(filter_predicate, |predicate| {
+        utils::with_filter(colliders, filter_predicate, |predicate| {
             let query_filter = QueryFilter {
                 flags: QueryFilterFlags::from_bits(filter_flags)
                     .unwrap_or(QueryFilterFlags::empty()),
@@ -160,7 +160,7 @@ impl RawQueryPipeline {
         filter_exclude_rigid_body: Option<FlatHandle>,
         filter_predicate: &js_sys::Function,
     ) -> Option<FlatHandle> {
-        utils::with_filter(filter_predicate, |predicate| {
+        utils::with_filter(colliders, filter_predicate, |predicate| {
             let query_filter = QueryFilter {
                 flags: QueryFilterFlags::from_bits(filter_flags)
                     .unwrap_or(QueryFilterFlags::empty()),
@@ -189,7 +189,7 @@ impl RawQueryPipeline {
         filter_exclude_rigid_body: Option<FlatHandle>,
         filter_predicate: &js_sys::Function,
     ) -> Option<RawPointColliderProjection> {
-        utils::with_filter(filter_predicate, |predicate| {
+        utils::with_filter(colliders, filter_predicate, |predicate| {
             let query_filter = QueryFilter {
                 flags: QueryFilterFlags::from_bits(filter_flags)
                     .unwrap_or(QueryFilterFlags::empty()),
@@ -226,7 +226,7 @@ impl RawQueryPipeline {
         filter_exclude_rigid_body: Option<FlatHandle>,
         filter_predicate: &js_sys::Function,
     ) -> Option<RawPointColliderProjection> {
-        utils::with_filter(filter_predicate, |predicate| {
+        utils::with_filter(colliders, filter_predicate, |predicate| {
             let query_filter = QueryFilter {
                 flags: QueryFilterFlags::from_bits(filter_flags)
                     .unwrap_or(QueryFilterFlags::empty()),
@@ -264,7 +264,7 @@ impl RawQueryPipeline {
         filter_exclude_rigid_body: Option<FlatHandle>,
         filter_predicate: &js_sys::Function,
     ) {
-        utils::with_filter(filter_predicate, |predicate| {
+        utils::with_filter(colliders, filter_predicate, |predicate| {
             let query_filter = QueryFilter {
                 flags: QueryFilterFlags::from_bits(filter_flags)
                     .unwrap_or(QueryFilterFlags::empty()),
@@ -307,7 +307,7 @@ impl RawQueryPipeline {
         filter_exclude_rigid_body: Option<FlatHandle>,
         filter_predicate: &js_sys::Function,
     ) -> Option<RawShapeColliderTOI> {
-        utils::with_filter(filter_predicate, |predicate| {
+        utils::with_filter(colliders, filter_predicate, |predicate| {
             let query_filter = QueryFilter {
                 flags: QueryFilterFlags::from_bits(filter_flags)
                     .unwrap_or(QueryFilterFlags::empty()),
@@ -348,7 +348,7 @@ impl RawQueryPipeline {
         filter_exclude_rigid_body: Option<FlatHandle>,
         filter_predicate: &js_sys::Function,
     ) {
-        utils::with_filter(filter_predicate, |predicate| {
+        utils::with_filter(colliders, filter_predicate, |predicate| {
             let query_filter = QueryFilter {
                 flags: QueryFilterFlags::from_bits(filter_flags)
                     .unwrap_or(QueryFilterFlags::empty()),
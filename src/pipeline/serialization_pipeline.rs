@@ -1,28 +1,300 @@
 use crate::dynamics::{
-    RawImpulseJointSet, RawIntegrationParameters, RawIslandManager, RawMultibodyJointSet,
-    RawRigidBodySet,
+    BreakThresholds, DisabledBody, DistanceJoint, RawImpulseJointSet, RawIntegrationParameters,
+    RawIslandManager, RawMultibodyJointSet, RawRigidBodySet, UserForces,
 };
-use crate::geometry::{RawBroadPhase, RawColliderSet, RawNarrowPhase};
-use crate::math::RawVector;
-use js_sys::Uint8Array;
+use crate::geometry::{DisabledCollider, RawBroadPhase, RawColliderSet, RawNarrowPhase};
+use crate::math::{RawRotation, RawVector};
+use crate::utils::{self, FlatHandle};
+use js_sys::{Float64Array, Uint8Array};
 use rapier::dynamics::{
-    ImpulseJointSet, IntegrationParameters, IslandManager, MultibodyJointSet, RigidBodySet,
+    GenericJoint, ImpulseJoint, ImpulseJointHandle, ImpulseJointSet, IntegrationParameters,
+    IslandManager, MultibodyJointSet, RigidBody, RigidBodyHandle, RigidBodySet,
 };
-use rapier::geometry::{BroadPhase, ColliderSet, NarrowPhase};
-use rapier::math::Vector;
+use rapier::geometry::{BroadPhase, Collider, ColliderHandle, ColliderSet, NarrowPhase};
+use rapier::math::{AngVector, Isometry, Real, Vector};
+use serde::de::{DeserializeOwned, IgnoredAny};
+use std::collections::{HashMap, HashSet};
+use std::convert::TryInto;
+use std::fmt;
 use wasm_bindgen::prelude::*;
 
+/// Bytes every snapshot generated by the serialization pipeline starts with.
+const SNAPSHOT_MAGIC: &[u8; 4] = b"RPRS";
+/// Version of the snapshot layout.
+///
+/// This must be incremented whenever the layout of the header or of the serialized data changes.
+const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+#[cfg(feature = "dim2")]
+const SNAPSHOT_DIM: u8 = 2;
+#[cfg(feature = "dim3")]
+const SNAPSHOT_DIM: u8 = 3;
+
+/// The type of data contained by a snapshot.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+enum SnapshotKind {
+    World,
+    BodyStates,
+    Prefab,
+}
+
+impl fmt::Display for SnapshotKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SnapshotKind::World => write!(f, "world"),
+            SnapshotKind::BodyStates => write!(f, "rigid-body states"),
+            SnapshotKind::Prefab => write!(f, "prefab"),
+        }
+    }
+}
+
+/// Describes the content of a snapshot.
+///
+/// It is serialized right after the magic bytes and the format version.
+#[derive(Serialize, Deserialize)]
+struct SnapshotHeader {
+    dim: u8,
+    kind: SnapshotKind,
+    crate_version: String,
+    rapier_version: String,
+}
+
+/// The minor version of rapier the serialized data is generated by, e.g. `0.16`.
+///
+/// The serialized data is only compatible with the same rapier minor version.
+fn rapier_minor_version() -> &'static str {
+    rapier::VERSION
+        .rsplit_once('.')
+        .map_or(rapier::VERSION, |(minor, _)| minor)
+}
+
+/// Errors that can occur while writing or reading a snapshot.
+#[derive(Debug)]
+enum SnapshotError {
+    MissingHeader,
+    UnsupportedFormatVersion(u32),
+    DimensionMismatch(u8),
+    RapierVersionMismatch {
+        crate_version: String,
+        rapier_version: String,
+    },
+    KindMismatch {
+        expected: SnapshotKind,
+        found: SnapshotKind,
+    },
+    Serialization(String),
+    Deserialization(String),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SnapshotError::MissingHeader => write!(
+                f,
+                "invalid snapshot: the data doesn’t start with a rapier.js snapshot header"
+            ),
+            SnapshotError::UnsupportedFormatVersion(version) => write!(
+                f,
+                "unsupported snapshot format version {} (expected version {})",
+                version, SNAPSHOT_FORMAT_VERSION
+            ),
+            SnapshotError::DimensionMismatch(dim) => write!(
+                f,
+                "cannot load a {}D snapshot with the {}D version of rapier.js",
+                dim, SNAPSHOT_DIM
+            ),
+            SnapshotError::RapierVersionMismatch {
+                crate_version,
+                rapier_version,
+            } => write!(
+                f,
+                "incompatible snapshot: it was created by rapier.js {} (rapier {}) but this is rapier.js {} (rapier {})",
+                crate_version,
+                rapier_version,
+                env!("CARGO_PKG_VERSION"),
+                rapier_minor_version()
+            ),
+            SnapshotError::KindMismatch { expected, found } => write!(
+                f,
+                "expected a snapshot of {}, found a snapshot of {}",
+                expected, found
+            ),
+            SnapshotError::Serialization(e) => write!(f, "failed to serialize the snapshot: {}", e),
+            SnapshotError::Deserialization(e) => {
+                write!(f, "corrupted snapshot: failed to deserialize its data: {}", e)
+            }
+        }
+    }
+}
+
+impl From<SnapshotError> for JsValue {
+    fn from(e: SnapshotError) -> JsValue {
+        js_sys::Error::new(&e.to_string()).into()
+    }
+}
+
+impl SnapshotHeader {
+    fn new(kind: SnapshotKind) -> Self {
+        Self {
+            dim: SNAPSHOT_DIM,
+            kind,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            rapier_version: rapier_minor_version().to_string(),
+        }
+    }
+
+    /// Checks that a snapshot with this header contains data of the given kind that can
+    /// be loaded by this version of the library.
+    fn check(self, kind: SnapshotKind) -> Result<(), SnapshotError> {
+        if self.dim != SNAPSHOT_DIM {
+            return Err(SnapshotError::DimensionMismatch(self.dim));
+        }
+
+        if self.rapier_version != rapier_minor_version() {
+            return Err(SnapshotError::RapierVersionMismatch {
+                crate_version: self.crate_version,
+                rapier_version: self.rapier_version,
+            });
+        }
+
+        if self.kind != kind {
+            return Err(SnapshotError::KindMismatch {
+                expected: kind,
+                found: self.kind,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Serializes `data` preceded by a snapshot header.
+fn write_snapshot<T: serde::Serialize>(
+    kind: SnapshotKind,
+    data: &T,
+) -> Result<Uint8Array, SnapshotError> {
+    let to_error = |e: bincode::Error| SnapshotError::Serialization(e.to_string());
+    let mut snap = SNAPSHOT_MAGIC.to_vec();
+    snap.extend_from_slice(&SNAPSHOT_FORMAT_VERSION.to_le_bytes());
+    bincode::serialize_into(&mut snap, &SnapshotHeader::new(kind)).map_err(to_error)?;
+    bincode::serialize_into(&mut snap, data).map_err(to_error)?;
+    Ok(Uint8Array::from(&snap[..]))
+}
+
+/// Checks the header of a snapshot generated by `write_snapshot`, and deserializes its data.
+fn read_snapshot<T: DeserializeOwned>(kind: SnapshotKind, data: &[u8]) -> Result<T, SnapshotError> {
+    let to_error = |e: bincode::Error| SnapshotError::Deserialization(e.to_string());
+    let data = data
+        .strip_prefix(&SNAPSHOT_MAGIC[..])
+        .ok_or(SnapshotError::MissingHeader)?;
+
+    if data.len() < 4 {
+        return Err(SnapshotError::MissingHeader);
+    }
+
+    let (version, mut data) = data.split_at(4);
+    let version = u32::from_le_bytes(version.try_into().unwrap());
+
+    if version != SNAPSHOT_FORMAT_VERSION {
+        return Err(SnapshotError::UnsupportedFormatVersion(version));
+    }
+
+    let header: SnapshotHeader = bincode::deserialize_from(&mut data).map_err(to_error)?;
+    header.check(kind)?;
+    bincode::deserialize(data).map_err(to_error)
+}
+
+/// The layout of a snapshot serialized as JSON.
+#[derive(Serialize, Deserialize)]
+struct JsonSnapshot<T> {
+    format_version: u32,
+    header: SnapshotHeader,
+    data: T,
+}
+
+/// Serializes `data` and a snapshot header into a pretty-printed JSON string.
+fn write_json_snapshot<T: serde::Serialize>(
+    kind: SnapshotKind,
+    data: &T,
+) -> Result<String, SnapshotError> {
+    let snap = JsonSnapshot {
+        format_version: SNAPSHOT_FORMAT_VERSION,
+        header: SnapshotHeader::new(kind),
+        data,
+    };
+    serde_json::to_string_pretty(&snap).map_err(|e| SnapshotError::Serialization(e.to_string()))
+}
+
+/// Checks the header of a snapshot generated by `write_json_snapshot`, and deserializes its data.
+fn read_json_snapshot<T: DeserializeOwned>(
+    kind: SnapshotKind,
+    data: &str,
+) -> Result<T, SnapshotError> {
+    let to_error = |e: serde_json::Error| SnapshotError::Deserialization(e.to_string());
+    // Check the header before attempting to deserialize the data.
+    let snap: JsonSnapshot<IgnoredAny> = serde_json::from_str(data).map_err(to_error)?;
+
+    if snap.format_version != SNAPSHOT_FORMAT_VERSION {
+        return Err(SnapshotError::UnsupportedFormatVersion(snap.format_version));
+    }
+
+    snap.header.check(kind)?;
+    let snap: JsonSnapshot<T> = serde_json::from_str(data).map_err(to_error)?;
+    Ok(snap.data)
+}
+
+/// Serializes a value as-is with binary serializers, and as opaque bincode bytes with
+/// human-readable ones.
+///
+/// This is needed for the structures containing maps with keys that can’t be represented
+/// in formats like JSON.
+mod opaque {
+    use serde::de::{DeserializeOwned, Error as _};
+    use serde::ser::Error as _;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer, T: Serialize>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let bytes = bincode::serialize(value).map_err(S::Error::custom)?;
+            serializer.serialize_bytes(&bytes)
+        } else {
+            value.serialize(serializer)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: DeserializeOwned>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        if deserializer.is_human_readable() {
+            let bytes = Vec::<u8>::deserialize(deserializer)?;
+            bincode::deserialize(&bytes).map_err(D::Error::custom)
+        } else {
+            T::deserialize(deserializer)
+        }
+    }
+}
+
 #[derive(Serialize)]
 struct SerializableWorld<'a> {
     gravity: &'a Vector<f32>,
     integration_parameters: &'a IntegrationParameters,
     islands: &'a IslandManager,
+    #[serde(serialize_with = "opaque::serialize")]
     broad_phase: &'a BroadPhase,
+    #[serde(serialize_with = "opaque::serialize")]
     narrow_phase: &'a NarrowPhase,
     bodies: &'a RigidBodySet,
     colliders: &'a ColliderSet,
     impulse_joints: &'a ImpulseJointSet,
     multibody_joints: &'a MultibodyJointSet,
+    disabled_bodies: Vec<(RigidBodyHandle, DisabledBody)>,
+    user_forces: Vec<(RigidBodyHandle, UserForces)>,
+    disabled_colliders: Vec<(ColliderHandle, DisabledCollider)>,
+    neutralized_joints: Vec<(ImpulseJointHandle, GenericJoint)>,
+    break_thresholds: Vec<(ImpulseJointHandle, BreakThresholds)>,
+    distance_joints: Vec<(ImpulseJointHandle, DistanceJoint)>,
 }
 
 #[derive(Deserialize)]
@@ -30,12 +302,119 @@ struct DeserializableWorld {
     gravity: Vector<f32>,
     integration_parameters: IntegrationParameters,
     islands: IslandManager,
+    #[serde(deserialize_with = "opaque::deserialize")]
     broad_phase: BroadPhase,
+    #[serde(deserialize_with = "opaque::deserialize")]
     narrow_phase: NarrowPhase,
     bodies: RigidBodySet,
     colliders: ColliderSet,
     impulse_joints: ImpulseJointSet,
     multibody_joints: MultibodyJointSet,
+    disabled_bodies: Vec<(RigidBodyHandle, DisabledBody)>,
+    user_forces: Vec<(RigidBodyHandle, UserForces)>,
+    disabled_colliders: Vec<(ColliderHandle, DisabledCollider)>,
+    neutralized_joints: Vec<(ImpulseJointHandle, GenericJoint)>,
+    break_thresholds: Vec<(ImpulseJointHandle, BreakThresholds)>,
+    distance_joints: Vec<(ImpulseJointHandle, DistanceJoint)>,
+}
+
+/// The part of a rigid-body state that changes while it is being simulated.
+#[derive(Serialize, Deserialize, PartialEq)]
+struct BodyState {
+    handle: RigidBodyHandle,
+    position: Isometry<Real>,
+    linvel: Vector<Real>,
+    angvel: AngVector<Real>,
+    sleeping: bool,
+}
+
+impl BodyState {
+    fn new(handle: RigidBodyHandle, rb: &RigidBody) -> Self {
+        Self {
+            handle,
+            position: *rb.position(),
+            linvel: *rb.linvel(),
+            #[cfg(feature = "dim2")]
+            angvel: rb.angvel(),
+            #[cfg(feature = "dim3")]
+            angvel: *rb.angvel(),
+            sleeping: rb.is_sleeping(),
+        }
+    }
+
+    fn apply(&self, rb: &mut RigidBody) {
+        rb.set_position(self.position, false);
+        rb.set_linvel(self.linvel, false);
+        rb.set_angvel(self.angvel, false);
+
+        if self.sleeping && !rb.is_sleeping() {
+            rb.sleep();
+        } else if !self.sleeping && rb.is_sleeping() {
+            rb.wake_up(true);
+        }
+    }
+}
+
+/// A set of rigid-bodies, with their attached colliders and the joints between them.
+///
+/// The handles are the handles the elements had in the world the prefab was extracted from.
+#[derive(Default, Serialize, Deserialize)]
+struct Prefab {
+    bodies: Vec<(
+        RigidBodyHandle,
+        RigidBody,
+        Option<DisabledBody>,
+        Option<UserForces>,
+    )>,
+    colliders: Vec<(ColliderHandle, Collider, Option<DisabledCollider>)>,
+    impulse_joints: Vec<(
+        FlatHandle,
+        ImpulseJoint,
+        Option<BreakThresholds>,
+        Option<DistanceJoint>,
+    )>,
+    // (handle, parent body, child body, joint)
+    multibody_joints: Vec<(FlatHandle, RigidBodyHandle, RigidBodyHandle, GenericJoint)>,
+}
+
+/// Rigid-body states decoded from the output of `serializeBodyStates`, indexed by rigid-body handle.
+///
+/// They can be used as the base of several calls to `serializeBodyStatesDelta` without being
+/// decoded again each time.
+#[wasm_bindgen]
+pub struct RawBodyStates(HashMap<RigidBodyHandle, BodyState>);
+
+/// The handles given to the elements of a prefab inserted into a physics world.
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct RawPrefabRemapping {
+    bodies: Vec<FlatHandle>,
+    colliders: Vec<FlatHandle>,
+    impulse_joints: Vec<FlatHandle>,
+    multibody_joints: Vec<FlatHandle>,
+}
+
+#[wasm_bindgen]
+impl RawPrefabRemapping {
+    /// The rigid-body handles, as a flat array of `[oldHandle, newHandle]` pairs.
+    pub fn bodies(&self) -> Float64Array {
+        Float64Array::from(&self.bodies[..])
+    }
+
+    /// The collider handles, as a flat array of `[oldHandle, newHandle]` pairs.
+    pub fn colliders(&self) -> Float64Array {
+        Float64Array::from(&self.colliders[..])
+    }
+
+    /// The impulse joint handles, as a flat array of `[oldHandle, newHandle]` pairs.
+    pub fn impulseJoints(&self) -> Float64Array {
+        Float64Array::from(&self.impulse_joints[..])
+    }
+
+    /// The multibody joint handles, as a flat array of `[oldHandle, newHandle]` pairs.
+    pub fn multibodyJoints(&self) -> Float64Array {
+        Float64Array::from(&self.multibody_joints[..])
+    }
 }
 
 #[wasm_bindgen]
@@ -51,6 +430,34 @@ pub struct RawDeserializedWorld {
     multibody_joints: Option<RawMultibodyJointSet>,
 }
 
+impl From<DeserializableWorld> for RawDeserializedWorld {
+    fn from(d: DeserializableWorld) -> Self {
+        RawDeserializedWorld {
+            gravity: Some(RawVector(d.gravity)),
+            integrationParameters: Some(RawIntegrationParameters(d.integration_parameters)),
+            islands: Some(RawIslandManager(d.islands)),
+            broadPhase: Some(RawBroadPhase(d.broad_phase)),
+            narrowPhase: Some(RawNarrowPhase(d.narrow_phase)),
+            bodies: Some(RawRigidBodySet::from_parts(
+                d.bodies,
+                d.disabled_bodies,
+                d.user_forces,
+            )),
+            colliders: Some(RawColliderSet::from_parts(
+                d.colliders,
+                d.disabled_colliders,
+            )),
+            impulse_joints: Some(RawImpulseJointSet::from_parts(
+                d.impulse_joints,
+                d.neutralized_joints,
+                d.break_thresholds,
+                d.distance_joints,
+            )),
+            multibody_joints: Some(RawMultibodyJointSet(d.multibody_joints)),
+        }
+    }
+}
+
 #[wasm_bindgen]
 impl RawDeserializedWorld {
     pub fn takeGravity(&mut self) -> Option<RawVector> {
@@ -111,7 +518,7 @@ impl RawSerializationPipeline {
         colliders: &RawColliderSet,
         impulse_joints: &RawImpulseJointSet,
         multibody_joints: &RawMultibodyJointSet,
-    ) -> Option<Uint8Array> {
+    ) -> Result<Uint8Array, JsValue> {
         let to_serialize = SerializableWorld {
             gravity: &gravity.0,
             integration_parameters: &integrationParameters.0,
@@ -122,24 +529,348 @@ impl RawSerializationPipeline {
             colliders: &colliders.0,
             impulse_joints: &impulse_joints.0,
             multibody_joints: &multibody_joints.0,
+            disabled_bodies: bodies.disabled_bodies(),
+            user_forces: bodies.user_forces(),
+            disabled_colliders: colliders.disabled_colliders(),
+            neutralized_joints: impulse_joints.neutralized_joints(),
+            break_thresholds: impulse_joints.break_thresholds(),
+            distance_joints: impulse_joints.distance_joints(),
         };
-        let snap = bincode::serialize(&to_serialize).ok()?;
-        Some(Uint8Array::from(&snap[..]))
+        Ok(write_snapshot(SnapshotKind::World, &to_serialize)?)
     }
 
-    pub fn deserializeAll(&self, data: Uint8Array) -> Option<RawDeserializedWorld> {
-        let data = data.to_vec();
-        let d: DeserializableWorld = bincode::deserialize(&data[..]).ok()?;
-        Some(RawDeserializedWorld {
-            gravity: Some(RawVector(d.gravity)),
-            integrationParameters: Some(RawIntegrationParameters(d.integration_parameters)),
-            islands: Some(RawIslandManager(d.islands)),
-            broadPhase: Some(RawBroadPhase(d.broad_phase)),
-            narrowPhase: Some(RawNarrowPhase(d.narrow_phase)),
-            bodies: Some(RawRigidBodySet(d.bodies)),
-            colliders: Some(RawColliderSet(d.colliders)),
-            impulse_joints: Some(RawImpulseJointSet(d.impulse_joints)),
-            multibody_joints: Some(RawMultibodyJointSet(d.multibody_joints)),
-        })
+    /// Deserializes a world snapshot generated by `serializeAll`.
+    ///
+    /// Throws an error explaining why the snapshot can’t be loaded if it is corrupted, or if it was
+    /// generated by an incompatible version or dimension of this library.
+    pub fn deserializeAll(&self, data: Uint8Array) -> Result<RawDeserializedWorld, JsValue> {
+        let d: DeserializableWorld = read_snapshot(SnapshotKind::World, &data.to_vec())?;
+        Ok(d.into())
+    }
+
+    /// Serializes a complete physics state into a human-readable JSON string.
+    ///
+    /// The broad-phase and narrow-phase are stored as opaque arrays of bytes because they
+    /// contain maps that can’t be represented in JSON.
+    pub fn serializeAllJson(
+        &self,
+        gravity: &RawVector,
+        integrationParameters: &RawIntegrationParameters,
+        islands: &RawIslandManager,
+        broadPhase: &RawBroadPhase,
+        narrowPhase: &RawNarrowPhase,
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        impulse_joints: &RawImpulseJointSet,
+        multibody_joints: &RawMultibodyJointSet,
+    ) -> Result<String, JsValue> {
+        let to_serialize = SerializableWorld {
+            gravity: &gravity.0,
+            integration_parameters: &integrationParameters.0,
+            islands: &islands.0,
+            broad_phase: &broadPhase.0,
+            narrow_phase: &narrowPhase.0,
+            bodies: &bodies.0,
+            colliders: &colliders.0,
+            impulse_joints: &impulse_joints.0,
+            multibody_joints: &multibody_joints.0,
+            disabled_bodies: bodies.disabled_bodies(),
+            user_forces: bodies.user_forces(),
+            disabled_colliders: colliders.disabled_colliders(),
+            neutralized_joints: impulse_joints.neutralized_joints(),
+            break_thresholds: impulse_joints.break_thresholds(),
+            distance_joints: impulse_joints.distance_joints(),
+        };
+        Ok(write_json_snapshot(SnapshotKind::World, &to_serialize)?)
+    }
+
+    /// Deserializes a world snapshot generated by `serializeAllJson`.
+    ///
+    /// Throws an error explaining why the snapshot can’t be loaded if it is invalid, or if it was
+    /// generated by an incompatible version or dimension of this library.
+    pub fn deserializeAllJson(&self, data: &str) -> Result<RawDeserializedWorld, JsValue> {
+        let d: DeserializableWorld = read_json_snapshot(SnapshotKind::World, data)?;
+        Ok(d.into())
+    }
+
+    /// Serializes the position, velocities, and sleep state of every rigid-body.
+    ///
+    /// The result can be decoded with `decodeBodyStates` to be used as the base of
+    /// `serializeBodyStatesDelta`, and restored with `applyBodyStates`.
+    pub fn serializeBodyStates(&self, bodies: &RawRigidBodySet) -> Result<Uint8Array, JsValue> {
+        let states: Vec<_> = bodies
+            .0
+            .iter()
+            .map(|(handle, rb)| BodyState::new(handle, rb))
+            .collect();
+        Ok(write_snapshot(SnapshotKind::BodyStates, &states)?)
+    }
+
+    /// Decodes rigid-body states generated by `serializeBodyStates`, to be used as the base of
+    /// `serializeBodyStatesDelta`.
+    ///
+    /// Throws an error if `data` can’t be loaded.
+    pub fn decodeBodyStates(&self, data: Uint8Array) -> Result<RawBodyStates, JsValue> {
+        let states: Vec<BodyState> = read_snapshot(SnapshotKind::BodyStates, &data.to_vec())?;
+        Ok(RawBodyStates(
+            states
+                .into_iter()
+                .map(|state| (state.handle, state))
+                .collect(),
+        ))
+    }
+
+    /// Serializes the position, velocities, and sleep state of every rigid-body which
+    /// state differs from its state in `base`.
+    ///
+    /// Rigid-bodies that don’t exist in `base` are always included. Applying the base, then
+    /// this delta, with `applyBodyStates` restores the rigid-body states at the time this
+    /// delta was created (assuming no rigid-body was added or removed in-between).
+    ///
+    /// # Parameters
+    /// - `base`: body states generated by `serializeBodyStates`, decoded by `decodeBodyStates`.
+    pub fn serializeBodyStatesDelta(
+        &self,
+        base: &RawBodyStates,
+        bodies: &RawRigidBodySet,
+    ) -> Result<Uint8Array, JsValue> {
+        let delta: Vec<_> = bodies
+            .0
+            .iter()
+            .map(|(handle, rb)| BodyState::new(handle, rb))
+            .filter(|state| base.0.get(&state.handle) != Some(state))
+            .collect();
+        Ok(write_snapshot(SnapshotKind::BodyStates, &delta)?)
+    }
+
+    /// Restores the rigid-body states serialized by `serializeBodyStates` or
+    /// `serializeBodyStatesDelta`.
+    ///
+    /// States of rigid-bodies that no longer exist are ignored. Throws an error if `data`
+    /// can’t be loaded.
+    pub fn applyBodyStates(
+        &self,
+        data: Uint8Array,
+        bodies: &mut RawRigidBodySet,
+    ) -> Result<(), JsValue> {
+        let states: Vec<BodyState> = read_snapshot(SnapshotKind::BodyStates, &data.to_vec())?;
+
+        for state in &states {
+            if let Some(rb) = bodies.0.get_mut(state.handle) {
+                state.apply(rb);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Serializes the given rigid-bodies, together with their attached colliders, and the
+    /// impulse and multibody joints linking them together.
+    ///
+    /// Joints attached to a rigid-body that is not part of `handles` are not serialized. The
+    /// result can be inserted into any physics world with `insertPrefab`.
+    ///
+    /// # Parameters
+    /// - `handles`: the handles of the rigid-bodies to serialize. Invalid handles are ignored.
+    pub fn serializePrefab(
+        &self,
+        handles: &[FlatHandle],
+        bodies: &RawRigidBodySet,
+        colliders: &RawColliderSet,
+        impulse_joints: &RawImpulseJointSet,
+        multibody_joints: &RawMultibodyJointSet,
+    ) -> Result<Uint8Array, JsValue> {
+        let mut selected = HashSet::new();
+        let handles: Vec<_> = handles
+            .iter()
+            .map(|h| utils::body_handle(*h))
+            .filter(|h| bodies.0.contains(*h) && selected.insert(*h))
+            .collect();
+        let mut prefab = Prefab::default();
+
+        for handle in handles {
+            let rb = &bodies.0[handle];
+
+            for co_handle in rb.colliders() {
+                prefab.colliders.push((
+                    *co_handle,
+                    colliders.0[*co_handle].clone(),
+                    colliders.1.get(co_handle).copied(),
+                ));
+            }
+
+            for (_, _, joint_handle, joint) in impulse_joints.0.attached_joints(handle) {
+                // Export each joint only once, when visiting its first rigid-body.
+                if joint.body1 == handle && selected.contains(&joint.body2) {
+                    let mut joint = joint.clone();
+                    // Export the original data of joints neutralized by a disabled rigid-body.
+                    if let Some(data) = impulse_joints.joint_data(joint_handle) {
+                        joint.data = *data;
+                    }
+                    prefab.impulse_joints.push((
+                        utils::flat_handle(joint_handle.0),
+                        joint,
+                        impulse_joints.2.get(&joint_handle).copied(),
+                        impulse_joints.4.get(&joint_handle).copied(),
+                    ));
+                }
+            }
+
+            for (_, _, joint_handle) in multibody_joints.0.attached_joints(handle) {
+                let (multibody, link_id) = match multibody_joints.0.get(joint_handle) {
+                    Some(joint) => joint,
+                    None => continue,
+                };
+                let link = match multibody.link(link_id) {
+                    Some(link) => link,
+                    None => continue,
+                };
+
+                // Export each joint only once, when visiting the rigid-body of its child link.
+                if link.rigid_body_handle() != handle {
+                    continue;
+                }
+
+                let parent = link
+                    .parent_id()
+                    .and_then(|parent_id| multibody.link(parent_id))
+                    .map(|parent| parent.rigid_body_handle());
+
+                if let Some(parent) = parent {
+                    if selected.contains(&parent) {
+                        prefab.multibody_joints.push((
+                            utils::flat_handle(joint_handle.0),
+                            parent,
+                            handle,
+                            link.joint().data,
+                        ));
+                    }
+                }
+            }
+
+            prefab.bodies.push((
+                handle,
+                rb.clone(),
+                bodies.1.get(&handle).copied(),
+                bodies.2.get(&handle).copied(),
+            ));
+        }
+
+        Ok(write_snapshot(SnapshotKind::Prefab, &prefab)?)
+    }
+
+    /// Inserts a prefab generated by `serializePrefab` into a physics world.
+    ///
+    /// The prefab’s rigid-bodies are moved by the given rigid-motion: their positions
+    /// are pre-multiplied by it, and their velocities and user forces are rotated.
+    ///
+    /// # Parameters
+    /// - `translation`: the translational part of the rigid-motion applied to the prefab.
+    /// - `rotation`: the rotational part of the rigid-motion applied to the prefab.
+    pub fn insertPrefab(
+        &self,
+        data: Uint8Array,
+        translation: &RawVector,
+        rotation: &RawRotation,
+        bodies: &mut RawRigidBodySet,
+        colliders: &mut RawColliderSet,
+        impulse_joints: &mut RawImpulseJointSet,
+        multibody_joints: &mut RawMultibodyJointSet,
+    ) -> Result<RawPrefabRemapping, JsValue> {
+        let prefab: Prefab = read_snapshot(SnapshotKind::Prefab, &data.to_vec())?;
+        let offset = Isometry::from_parts(translation.0.into(), rotation.0);
+        let mut body_handles = HashMap::new();
+        let mut remapping = RawPrefabRemapping::default();
+
+        for (old_handle, mut rb, disabled, user_forces) in prefab.bodies {
+            rb.set_position(offset * rb.position(), false);
+            rb.set_linvel(offset.rotation * rb.linvel(), false);
+            #[cfg(feature = "dim3")]
+            rb.set_angvel(offset.rotation * rb.angvel(), false);
+
+            let user_forces = user_forces.map(|mut forces| {
+                forces.force = offset.rotation * forces.force;
+                #[cfg(feature = "dim3")]
+                {
+                    forces.torque = offset.rotation * forces.torque;
+                }
+                // The forces stored by the rigid-body are the user forces, rotated the same way.
+                rb.reset_forces(false);
+                rb.reset_torques(false);
+                rb.add_force(forces.force, false);
+                rb.add_torque(forces.torque, false);
+                forces
+            });
+
+            let new_handle = bodies.0.insert(rb);
+            if let Some(mut disabled) = disabled {
+                disabled.linvel = offset.rotation * disabled.linvel;
+                #[cfg(feature = "dim3")]
+                {
+                    disabled.angvel = offset.rotation * disabled.angvel;
+                }
+                bodies.1.insert(new_handle, disabled);
+            }
+            if let Some(user_forces) = user_forces {
+                bodies.2.insert(new_handle, user_forces);
+            }
+            body_handles.insert(old_handle, new_handle);
+            remapping.bodies.push(utils::flat_handle(old_handle.0));
+            remapping.bodies.push(utils::flat_handle(new_handle.0));
+        }
+
+        for (old_handle, mut co, disabled) in prefab.colliders {
+            let parent = co.parent().and_then(|parent| body_handles.get(&parent));
+
+            if let Some(parent) = parent.copied() {
+                // The position of a collider inserted with a parent is interpreted as
+                // its position relative to that parent.
+                if let Some(pos_wrt_parent) = co.position_wrt_parent().copied() {
+                    co.set_position(pos_wrt_parent);
+                }
+
+                let new_handle = colliders.0.insert_with_parent(co, parent, &mut bodies.0);
+                if let Some(disabled) = disabled {
+                    colliders.1.insert(new_handle, disabled);
+                }
+                remapping.colliders.push(utils::flat_handle(old_handle.0));
+                remapping.colliders.push(utils::flat_handle(new_handle.0));
+            }
+        }
+
+        for (old_handle, joint, break_thresholds, distance_joint) in prefab.impulse_joints {
+            let body1 = body_handles.get(&joint.body1);
+            let body2 = body_handles.get(&joint.body2);
+
+            if let (Some(body1), Some(body2)) = (body1, body2) {
+                let new_handle = impulse_joints.0.insert(*body1, *body2, joint.data, true);
+                if let Some(break_thresholds) = break_thresholds {
+                    impulse_joints.2.insert(new_handle, break_thresholds);
+                }
+                if let Some(distance_joint) = distance_joint {
+                    impulse_joints.4.insert(new_handle, distance_joint);
+                }
+                remapping.impulse_joints.push(old_handle);
+                remapping
+                    .impulse_joints
+                    .push(utils::flat_handle(new_handle.0));
+            }
+        }
+
+        for (old_handle, parent, child, data) in prefab.multibody_joints {
+            let parent = body_handles.get(&parent);
+            let child = body_handles.get(&child);
+
+            if let (Some(parent), Some(child)) = (parent, child) {
+                if let Some(new_handle) = multibody_joints.0.insert(*parent, *child, data, true) {
+                    remapping.multibody_joints.push(old_handle);
+                    remapping
+                        .multibody_joints
+                        .push(utils::flat_handle(new_handle.0));
+                }
+            }
+        }
+
+        Ok(remapping)
     }
 }
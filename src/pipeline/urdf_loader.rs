@@ -0,0 +1,661 @@
+use crate::dynamics::{RawImpulseJointSet, RawMultibodyJointSet, RawRigidBodySet};
+use crate::geometry::RawColliderSet;
+use crate::math::{RawRotation, RawVector};
+use crate::utils::{self, FlatHandle};
+use js_sys::Float64Array;
+use na::{Matrix3, Translation3, Unit, UnitQuaternion};
+use rapier::dynamics::{
+    GenericJoint, GenericJointBuilder, IslandManager, JointAxesMask, JointAxis, MassProperties,
+    RigidBodyBuilder, RigidBodyType,
+};
+use rapier::geometry::{ColliderBuilder, SharedShape};
+use rapier::math::{Isometry, Point, Real, Vector};
+use roxmltree::{Document, Node};
+use std::collections::HashMap;
+use std::f32::consts::{FRAC_PI_2, PI};
+use std::fmt;
+use wasm_bindgen::prelude::*;
+
+/// Errors that can occur while loading a URDF file.
+#[derive(Debug)]
+enum UrdfError {
+    Xml(String),
+    MissingRobot,
+    MissingElement {
+        parent: String,
+        element: &'static str,
+    },
+    MissingAttribute {
+        element: String,
+        attribute: &'static str,
+    },
+    InvalidAttribute {
+        element: String,
+        attribute: &'static str,
+        value: String,
+    },
+    UnknownLink {
+        joint: String,
+        link: String,
+    },
+    UnsupportedJointType {
+        joint: String,
+        joint_type: String,
+    },
+    InvalidTree(String),
+    MultibodyInsertion {
+        joint: String,
+    },
+}
+
+impl fmt::Display for UrdfError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UrdfError::Xml(e) => write!(f, "invalid URDF: failed to parse the XML: {}", e),
+            UrdfError::MissingRobot => write!(f, "invalid URDF: the root element must be <robot>"),
+            UrdfError::MissingElement { parent, element } => write!(
+                f,
+                "invalid URDF: {} is missing its <{}> element",
+                parent, element
+            ),
+            UrdfError::MissingAttribute { element, attribute } => write!(
+                f,
+                "invalid URDF: {} is missing its `{}` attribute",
+                element, attribute
+            ),
+            UrdfError::InvalidAttribute {
+                element,
+                attribute,
+                value,
+            } => write!(
+                f,
+                "invalid URDF: invalid value \"{}\" for the `{}` attribute of {}",
+                value, attribute, element
+            ),
+            UrdfError::UnknownLink { joint, link } => write!(
+                f,
+                "invalid URDF: the joint \"{}\" references the unknown link \"{}\"",
+                joint, link
+            ),
+            UrdfError::UnsupportedJointType { joint, joint_type } => write!(
+                f,
+                "unsupported URDF: the joint \"{}\" has the unsupported type \"{}\"",
+                joint, joint_type
+            ),
+            UrdfError::InvalidTree(e) => write!(f, "invalid URDF: {}", e),
+            UrdfError::MultibodyInsertion { joint } => write!(
+                f,
+                "invalid URDF: the joint \"{}\" can’t be inserted into a multibody",
+                joint
+            ),
+        }
+    }
+}
+
+impl From<UrdfError> for JsValue {
+    fn from(e: UrdfError) -> JsValue {
+        js_sys::Error::new(&e.to_string()).into()
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum UrdfJointType {
+    Revolute,
+    Continuous,
+    Prismatic,
+    Fixed,
+    Floating,
+    Planar,
+}
+
+struct UrdfLink {
+    name: String,
+    mass_properties: Option<MassProperties>,
+    collisions: Vec<(Isometry<Real>, SharedShape)>,
+}
+
+struct UrdfJoint {
+    name: String,
+    joint_type: UrdfJointType,
+    parent: String,
+    child: String,
+    origin: Isometry<Real>,
+    axis: Unit<Vector<Real>>,
+    limits: Option<[Real; 2]>,
+}
+
+impl UrdfJoint {
+    /// The rapier joint equivalent to this URDF joint, or `None` if it doesn’t constrain
+    /// the relative motion of its links.
+    fn to_generic_joint(&self, contacts_enabled: bool) -> Option<GenericJoint> {
+        let (locked_axes, limited_axis) = match self.joint_type {
+            UrdfJointType::Revolute | UrdfJointType::Continuous => (
+                JointAxesMask::X
+                    | JointAxesMask::Y
+                    | JointAxesMask::Z
+                    | JointAxesMask::ANG_Y
+                    | JointAxesMask::ANG_Z,
+                JointAxis::AngX,
+            ),
+            UrdfJointType::Prismatic => (
+                JointAxesMask::Y
+                    | JointAxesMask::Z
+                    | JointAxesMask::ANG_X
+                    | JointAxesMask::ANG_Y
+                    | JointAxesMask::ANG_Z,
+                JointAxis::X,
+            ),
+            UrdfJointType::Fixed => (JointAxesMask::all(), JointAxis::X),
+            // The axis of a planar joint is the normal of its plane.
+            UrdfJointType::Planar => (
+                JointAxesMask::X | JointAxesMask::ANG_Y | JointAxesMask::ANG_Z,
+                JointAxis::AngX,
+            ),
+            UrdfJointType::Floating => return None,
+        };
+
+        // The joint frames are rotated so their `x` axis is aligned with the URDF joint axis.
+        let axis_rotation = UnitQuaternion::rotation_between_axis(&Vector::x_axis(), &self.axis)
+            .unwrap_or_else(|| UnitQuaternion::from_axis_angle(&Vector::y_axis(), PI));
+        let mut joint = GenericJointBuilder::new(locked_axes).build();
+        joint.set_local_frame1(Isometry::from_parts(
+            self.origin.translation,
+            self.origin.rotation * axis_rotation,
+        ));
+        joint.set_local_frame2(Isometry::from_parts(
+            Translation3::identity(),
+            axis_rotation,
+        ));
+        joint.contacts_enabled = contacts_enabled;
+
+        if let Some(limits) = self.limits {
+            if matches!(
+                self.joint_type,
+                UrdfJointType::Revolute | UrdfJointType::Prismatic
+            ) {
+                joint.set_limits(limited_axis, limits);
+            }
+        }
+
+        Some(joint)
+    }
+}
+
+/// Describes an element for error messages, e.g., `<joint name="elbow">`.
+fn describe(node: Node) -> String {
+    match node.attribute("name") {
+        Some(name) => format!("<{} name=\"{}\">", node.tag_name().name(), name),
+        None => format!("<{}>", node.tag_name().name()),
+    }
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, tag: &str) -> Option<Node<'a, 'input>> {
+    node.children()
+        .find(|n| n.is_element() && n.tag_name().name() == tag)
+}
+
+fn required_child<'a, 'input>(
+    node: Node<'a, 'input>,
+    tag: &'static str,
+) -> Result<Node<'a, 'input>, UrdfError> {
+    child(node, tag).ok_or_else(|| UrdfError::MissingElement {
+        parent: describe(node),
+        element: tag,
+    })
+}
+
+fn attribute<'a>(node: Node<'a, '_>, name: &'static str) -> Result<&'a str, UrdfError> {
+    node.attribute(name)
+        .ok_or_else(|| UrdfError::MissingAttribute {
+            element: describe(node),
+            attribute: name,
+        })
+}
+
+/// Parses an attribute made of `N` whitespace-separated real numbers.
+fn parse_reals<const N: usize>(
+    node: Node,
+    name: &'static str,
+    value: &str,
+) -> Result<[Real; N], UrdfError> {
+    let invalid = || UrdfError::InvalidAttribute {
+        element: describe(node),
+        attribute: name,
+        value: value.to_string(),
+    };
+    let mut result = [0.0; N];
+    let mut components = value.split_whitespace();
+
+    for component in result.iter_mut() {
+        *component = components
+            .next()
+            .and_then(|c| c.parse().ok())
+            .ok_or_else(invalid)?;
+    }
+
+    if components.next().is_some() {
+        return Err(invalid());
+    }
+
+    Ok(result)
+}
+
+fn real_attribute(node: Node, name: &'static str) -> Result<Real, UrdfError> {
+    let [value] = parse_reals::<1>(node, name, attribute(node, name)?)?;
+    Ok(value)
+}
+
+/// Parses the optional `<origin>` child of an element.
+fn parse_origin(node: Node) -> Result<Isometry<Real>, UrdfError> {
+    let origin = match child(node, "origin") {
+        Some(origin) => origin,
+        None => return Ok(Isometry::identity()),
+    };
+    let xyz = match origin.attribute("xyz") {
+        Some(xyz) => parse_reals::<3>(origin, "xyz", xyz)?,
+        None => [0.0; 3],
+    };
+    let rpy = match origin.attribute("rpy") {
+        Some(rpy) => parse_reals::<3>(origin, "rpy", rpy)?,
+        None => [0.0; 3],
+    };
+
+    Ok(Isometry::from_parts(
+        Vector::from(xyz).into(),
+        UnitQuaternion::from_euler_angles(rpy[0], rpy[1], rpy[2]),
+    ))
+}
+
+fn parse_inertial(node: Node) -> Result<MassProperties, UrdfError> {
+    let origin = parse_origin(node)?;
+    let mass = real_attribute(required_child(node, "mass")?, "value")?;
+    let inertia = required_child(node, "inertia")?;
+    let ixx = real_attribute(inertia, "ixx")?;
+    let ixy = real_attribute(inertia, "ixy")?;
+    let ixz = real_attribute(inertia, "ixz")?;
+    let iyy = real_attribute(inertia, "iyy")?;
+    let iyz = real_attribute(inertia, "iyz")?;
+    let izz = real_attribute(inertia, "izz")?;
+
+    // The inertia tensor is expressed in the frame of the <inertial> element.
+    let rot = origin.rotation.to_rotation_matrix().into_inner();
+    let inertia = rot * Matrix3::new(ixx, ixy, ixz, ixy, iyy, iyz, ixz, iyz, izz) * rot.transpose();
+
+    Ok(MassProperties::with_inertia_matrix(
+        Point::from(origin.translation.vector),
+        mass,
+        inertia,
+    ))
+}
+
+/// Parses a `<collision>` element.
+///
+/// Returns `None` for mesh geometries, and for unknown geometries.
+fn parse_collision(node: Node) -> Result<Option<(Isometry<Real>, SharedShape)>, UrdfError> {
+    let origin = parse_origin(node)?;
+    let geometry = required_child(node, "geometry")?;
+    let shape = match geometry.children().find(|n| n.is_element()) {
+        Some(shape) => shape,
+        None => {
+            return Err(UrdfError::MissingElement {
+                parent: describe(geometry),
+                element: "box",
+            })
+        }
+    };
+
+    let result = match shape.tag_name().name() {
+        "box" => {
+            let [x, y, z] = parse_reals::<3>(shape, "size", attribute(shape, "size")?)?;
+            Some((origin, SharedShape::cuboid(x / 2.0, y / 2.0, z / 2.0)))
+        }
+        "sphere" => {
+            let radius = real_attribute(shape, "radius")?;
+            Some((origin, SharedShape::ball(radius)))
+        }
+        "cylinder" => {
+            let radius = real_attribute(shape, "radius")?;
+            let length = real_attribute(shape, "length")?;
+            // URDF cylinders are aligned with the `z` axis, rapier cylinders with the `y` axis.
+            let to_z = UnitQuaternion::from_axis_angle(&Vector::x_axis(), FRAC_PI_2);
+            Some((origin * to_z, SharedShape::cylinder(length / 2.0, radius)))
+        }
+        "capsule" => {
+            let radius = real_attribute(shape, "radius")?;
+            let length = real_attribute(shape, "length")?;
+            Some((origin, SharedShape::capsule_z(length / 2.0, radius)))
+        }
+        _ => None,
+    };
+
+    Ok(result)
+}
+
+fn parse_link(node: Node) -> Result<UrdfLink, UrdfError> {
+    let mass_properties = match child(node, "inertial") {
+        Some(inertial) => Some(parse_inertial(inertial)?),
+        None => None,
+    };
+    let mut collisions = vec![];
+
+    for collision in node
+        .children()
+        .filter(|n| n.is_element() && n.tag_name().name() == "collision")
+    {
+        if let Some(collision) = parse_collision(collision)? {
+            collisions.push(collision);
+        }
+    }
+
+    Ok(UrdfLink {
+        name: attribute(node, "name")?.to_string(),
+        mass_properties,
+        collisions,
+    })
+}
+
+fn parse_joint(node: Node) -> Result<UrdfJoint, UrdfError> {
+    let name = attribute(node, "name")?.to_string();
+    let joint_type = match attribute(node, "type")? {
+        "revolute" => UrdfJointType::Revolute,
+        "continuous" => UrdfJointType::Continuous,
+        "prismatic" => UrdfJointType::Prismatic,
+        "fixed" => UrdfJointType::Fixed,
+        "floating" => UrdfJointType::Floating,
+        "planar" => UrdfJointType::Planar,
+        joint_type => {
+            return Err(UrdfError::UnsupportedJointType {
+                joint: name,
+                joint_type: joint_type.to_string(),
+            })
+        }
+    };
+
+    let axis = match child(node, "axis") {
+        Some(axis) => {
+            let value = attribute(axis, "xyz")?;
+            let xyz = parse_reals::<3>(axis, "xyz", value)?;
+            Unit::try_new(Vector::from(xyz), Real::EPSILON).ok_or_else(|| {
+                UrdfError::InvalidAttribute {
+                    element: describe(axis),
+                    attribute: "xyz",
+                    value: value.to_string(),
+                }
+            })?
+        }
+        None => Vector::x_axis(),
+    };
+
+    let limits = match child(node, "limit") {
+        Some(limit) => {
+            let bound = |name: &'static str| match limit.attribute(name) {
+                Some(value) => parse_reals::<1>(limit, name, value).map(|[v]| v),
+                None => Ok(0.0),
+            };
+            Some([bound("lower")?, bound("upper")?])
+        }
+        None => None,
+    };
+
+    Ok(UrdfJoint {
+        joint_type,
+        parent: attribute(required_child(node, "parent")?, "link")?.to_string(),
+        child: attribute(required_child(node, "child")?, "link")?.to_string(),
+        origin: parse_origin(node)?,
+        axis,
+        limits,
+        name,
+    })
+}
+
+/// The rigid-bodies, colliders, and joints created from a URDF file.
+#[wasm_bindgen]
+pub struct RawUrdfRobot {
+    name: String,
+    link_names: Vec<String>,
+    bodies: Vec<FlatHandle>,
+    colliders: Vec<Vec<FlatHandle>>,
+    joint_names: Vec<String>,
+    joints: Vec<FlatHandle>,
+    multibody: bool,
+}
+
+#[wasm_bindgen]
+impl RawUrdfRobot {
+    /// Parses a URDF file, and inserts the robot it describes into the given sets.
+    ///
+    /// Each link is converted into a dynamic rigid-body with one collider per supported
+    /// collision geometry (box, sphere, cylinder, and capsule). Mesh geometries are ignored since
+    /// their files can’t be accessed. If a link has an `<inertial>` element, its mass properties
+    /// are used instead of the ones computed from its colliders. Floating joints are ignored, so
+    /// their child links are free rigid-bodies, and the other joints are converted into impulse
+    /// joints or multibody joints.
+    ///
+    /// Nothing is inserted if the URDF file is invalid, in which case an error is thrown.
+    ///
+    /// # Parameters
+    /// - `urdf`: the content of the URDF file.
+    /// - `translation`, `rotation`: the pose of the robot’s root links.
+    /// - `fixedRoot`: if `true`, the links without parent joint are converted into fixed
+    ///   rigid-bodies.
+    /// - `multibody`: if `true`, the joints are inserted into `multibodyJoints` instead of
+    ///   `impulseJoints`.
+    /// - `jointCollisions`: if `true`, contacts are enabled between links attached by a joint.
+    pub fn load(
+        urdf: &str,
+        bodies: &mut RawRigidBodySet,
+        colliders: &mut RawColliderSet,
+        impulseJoints: &mut RawImpulseJointSet,
+        multibodyJoints: &mut RawMultibodyJointSet,
+        translation: &RawVector,
+        rotation: &RawRotation,
+        fixedRoot: bool,
+        multibody: bool,
+        jointCollisions: bool,
+    ) -> Result<RawUrdfRobot, JsValue> {
+        let doc = Document::parse(urdf).map_err(|e| UrdfError::Xml(e.to_string()))?;
+        let root = doc.root_element();
+
+        if root.tag_name().name() != "robot" {
+            return Err(UrdfError::MissingRobot.into());
+        }
+
+        let mut links = vec![];
+        let mut joints = vec![];
+
+        for node in root.children().filter(|n| n.is_element()) {
+            match node.tag_name().name() {
+                "link" => links.push(parse_link(node)?),
+                "joint" => joints.push(parse_joint(node)?),
+                _ => {}
+            }
+        }
+
+        // Check the joints form a forest before inserting anything.
+        let link_ids: HashMap<&str, usize> = links
+            .iter()
+            .enumerate()
+            .map(|(id, link)| (&link.name[..], id))
+            .collect();
+        let mut parent_joints = vec![None; links.len()];
+
+        for (joint_id, joint) in joints.iter().enumerate() {
+            for link in &[&joint.parent, &joint.child] {
+                if !link_ids.contains_key(&link[..]) {
+                    return Err(UrdfError::UnknownLink {
+                        joint: joint.name.clone(),
+                        link: link.to_string(),
+                    }
+                    .into());
+                }
+            }
+
+            let child_id = link_ids[&joint.child[..]];
+            if parent_joints[child_id].replace(joint_id).is_some() {
+                return Err(UrdfError::InvalidTree(format!(
+                    "the link \"{}\" is the child of several joints",
+                    joint.child
+                ))
+                .into());
+            }
+        }
+
+        let root_pose = Isometry::from_parts(translation.0.into(), rotation.0);
+        let mut poses = Vec::with_capacity(links.len());
+
+        for (link_id, link) in links.iter().enumerate() {
+            let mut pose = Isometry::identity();
+            let mut curr = link_id;
+            let mut depth = 0;
+
+            while let Some(joint_id) = parent_joints[curr] {
+                let joint: &UrdfJoint = &joints[joint_id];
+                pose = joint.origin * pose;
+                curr = link_ids[&joint.parent[..]];
+                depth += 1;
+
+                if depth > joints.len() {
+                    return Err(UrdfError::InvalidTree(format!(
+                        "the joints form a cycle containing the link \"{}\"",
+                        link.name
+                    ))
+                    .into());
+                }
+            }
+
+            poses.push(root_pose * pose);
+        }
+
+        let mut robot = RawUrdfRobot {
+            name: root.attribute("name").unwrap_or_default().to_string(),
+            link_names: vec![],
+            bodies: vec![],
+            colliders: vec![],
+            joint_names: vec![],
+            joints: vec![],
+            multibody,
+        };
+        let mut body_handles = vec![];
+
+        for ((link, pose), parent_joint) in links.iter().zip(poses).zip(&parent_joints) {
+            let body_type = if fixedRoot && parent_joint.is_none() {
+                RigidBodyType::Fixed
+            } else {
+                RigidBodyType::Dynamic
+            };
+            let mut rb = RigidBodyBuilder::new(body_type).position(pose);
+            if let Some(mass_properties) = link.mass_properties {
+                rb = rb.additional_mass_properties(mass_properties);
+            }
+
+            let body_handle = bodies.0.insert(rb.build());
+            let mut collider_handles = vec![];
+
+            for (pos, shape) in &link.collisions {
+                let mut co = ColliderBuilder::new(shape.clone()).position(*pos);
+                if link.mass_properties.is_some() {
+                    co = co.density(0.0);
+                }
+
+                let co_handle =
+                    colliders
+                        .0
+                        .insert_with_parent(co.build(), body_handle, &mut bodies.0);
+                collider_handles.push(utils::flat_handle(co_handle.0));
+            }
+
+            body_handles.push(body_handle);
+            robot.link_names.push(link.name.clone());
+            robot.bodies.push(utils::flat_handle(body_handle.0));
+            robot.colliders.push(collider_handles);
+        }
+
+        for joint in &joints {
+            let data = match joint.to_generic_joint(jointCollisions) {
+                Some(data) => data,
+                None => continue,
+            };
+            let body1 = body_handles[link_ids[&joint.parent[..]]];
+            let body2 = body_handles[link_ids[&joint.child[..]]];
+
+            let handle = if multibody {
+                match multibodyJoints.0.insert(body1, body2, data, true) {
+                    Some(handle) => utils::flat_handle(handle.0),
+                    None => {
+                        // Remove everything inserted so far, with the attached colliders
+                        // and joints.
+                        let mut islands = IslandManager::new();
+                        for handle in &body_handles {
+                            bodies.0.remove(
+                                *handle,
+                                &mut islands,
+                                &mut colliders.0,
+                                &mut impulseJoints.0,
+                                &mut multibodyJoints.0,
+                                true,
+                            );
+                        }
+
+                        return Err(UrdfError::MultibodyInsertion {
+                            joint: joint.name.clone(),
+                        }
+                        .into());
+                    }
+                }
+            } else {
+                utils::flat_handle(impulseJoints.0.insert(body1, body2, data, true).0)
+            };
+
+            robot.joint_names.push(joint.name.clone());
+            robot.joints.push(handle);
+        }
+
+        Ok(robot)
+    }
+
+    /// The name of the robot.
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    /// Are the joints of this robot multibody joints?
+    pub fn isMultibody(&self) -> bool {
+        self.multibody
+    }
+
+    /// The number of links of this robot.
+    pub fn numLinks(&self) -> usize {
+        self.link_names.len()
+    }
+
+    /// The name of the `i`-th link of this robot.
+    pub fn linkName(&self, i: usize) -> Option<String> {
+        self.link_names.get(i).cloned()
+    }
+
+    /// The handle of the rigid-body created for the `i`-th link of this robot.
+    pub fn linkBody(&self, i: usize) -> Option<FlatHandle> {
+        self.bodies.get(i).copied()
+    }
+
+    /// The handles of the colliders created for the `i`-th link of this robot.
+    pub fn linkColliders(&self, i: usize) -> Float64Array {
+        let colliders = self.colliders.get(i).map(|c| &c[..]).unwrap_or(&[]);
+        Float64Array::from(colliders)
+    }
+
+    /// The number of joints created for this robot.
+    pub fn numJoints(&self) -> usize {
+        self.joint_names.len()
+    }
+
+    /// The name of the `i`-th joint created for this robot.
+    pub fn jointName(&self, i: usize) -> Option<String> {
+        self.joint_names.get(i).cloned()
+    }
+
+    /// The handle of the `i`-th joint created for this robot.
+    pub fn joint(&self, i: usize) -> Option<FlatHandle> {
+        self.joints.get(i).copied()
+    }
+}
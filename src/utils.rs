@@ -1,3 +1,4 @@
+use crate::geometry::RawColliderSet;
 use rapier::data::Index;
 use rapier::dynamics::{ImpulseJointHandle, MultibodyJointHandle, RigidBodyHandle};
 use rapier::geometry::{Collider, ColliderHandle};
@@ -59,17 +60,31 @@ pub fn flat_handle(id: Index) -> FlatHandle {
 //     i as u32 | ((g as u32) << 16)
 // }
 
+/// Calls `f` with a collider predicate combining the JS `filter` function (if it is a
+/// function) and the exclusion of the colliders disabled from `colliders`.
 #[inline(always)]
 pub fn with_filter<T>(
+    colliders: &RawColliderSet,
     filter: &js_sys::Function,
     f: impl FnOnce(Option<&dyn Fn(ColliderHandle, &Collider) -> bool>) -> T,
 ) -> T {
-    if filter.is_function() {
-        let filtercb = move |handle: ColliderHandle, _: &Collider| match filter
-            .call1(&JsValue::null(), &JsValue::from(flat_handle(handle.0)))
-        {
-            Err(_) => true,
-            Ok(val) => val.as_bool().unwrap_or(true),
+    let check_filter = filter.is_function();
+    let check_enabled = colliders.has_disabled_colliders();
+
+    if check_filter || check_enabled {
+        let filtercb = move |handle: ColliderHandle, _: &Collider| {
+            if check_enabled && !colliders.is_enabled(handle) {
+                return false;
+            }
+
+            if !check_filter {
+                return true;
+            }
+
+            match filter.call1(&JsValue::null(), &JsValue::from(flat_handle(handle.0))) {
+                Err(_) => true,
+                Ok(val) => val.as_bool().unwrap_or(true),
+            }
         };
 
         f(Some(&filtercb))